@@ -12,6 +12,15 @@
 //!
 //! If required tools are not available, the script will skip those fixtures
 //! and emit warnings. Tests will gracefully skip when fixtures are missing.
+//!
+//! The `package_validator::fixtures` module now provides a toolchain-free alternative that
+//! synthesizes ELF objects and `.deb`/`.rpm` archives directly in Rust, so callers who don't
+//! want to depend on `gcc`/`patchelf`/`fakeroot`/`dpkg-deb`/`rpmbuild` being present can use it
+//! directly instead of these fixtures. This script isn't rewired to call it itself: a build
+//! script is its own Cargo target and can't depend on the library crate it builds without a
+//! `[build-dependencies]` entry pointing back at this package, which would be a circular
+//! dependency. The shell-based generation below is left in place for environments that do have
+//! the tools installed.
 
 use std::env;
 use std::fs;
@@ -1,9 +1,27 @@
 // Copyright (C) 2026 Checkmk GmbH - License: GNU General Public License v2
 // This file is part of Checkmk (https://checkmk.com). It is subject to the terms and
 // conditions defined in the file COPYING, which is part of this source code package.
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use std::path::PathBuf;
 
+/// Output format for the `--report` file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// The tool's own JSON report structure.
+    Json,
+    /// SARIF 2.1.0, for uploading directly to code-scanning dashboards.
+    Sarif,
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Json => write!(f, "json"),
+            Self::Sarif => write!(f, "sarif"),
+        }
+    }
+}
+
 #[derive(Parser)]
 #[command(name = "package_validator")]
 #[command(version)]
@@ -18,8 +36,136 @@ pub(crate) struct Args {
     #[arg(
         long,
         long_help = "Path to a text file of known system dependencies.\n\
-                Each line contains an exact dependency name.\n\
+                Each line contains an exact dependency name, a glob pattern (e.g. libm.so.*),\n\
+                or a symbol version baseline override for a provider (e.g. GLIBCXX <= 3.4.19),\n\
+                which takes precedence over --max-glibc for that provider.\n\
                 Empty lines and lines starting with # are ignored."
     )]
     pub system_dependencies: Option<PathBuf>,
+
+    #[arg(
+        long,
+        long_help = "Treat a bare soname entry in --system-dependencies (e.g. libfoo.so, with\n\
+                no version suffix) as matching any versioned soname the dynamic linker would\n\
+                accept as an instance of it (libfoo.so.6, libfoo.so.6.0, ...), in addition to\n\
+                exact and glob matches."
+    )]
+    pub normalize_sonames: bool,
+
+    #[arg(
+        long,
+        long_help = "Maximum glibc/libstdc++ version ELF binaries may require, e.g. `2.17`.\n\
+                Binaries whose `.gnu.version_r` section requires a newer versioned symbol\n\
+                (e.g. GLIBC_2.27) than this baseline are reported as errors."
+    )]
+    pub max_glibc: Option<String>,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = OutputFormat::Json,
+        long_help = "Output format for the --report file: the tool's own JSON structure, or a\n\
+                SARIF 2.1.0 log suitable for code-scanning dashboards."
+    )]
+    pub format: OutputFormat,
+
+    #[arg(
+        long,
+        long_help = "Root directory to resolve ld.so.conf and the trusted default library\n\
+                directories (/lib, /usr/lib, etc.) against, instead of the host's own root.\n\
+                Use this to analyze a staged install tree or container rootfs.\n\
+                Defaults to the host's own root (/)."
+    )]
+    pub sysroot: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_delimiter = ':',
+        long_help = "Emulated LD_LIBRARY_PATH, as a colon-separated list of directories,\n\
+                searched in the same order the dynamic linker would (after RPATH, before\n\
+                RUNPATH)."
+    )]
+    pub ld_library_path: Vec<PathBuf>,
+
+    #[arg(
+        long,
+        long_help = "Extract the package into this directory (instead of discarding the\n\
+                extraction tree), rewrite every ELF's absolute RPATH/RUNPATH entries into\n\
+                portable $ORIGIN-relative form using patchelf, and re-validate the result.\n\
+                The corrected, extracted package tree is left at this path. An ELF whose\n\
+                RPATH/RUNPATH is invalid in a way the normal validation already rejects\n\
+                (e.g. a bare relative path) fails extraction before this can run."
+    )]
+    pub fix: Option<PathBuf>,
+
+    #[arg(
+        long,
+        long_help = "Print a cargo-tree-like rendering of each top-level binary's transitive\n\
+                dependency closure to the console, annotated with each dependency's resolution\n\
+                status and kind, in addition to the usual summary tables."
+    )]
+    pub tree: bool,
+
+    #[arg(
+        long,
+        long_help = "Print the inverse of the missing-dependencies table: one row per\n\
+                missing soname, listing every ELF file that requires it, sorted by impact\n\
+                (most dependents first). Far more actionable than a per-file list when a\n\
+                single missing system library is breaking many binaries at once."
+    )]
+    pub by_dependency: bool,
+
+    #[arg(
+        long,
+        long_help = "Dependency name or glob pattern (e.g. libfoo.*) to treat as tolerable\n\
+                even when missing, e.g. a library known to be provided at runtime by the\n\
+                deploy target but absent from this validation environment. May be repeated."
+    )]
+    pub ignore_dependency: Vec<String>,
+
+    #[arg(
+        long,
+        long_help = "Maximum number of distinct missing dependencies (after\n\
+                --ignore-dependency filtering) to tolerate before validation fails.\n\
+                Defaults to 0, i.e. any missing dependency fails."
+    )]
+    pub max_missing: Option<usize>,
+
+    #[arg(
+        long,
+        long_help = "Downgrade dependencies of DependencyKind::Unknown (could not be\n\
+                classified as system- or package-provided) from validation failures to\n\
+                warnings."
+    )]
+    pub downgrade_unknown_kind: bool,
+
+    #[arg(
+        long,
+        long_help = "Path to a policy file widening the validation policy beyond\n\
+                --ignore-dependency/--max-missing/--downgrade-unknown-kind: one directive per\n\
+                line, either `ignore <NAME_OR_GLOB>`, `max-missing-unique <N>` (tightened to\n\
+                the lower of this and any CLI value), or the bare `downgrade-unknown-kind`.\n\
+                Empty lines and lines starting with # are ignored."
+    )]
+    pub policy: Option<PathBuf>,
+
+    #[arg(
+        long,
+        long_help = "Path to a fingerprint cache file. When set, a package whose contents are\n\
+                unchanged (by size+mtime, falling back to a content hash) since the last run\n\
+                that wrote this cache skips extraction and re-validation entirely, reusing the\n\
+                prior report and exit status. Changing any flag that affects dependency\n\
+                resolution or validation (--system-dependencies, --normalize-sonames,\n\
+                --max-glibc, --sysroot, --ld-library-path, --ignore-dependency, --max-missing,\n\
+                --downgrade-unknown-kind, --policy) invalidates the whole cache. Ignored if\n\
+                --no-cache is set."
+    )]
+    pub cache_file: Option<PathBuf>,
+
+    #[arg(
+        long,
+        long_help = "Ignore --cache-file for this run: always fully re-validate; the cache\n\
+                file is neither read nor updated."
+    )]
+    pub no_cache: bool,
 }
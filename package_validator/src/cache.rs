@@ -0,0 +1,422 @@
+// Copyright (C) 2026 Checkmk GmbH - License: GNU General Public License v2
+// This file is part of Checkmk (https://checkmk.com). It is subject to the terms and
+// conditions defined in the file COPYING, which is part of this source code package.
+
+//! An on-disk sidecar cache of prior validation outcomes, keyed by each validated package's own
+//! content fingerprint, so re-running `package_validator` against an artifact that hasn't
+//! changed since the last run can skip re-extracting and re-resolving it entirely. Modeled on
+//! Cargo's dep-info files: a small binary format, not JSON, since it's read and rewritten on
+//! every invocation and may accumulate one entry per package validated in a shared CI cache
+//! location.
+//!
+//! Fingerprinting is scoped to the package artifact itself (its `.deb`/`.rpm`/`.ipk` file)
+//! rather than each file inside it: `Package` discards its extraction tree's raw bytes once
+//! parsing is done, so per-inner-file content hashes aren't available past that point without
+//! persisting the whole extraction tree (see `Package::new_with_persistence`). The package
+//! artifact's own bytes, by contrast, are never touched by `package_validator` and remain on
+//! disk for the whole run, making them the natural fingerprint root.
+
+use anyhow::{Context, Result};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::hash::Hasher;
+use std::io::{BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Whether a cached validation run passed or failed `validate_report`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CachedVerdict {
+    Clean,
+    Failed,
+}
+
+impl CachedVerdict {
+    fn to_byte(self) -> u8 {
+        match self {
+            Self::Clean => 0,
+            Self::Failed => 1,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Result<Self> {
+        match byte {
+            0 => Ok(Self::Clean),
+            1 => Ok(Self::Failed),
+            other => Err(anyhow::anyhow!("Invalid cached verdict byte: {other}")),
+        }
+    }
+}
+
+/// A cache hit: the verdict and the exact report-file bytes produced last time, so the caller
+/// can write them back out without re-running the resolver.
+pub struct CachedOutcome {
+    pub verdict: CachedVerdict,
+    pub report_bytes: Vec<u8>,
+}
+
+/// A package's fingerprint at the time it was last validated: size and modification time are
+/// checked first since they're nearly free; the content hash is only (re)computed when either
+/// differs, to tell whether the file's bytes actually changed or it was merely touched/recopied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Fingerprint {
+    size: u64,
+    mtime_secs: u64,
+    hash: u64,
+}
+
+impl Fingerprint {
+    fn for_file(path: &Path) -> Result<Self> {
+        let metadata = fs::metadata(path)
+            .with_context(|| format!("Failed to read metadata for {}", path.display()))?;
+        Ok(Self {
+            size: metadata.len(),
+            mtime_secs: Self::mtime_secs(&metadata),
+            hash: Self::hash_contents(path)?,
+        })
+    }
+
+    fn mtime_secs(metadata: &fs::Metadata) -> u64 {
+        metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |duration| duration.as_secs())
+    }
+
+    /// Hash the file's full contents with `DefaultHasher`, which (unlike the `RandomState` used
+    /// by `HashMap`) always starts from the same fixed keys, so identical bytes hash identically
+    /// across runs. Not a cryptographic hash -- this is a local change-detection cache, not a
+    /// security boundary, so a fast, dependency-free hash is enough.
+    fn hash_contents(path: &Path) -> Result<u64> {
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open {} for hashing", path.display()))?;
+        let mut reader = BufReader::new(file);
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        let mut buffer = [0u8; 64 * 1024];
+        loop {
+            let read = reader
+                .read(&mut buffer)
+                .with_context(|| format!("Failed to read {} for hashing", path.display()))?;
+            if read == 0 {
+                break;
+            }
+            hasher.write(&buffer[..read]);
+        }
+        Ok(hasher.finish())
+    }
+
+    /// Whether `path`'s current state still matches this fingerprint: the cheap size/mtime check
+    /// first, falling back to a content hash comparison if either differs (e.g. the file was
+    /// recopied with a fresh mtime but identical bytes).
+    fn still_matches(&self, path: &Path) -> Result<bool> {
+        let metadata = fs::metadata(path)
+            .with_context(|| format!("Failed to read metadata for {}", path.display()))?;
+        if metadata.len() == self.size && Self::mtime_secs(&metadata) == self.mtime_secs {
+            return Ok(true);
+        }
+        Ok(Self::hash_contents(path)? == self.hash)
+    }
+}
+
+struct CacheRecord {
+    fingerprint: Fingerprint,
+    verdict: CachedVerdict,
+    format_tag: u8,
+    report_bytes: Vec<u8>,
+}
+
+/// A fingerprint-backed cache of prior validation outcomes, persisted as a small binary sidecar:
+/// a `u64` LE epoch, followed by a `u32` LE record count, followed by one record per package of
+/// `[verdict: u8][format_tag: u8][path_len: u32 LE][path bytes][size: u64 LE][mtime_secs: u64
+/// LE][hash: u64 LE][report_len: u32 LE][report bytes]`.
+///
+/// The epoch is a hash of whatever should invalidate every entry at once when it changes (e.g.
+/// the system-dependencies file's own bytes, so editing it forces full revalidation): a stored
+/// cache whose epoch doesn't match the current one is treated as empty.
+pub struct ValidationCache {
+    epoch: u64,
+    entries: HashMap<PathBuf, CacheRecord>,
+    touched: HashSet<PathBuf>,
+}
+
+impl ValidationCache {
+    /// Load the cache at `path`, keyed to a hash of `epoch_input`. If the file doesn't exist, is
+    /// corrupt, or its recorded epoch doesn't match, an empty cache is returned -- every package
+    /// is then treated as uncached -- rather than erroring the whole run out over a stale or
+    /// unreadable cache file.
+    #[must_use]
+    pub fn load(path: &Path, epoch_input: &[u8]) -> Self {
+        let epoch = Self::hash_bytes(epoch_input);
+        Self::try_load(path, epoch).unwrap_or_else(|| Self {
+            epoch,
+            entries: HashMap::new(),
+            touched: HashSet::new(),
+        })
+    }
+
+    fn try_load(path: &Path, epoch: u64) -> Option<Self> {
+        let bytes = fs::read(path).ok()?;
+        let mut cursor = 0usize;
+        let stored_epoch = Self::read_u64(&bytes, &mut cursor)?;
+        if stored_epoch != epoch {
+            return None;
+        }
+        let count = Self::read_u32(&bytes, &mut cursor)?;
+        let mut entries = HashMap::new();
+        for _ in 0..count {
+            let verdict = CachedVerdict::from_byte(*bytes.get(cursor)?).ok()?;
+            cursor += 1;
+            let format_tag = *bytes.get(cursor)?;
+            cursor += 1;
+            let path_len = Self::read_u32(&bytes, &mut cursor)? as usize;
+            let path_bytes = bytes.get(cursor..cursor + path_len)?;
+            cursor += path_len;
+            let relative_path = PathBuf::from(std::str::from_utf8(path_bytes).ok()?);
+            let size = Self::read_u64(&bytes, &mut cursor)?;
+            let mtime_secs = Self::read_u64(&bytes, &mut cursor)?;
+            let hash = Self::read_u64(&bytes, &mut cursor)?;
+            let report_len = Self::read_u32(&bytes, &mut cursor)? as usize;
+            let report_bytes = bytes.get(cursor..cursor + report_len)?.to_vec();
+            cursor += report_len;
+            entries.insert(
+                relative_path,
+                CacheRecord {
+                    fingerprint: Fingerprint { size, mtime_secs, hash },
+                    verdict,
+                    format_tag,
+                    report_bytes,
+                },
+            );
+        }
+        Some(Self {
+            epoch,
+            entries,
+            touched: HashSet::new(),
+        })
+    }
+
+    /// Look up the cached outcome for `package_path`, if its fingerprint still matches what was
+    /// last recorded and it was last validated in the same `format_tag` (a cached SARIF report
+    /// can't stand in for a requested JSON one, or vice versa). A hit is automatically carried
+    /// forward to the next `store` call; a miss is not -- the caller must `record` a fresh
+    /// outcome for it to persist.
+    pub fn lookup(&mut self, package_path: &Path, format_tag: u8) -> Option<CachedOutcome> {
+        let record = self.entries.get(package_path)?;
+        if record.format_tag != format_tag {
+            return None;
+        }
+        if record.fingerprint.still_matches(package_path).ok()? {
+            self.touched.insert(package_path.to_path_buf());
+            Some(CachedOutcome {
+                verdict: record.verdict,
+                report_bytes: record.report_bytes.clone(),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Record `package_path`'s fresh outcome for the next run.
+    ///
+    /// # Errors
+    /// Returns an error if `package_path`'s metadata or contents can't be read to compute its
+    /// fingerprint.
+    pub fn record(
+        &mut self,
+        package_path: &Path,
+        format_tag: u8,
+        verdict: CachedVerdict,
+        report_bytes: Vec<u8>,
+    ) -> Result<()> {
+        let fingerprint = Fingerprint::for_file(package_path)?;
+        self.entries.insert(
+            package_path.to_path_buf(),
+            CacheRecord {
+                fingerprint,
+                verdict,
+                format_tag,
+                report_bytes,
+            },
+        );
+        self.touched.insert(package_path.to_path_buf());
+        Ok(())
+    }
+
+    /// Persist the cache to `path`, dropping any entry that wasn't looked up or recorded this
+    /// run -- e.g. a package removed from a multi-package cache directory, or one left over from
+    /// before an epoch change.
+    ///
+    /// # Errors
+    /// Returns an error if `path` cannot be written.
+    pub fn store(&self, path: &Path) -> Result<()> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&self.epoch.to_le_bytes());
+
+        let live_entries: Vec<(&PathBuf, &CacheRecord)> = self
+            .entries
+            .iter()
+            .filter(|(entry_path, _)| self.touched.contains(*entry_path))
+            .collect();
+        bytes.extend_from_slice(&u32::try_from(live_entries.len()).unwrap_or(u32::MAX).to_le_bytes());
+
+        for (entry_path, record) in live_entries {
+            bytes.push(record.verdict.to_byte());
+            bytes.push(record.format_tag);
+            let path_bytes = entry_path.to_string_lossy().into_owned().into_bytes();
+            bytes.extend_from_slice(&u32::try_from(path_bytes.len()).unwrap_or(u32::MAX).to_le_bytes());
+            bytes.extend_from_slice(&path_bytes);
+            bytes.extend_from_slice(&record.fingerprint.size.to_le_bytes());
+            bytes.extend_from_slice(&record.fingerprint.mtime_secs.to_le_bytes());
+            bytes.extend_from_slice(&record.fingerprint.hash.to_le_bytes());
+            bytes.extend_from_slice(
+                &u32::try_from(record.report_bytes.len()).unwrap_or(u32::MAX).to_le_bytes(),
+            );
+            bytes.extend_from_slice(&record.report_bytes);
+        }
+
+        let mut file = File::create(path)
+            .with_context(|| format!("Failed to create cache file: {}", path.display()))?;
+        file.write_all(&bytes)
+            .with_context(|| format!("Failed to write cache file: {}", path.display()))?;
+        Ok(())
+    }
+
+    fn hash_bytes(bytes: &[u8]) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        hasher.write(bytes);
+        hasher.finish()
+    }
+
+    fn read_u32(bytes: &[u8], cursor: &mut usize) -> Option<u32> {
+        let value = u32::from_le_bytes(bytes.get(*cursor..*cursor + 4)?.try_into().ok()?);
+        *cursor += 4;
+        Some(value)
+    }
+
+    fn read_u64(bytes: &[u8], cursor: &mut usize) -> Option<u64> {
+        let value = u64::from_le_bytes(bytes.get(*cursor..*cursor + 8)?.try_into().ok()?);
+        *cursor += 8;
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write as _;
+    use tempfile::NamedTempFile;
+
+    fn write_package(contents: &[u8]) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(contents).unwrap();
+        file.flush().unwrap();
+        file
+    }
+
+    #[test]
+    fn test_miss_on_empty_cache() {
+        let package = write_package(b"package bytes");
+        let mut cache = ValidationCache::load(Path::new("/nonexistent/cache/file"), b"");
+        assert!(cache.lookup(package.path(), 0).is_none());
+    }
+
+    #[test]
+    fn test_hit_after_record_with_matching_format() {
+        let package = write_package(b"package bytes");
+        let mut cache = ValidationCache::load(Path::new("/nonexistent/cache/file"), b"");
+        cache
+            .record(package.path(), 0, CachedVerdict::Clean, b"report".to_vec())
+            .unwrap();
+
+        let outcome = cache.lookup(package.path(), 0).unwrap();
+        assert_eq!(outcome.verdict, CachedVerdict::Clean);
+        assert_eq!(outcome.report_bytes, b"report");
+    }
+
+    #[test]
+    fn test_miss_on_format_tag_mismatch() {
+        let package = write_package(b"package bytes");
+        let mut cache = ValidationCache::load(Path::new("/nonexistent/cache/file"), b"");
+        cache
+            .record(package.path(), 0, CachedVerdict::Clean, b"report".to_vec())
+            .unwrap();
+
+        assert!(cache.lookup(package.path(), 1).is_none());
+    }
+
+    #[test]
+    fn test_round_trip_through_store_and_load() {
+        let package = write_package(b"package bytes");
+        let cache_file = NamedTempFile::new().unwrap();
+
+        let mut cache = ValidationCache::load(Path::new("/nonexistent/cache/file"), b"");
+        cache
+            .record(package.path(), 1, CachedVerdict::Failed, b"sarif report".to_vec())
+            .unwrap();
+        cache.store(cache_file.path()).unwrap();
+
+        let mut reloaded = ValidationCache::load(cache_file.path(), b"");
+        let outcome = reloaded.lookup(package.path(), 1).unwrap();
+        assert_eq!(outcome.verdict, CachedVerdict::Failed);
+        assert_eq!(outcome.report_bytes, b"sarif report");
+    }
+
+    #[test]
+    fn test_epoch_change_invalidates_cache() {
+        let package = write_package(b"package bytes");
+        let cache_file = NamedTempFile::new().unwrap();
+
+        let mut cache = ValidationCache::load(Path::new("/nonexistent/cache/file"), b"epoch-a");
+        cache
+            .record(package.path(), 0, CachedVerdict::Clean, b"report".to_vec())
+            .unwrap();
+        cache.store(cache_file.path()).unwrap();
+
+        let mut reloaded = ValidationCache::load(cache_file.path(), b"epoch-b");
+        assert!(reloaded.lookup(package.path(), 0).is_none());
+    }
+
+    #[test]
+    fn test_changed_package_contents_invalidate_entry() {
+        let mut package = write_package(b"original bytes");
+        let cache_file = NamedTempFile::new().unwrap();
+
+        let mut cache = ValidationCache::load(Path::new("/nonexistent/cache/file"), b"");
+        cache
+            .record(package.path(), 0, CachedVerdict::Clean, b"report".to_vec())
+            .unwrap();
+        cache.store(cache_file.path()).unwrap();
+
+        package.write_all(b"extra bytes, different size").unwrap();
+        package.flush().unwrap();
+
+        let mut reloaded = ValidationCache::load(cache_file.path(), b"");
+        assert!(reloaded.lookup(package.path(), 0).is_none());
+    }
+
+    #[test]
+    fn test_entries_not_touched_this_run_are_pruned_on_store() {
+        let kept = write_package(b"kept package");
+        let dropped = write_package(b"dropped package");
+        let cache_file = NamedTempFile::new().unwrap();
+
+        let mut cache = ValidationCache::load(Path::new("/nonexistent/cache/file"), b"");
+        cache
+            .record(kept.path(), 0, CachedVerdict::Clean, b"kept report".to_vec())
+            .unwrap();
+        cache
+            .record(dropped.path(), 0, CachedVerdict::Clean, b"dropped report".to_vec())
+            .unwrap();
+        cache.store(cache_file.path()).unwrap();
+
+        // Reload, only touch `kept` this run, then store again.
+        let mut reloaded = ValidationCache::load(cache_file.path(), b"");
+        assert!(reloaded.lookup(kept.path(), 0).is_some());
+        reloaded.store(cache_file.path()).unwrap();
+
+        let mut final_cache = ValidationCache::load(cache_file.path(), b"");
+        assert!(final_cache.lookup(kept.path(), 0).is_some());
+        assert!(final_cache.lookup(dropped.path(), 0).is_none());
+    }
+}
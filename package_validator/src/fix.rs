@@ -0,0 +1,102 @@
+// Copyright (C) 2026 Checkmk GmbH - License: GNU General Public License v2
+// This file is part of Checkmk (https://checkmk.com). It is subject to the terms and
+// conditions defined in the file COPYING, which is part of this source code package.
+
+//! Rewrites each ELF's absolute RPATH/RUNPATH entries into portable `$ORIGIN`-relative form
+//! using `patchelf`, then re-validates the result. Invoked by `--fix`; see `main.rs`.
+//!
+//! Extraction persists the package tree to disk instead of discarding it (the normal validation
+//! flow parses everything in memory and throws the extraction tree away), since `patchelf` needs
+//! a real file to patch. An ELF whose RPATH/RUNPATH is already invalid in a way `Elf::validate`
+//! rejects (e.g. a bare relative path with no `$ORIGIN`) fails extraction before this runs at
+//! all; `--fix` only rewrites already-valid-but-absolute entries into a portable form.
+
+use anyhow::{bail, Context, Result};
+use package_validator::package::{Elf, ExtractionFilter, Package, PackageFile};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Extract `package_path` into `output_dir`, rewrite every ELF's RPATH/RUNPATH entries into
+/// `$ORIGIN`-relative form via `patchelf`, and re-parse each patched file to confirm it now
+/// validates. Leaves the corrected, extracted package tree at `output_dir`.
+///
+/// # Errors
+/// Returns an error if extraction, `patchelf`, or re-validation of a patched file fails.
+pub(crate) fn fix_package(package_path: &Path, output_dir: &Path) -> Result<()> {
+    let package = Package::new_with_persistence(
+        package_path.to_path_buf(),
+        &ExtractionFilter::unfiltered(),
+        output_dir,
+    )
+    .with_context(|| {
+        format!(
+            "Failed to extract package for fixing: {}",
+            package_path.display()
+        )
+    })?;
+
+    for (in_package_path, file) in package.files() {
+        if let PackageFile::Elf(elf) = file {
+            fix_elf(output_dir, in_package_path, elf)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Rewrite one ELF's absolute RPATH/RUNPATH directories into `$ORIGIN`-relative form in place.
+/// Does nothing if the ELF has no RPATH/RUNPATH, or if every entry is already a dynamic string
+/// token (nothing left to make more portable).
+fn fix_elf(output_dir: &Path, in_package_path: &Path, elf: &Elf) -> Result<()> {
+    if !elf
+        .rpath()
+        .iter()
+        .chain(elf.runpath())
+        .any(|entry| entry.starts_with('/'))
+    {
+        return Ok(());
+    }
+
+    let on_disk_path =
+        output_dir.join(in_package_path.strip_prefix("/").unwrap_or(in_package_path));
+    // Relative to where the binary sits inside the package, since the lib dirs are package paths.
+    let binary_dir = in_package_path.parent().unwrap_or_else(|| Path::new("/"));
+    // Keep every entry in its original order; only absolute ones are rewritten.
+    let mut seen = HashSet::new();
+    let rpath_value = elf
+        .rpath()
+        .iter()
+        .chain(elf.runpath())
+        .map(|entry| {
+            if entry.starts_with('/') {
+                elf.fix_rpaths(binary_dir, &[PathBuf::from(entry)]).concat()
+            } else {
+                entry.clone()
+            }
+        })
+        .filter(|entry| seen.insert(entry.clone()))
+        .collect::<Vec<_>>()
+        .join(":");
+
+    let status = Command::new("patchelf")
+        .args(["--force-rpath", "--set-rpath", &rpath_value])
+        .arg(&on_disk_path)
+        .status()
+        .with_context(|| format!("Failed to run patchelf on {}", on_disk_path.display()))?;
+    if !status.success() {
+        bail!(
+            "patchelf exited with a failure status for {}",
+            on_disk_path.display()
+        );
+    }
+
+    Elf::from_path(&on_disk_path).with_context(|| {
+        format!(
+            "Fixed RPATH still fails validation: {}",
+            on_disk_path.display()
+        )
+    })?;
+
+    Ok(())
+}
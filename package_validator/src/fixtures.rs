@@ -0,0 +1,475 @@
+// Copyright (C) 2026 Checkmk GmbH - License: GNU General Public License v2
+// This file is part of Checkmk (https://checkmk.com). It is subject to the terms and
+// conditions defined in the file COPYING, which is part of this source code package.
+
+//! Synthesizes ELF objects and `.deb`/`.rpm` packages directly in Rust, without shelling out to
+//! `gcc`, `patchelf`, `fakeroot`, `dpkg-deb`, or `rpmbuild`. Intended for test fixtures (this
+//! crate's own, and downstream consumers crafting packages to exercise their own RPATH
+//! policies): give it a declarative list of `ArtifactSpec`s -- each one a binary or shared
+//! library, its install path, its `DT_NEEDED` sonames, and its RPATH/RUNPATH setting -- and
+//! `build_package` produces a valid archive on disk.
+//!
+//! The ELF objects this module emits are deliberately minimal: a single `PT_LOAD` segment
+//! covering the whole file (so file offsets double as virtual addresses, with no linking or
+//! loading implied) plus a `PT_DYNAMIC` segment and `.dynamic`/`.dynstr` content, which is all
+//! `package::elf::Elf::parse` (via `goblin`) needs to recover `DT_NEEDED`/`DT_RPATH`/
+//! `DT_RUNPATH`. They have no section headers, no code, and are not executable -- only a
+//! structurally valid dynamic-linking view.
+//!
+//! `.deb` archives are assembled with the `ar`, `tar`, and `flate2` crates this crate already
+//! depends on for reading them (see `package::deb`). `.rpm` archives are built to the exact byte
+//! layout `package::rpm::RpmExtractor::extract_native` reads back: a lead, an empty signature
+//! header, an empty main header (so the default `gzip` payload compressor applies), and a
+//! gzip-compressed `newc` CPIO payload.
+
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Whether a synthesized artifact is a top-level executable or a shared library, mirroring
+/// `package::elf::ElfType::Executable`/`SharedObject`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtifactKind {
+    Executable,
+    SharedLibrary,
+}
+
+/// An artifact's `DT_RPATH`/`DT_RUNPATH` setting. At most one applies, matching how a real
+/// linker emits either an old-style `RPATH` or (with `--enable-new-dtags`, the modern default)
+/// a `RUNPATH`, never both for the same value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RpathSetting {
+    /// Neither `DT_RPATH` nor `DT_RUNPATH` is present.
+    None,
+    Rpath(String),
+    Runpath(String),
+}
+
+/// A declarative description of one ELF artifact to synthesize: its kind, its path inside the
+/// package (relative, e.g. `usr/lib/libfoo.so.1`), the sonames it depends on, and its
+/// RPATH/RUNPATH setting.
+#[derive(Debug, Clone)]
+pub struct ArtifactSpec {
+    kind: ArtifactKind,
+    install_path: PathBuf,
+    needed: Vec<String>,
+    rpath: RpathSetting,
+}
+
+impl ArtifactSpec {
+    /// Start a new artifact with no `DT_NEEDED` entries and no RPATH/RUNPATH.
+    ///
+    /// `install_path` must be relative to the package root (e.g. `usr/bin/hello`), since it
+    /// becomes both the tar/CPIO entry name and the path the validator reports findings against.
+    #[must_use]
+    pub fn new(kind: ArtifactKind, install_path: impl Into<PathBuf>) -> Self {
+        Self {
+            kind,
+            install_path: install_path.into(),
+            needed: Vec::new(),
+            rpath: RpathSetting::None,
+        }
+    }
+
+    /// Add a `DT_NEEDED` entry for `soname`.
+    #[must_use]
+    pub fn needed(mut self, soname: impl Into<String>) -> Self {
+        self.needed.push(soname.into());
+        self
+    }
+
+    /// Set this artifact's RPATH/RUNPATH setting, replacing any previous one.
+    #[must_use]
+    pub fn rpath(mut self, setting: RpathSetting) -> Self {
+        self.rpath = setting;
+        self
+    }
+}
+
+/// The package archive format to assemble.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageFormat {
+    Deb,
+    Rpm,
+}
+
+/// Synthesize a minimal ELF64 little-endian object (an `ET_EXEC` or `ET_DYN`, per `spec.kind`)
+/// with `spec.needed`'s sonames as `DT_NEEDED` entries and `spec.rpath` as `DT_RPATH`/
+/// `DT_RUNPATH`, readable by `package::elf::Elf::parse`.
+#[must_use]
+pub fn build_elf(spec: &ArtifactSpec) -> Vec<u8> {
+    const EHDR_SIZE: usize = 64;
+    const PHDR_SIZE: usize = 56;
+    const PT_LOAD: u32 = 1;
+    const PT_DYNAMIC: u32 = 2;
+    const DT_NEEDED: i64 = 1;
+    const DT_RPATH: i64 = 15;
+    const DT_STRTAB: i64 = 5;
+    const DT_STRSZ: i64 = 10;
+    const DT_RUNPATH: i64 = 29;
+    const DT_NULL: i64 = 0;
+    const EM_X86_64: u16 = 62;
+
+    // .dynstr: a leading NUL (so a zero string-table index means "no name"), then each
+    // referenced string in turn, recording the offset it was written at.
+    let mut dynstr = vec![0u8];
+    let mut push_string = |dynstr: &mut Vec<u8>, value: &str| -> u64 {
+        let offset = dynstr.len() as u64;
+        dynstr.extend_from_slice(value.as_bytes());
+        dynstr.push(0);
+        offset
+    };
+    let needed_offsets: Vec<u64> = spec
+        .needed
+        .iter()
+        .map(|name| push_string(&mut dynstr, name))
+        .collect();
+    let rpath_offset = match &spec.rpath {
+        RpathSetting::Rpath(value) => Some(push_string(&mut dynstr, value)),
+        RpathSetting::Runpath(_) | RpathSetting::None => None,
+    };
+    let runpath_offset = match &spec.rpath {
+        RpathSetting::Runpath(value) => Some(push_string(&mut dynstr, value)),
+        RpathSetting::Rpath(_) | RpathSetting::None => None,
+    };
+
+    // .dynamic: one (tag, val) pair per entry, DT_NULL-terminated.
+    let mut dyn_entries: Vec<(i64, u64)> = needed_offsets.iter().map(|offset| (DT_NEEDED, *offset)).collect();
+    if let Some(offset) = rpath_offset {
+        dyn_entries.push((DT_RPATH, offset));
+    }
+    if let Some(offset) = runpath_offset {
+        dyn_entries.push((DT_RUNPATH, offset));
+    }
+
+    let phdr_offset = EHDR_SIZE;
+    let dynamic_offset = phdr_offset + 2 * PHDR_SIZE;
+    // + DT_STRTAB, DT_STRSZ, DT_NULL, appended below once their values (which depend on
+    // dynstr's final layout) are known.
+    let dynamic_size = (dyn_entries.len() + 3) * 16;
+    let dynstr_offset = dynamic_offset + dynamic_size;
+
+    dyn_entries.push((DT_STRTAB, dynstr_offset as u64));
+    dyn_entries.push((DT_STRSZ, dynstr.len() as u64));
+    dyn_entries.push((DT_NULL, 0));
+
+    let total_size = dynstr_offset + dynstr.len();
+    let mut bytes = Vec::with_capacity(total_size);
+
+    // e_ident
+    bytes.extend_from_slice(&[0x7f, b'E', b'L', b'F']);
+    bytes.push(2); // ELFCLASS64
+    bytes.push(1); // ELFDATA2LSB
+    bytes.push(1); // EV_CURRENT
+    bytes.push(0); // ELFOSABI_NONE
+    bytes.extend_from_slice(&[0u8; 8]); // ABI version + padding
+
+    let e_type: u16 = match spec.kind {
+        ArtifactKind::Executable => 2,    // ET_EXEC
+        ArtifactKind::SharedLibrary => 3, // ET_DYN
+    };
+    bytes.extend_from_slice(&e_type.to_le_bytes());
+    bytes.extend_from_slice(&EM_X86_64.to_le_bytes());
+    bytes.extend_from_slice(&1u32.to_le_bytes()); // e_version
+    bytes.extend_from_slice(&0u64.to_le_bytes()); // e_entry
+    bytes.extend_from_slice(&(phdr_offset as u64).to_le_bytes()); // e_phoff
+    bytes.extend_from_slice(&0u64.to_le_bytes()); // e_shoff (no section headers)
+    bytes.extend_from_slice(&0u32.to_le_bytes()); // e_flags
+    bytes.extend_from_slice(&(EHDR_SIZE as u16).to_le_bytes()); // e_ehsize
+    bytes.extend_from_slice(&(PHDR_SIZE as u16).to_le_bytes()); // e_phentsize
+    bytes.extend_from_slice(&2u16.to_le_bytes()); // e_phnum
+    bytes.extend_from_slice(&0u16.to_le_bytes()); // e_shentsize
+    bytes.extend_from_slice(&0u16.to_le_bytes()); // e_shnum
+    bytes.extend_from_slice(&0u16.to_le_bytes()); // e_shstrndx
+    assert_eq!(bytes.len(), EHDR_SIZE, "ELF header size drifted from EHDR_SIZE");
+
+    // PT_LOAD, identity-mapped (p_vaddr == p_offset) so every vaddr the dynamic section
+    // references -- e.g. DT_STRTAB -- resolves to the same file offset without any further
+    // translation.
+    write_program_header(&mut bytes, PT_LOAD, 0b101, 0, total_size as u64, 0x1000);
+    // PT_DYNAMIC, covering just the .dynamic array itself (not .dynstr).
+    write_program_header(&mut bytes, PT_DYNAMIC, 0b110, dynamic_offset as u64, dynamic_size as u64, 8);
+    assert_eq!(bytes.len(), dynamic_offset, "program headers overran the .dynamic offset");
+
+    for (tag, val) in &dyn_entries {
+        bytes.extend_from_slice(&tag.to_le_bytes());
+        bytes.extend_from_slice(&val.to_le_bytes());
+    }
+    assert_eq!(bytes.len(), dynstr_offset, ".dynamic array overran the .dynstr offset");
+    bytes.extend_from_slice(&dynstr);
+
+    bytes
+}
+
+/// Write one `Elf64_Phdr` (`p_type`, `p_flags`, `p_offset`, `p_vaddr`, `p_paddr`, `p_filesz`,
+/// `p_memsz`, `p_align`) -- `p_offset`/`p_vaddr`/`p_paddr` are always identical here, since every
+/// synthesized ELF identity-maps file offsets to virtual addresses.
+fn write_program_header(bytes: &mut Vec<u8>, p_type: u32, p_flags: u32, offset: u64, filesz: u64, align: u64) {
+    bytes.extend_from_slice(&p_type.to_le_bytes());
+    bytes.extend_from_slice(&p_flags.to_le_bytes());
+    bytes.extend_from_slice(&offset.to_le_bytes());
+    bytes.extend_from_slice(&offset.to_le_bytes());
+    bytes.extend_from_slice(&offset.to_le_bytes());
+    bytes.extend_from_slice(&filesz.to_le_bytes());
+    bytes.extend_from_slice(&filesz.to_le_bytes());
+    bytes.extend_from_slice(&align.to_le_bytes());
+}
+
+/// Build a package of the requested `format` containing `artifacts`, and write it to `dest`.
+///
+/// # Errors
+/// Returns an error if `dest` cannot be created or writing to it fails.
+pub fn build_package(
+    format: PackageFormat,
+    package_name: &str,
+    version: &str,
+    artifacts: &[ArtifactSpec],
+    dest: &Path,
+) -> io::Result<()> {
+    match format {
+        PackageFormat::Deb => build_deb(package_name, version, artifacts, dest),
+        PackageFormat::Rpm => build_rpm(artifacts, dest),
+    }
+}
+
+fn build_deb(package_name: &str, version: &str, artifacts: &[ArtifactSpec], dest: &Path) -> io::Result<()> {
+    let control_content = format!(
+        "Package: {package_name}\n\
+         Version: {version}\n\
+         Architecture: amd64\n\
+         Maintainer: package_validator fixtures <fixtures@example.invalid>\n\
+         Description: Synthetic fixture package\n"
+    );
+    let control_tar_gz = gzip_tar(|builder| append_tar_entry(builder, "./control", control_content.as_bytes(), 0o644))?;
+
+    let data_tar_gz = gzip_tar(|builder| {
+        for artifact in artifacts {
+            let elf_bytes = build_elf(artifact);
+            let name = format!("./{}", artifact.install_path.display());
+            append_tar_entry(builder, &name, &elf_bytes, 0o755)?;
+        }
+        Ok(())
+    })?;
+
+    let file = File::create(dest)?;
+    let mut builder = ar::Builder::new(file);
+    builder.append(&ar::Header::new(b"debian-binary".to_vec(), 4), &b"2.0\n"[..])?;
+    builder.append(
+        &ar::Header::new(b"control.tar.gz".to_vec(), control_tar_gz.len() as u64),
+        control_tar_gz.as_slice(),
+    )?;
+    builder.append(
+        &ar::Header::new(b"data.tar.gz".to_vec(), data_tar_gz.len() as u64),
+        data_tar_gz.as_slice(),
+    )?;
+    Ok(())
+}
+
+/// Build a gzip-compressed tar archive in memory: `write_entries` appends whatever entries it
+/// needs to the tar builder it's given, in order.
+fn gzip_tar(write_entries: impl FnOnce(&mut tar::Builder<flate2::write::GzEncoder<&mut Vec<u8>>>) -> io::Result<()>) -> io::Result<Vec<u8>> {
+    let mut compressed = Vec::new();
+    {
+        let encoder = flate2::write::GzEncoder::new(&mut compressed, flate2::Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        write_entries(&mut builder)?;
+        builder.into_inner()?.finish()?;
+    }
+    Ok(compressed)
+}
+
+fn append_tar_entry<W: Write>(builder: &mut tar::Builder<W>, path: &str, contents: &[u8], mode: u32) -> io::Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_path(path)?;
+    header.set_size(contents.len() as u64);
+    header.set_mode(mode);
+    header.set_cksum();
+    builder.append(&header, contents)
+}
+
+fn build_rpm(artifacts: &[ArtifactSpec], dest: &Path) -> io::Result<()> {
+    // This crate's own native RPM reader (`package::rpm::RpmExtractor::extract_native`) never
+    // validates the lead's own contents beyond its length, so an all-zero lead with just the
+    // conventional magic is enough here.
+    let mut lead = vec![0u8; 96];
+    lead[0..4].copy_from_slice(&[0xed, 0xab, 0xee, 0xdb]);
+    lead[4] = 3; // major version
+
+    // Both headers are left empty (no index entries, no data store): the reader only needs
+    // their 16-byte magic+reserved+count+size prefix to find where each section ends, and an
+    // absent RPMTAG_PAYLOADCOMPRESSOR on the main header means "gzip", matching what we
+    // compress the payload with below.
+    let signature_header = empty_rpm_header();
+    let signature_padding = vec![0u8; (8 - signature_header.len() % 8) % 8];
+    let main_header = empty_rpm_header();
+
+    let payload = build_newc_cpio(artifacts);
+    let mut compressed_payload = Vec::new();
+    {
+        let mut encoder = flate2::write::GzEncoder::new(&mut compressed_payload, flate2::Compression::default());
+        encoder.write_all(&payload)?;
+        encoder.finish()?;
+    }
+
+    let mut file = File::create(dest)?;
+    file.write_all(&lead)?;
+    file.write_all(&signature_header)?;
+    file.write_all(&signature_padding)?;
+    file.write_all(&main_header)?;
+    file.write_all(&compressed_payload)?;
+    Ok(())
+}
+
+fn empty_rpm_header() -> Vec<u8> {
+    let mut header = Vec::with_capacity(16);
+    header.extend_from_slice(&[0x8E, 0xAD, 0xE8, 0x01]); // magic
+    header.extend_from_slice(&[0u8; 4]); // reserved
+    header.extend_from_slice(&0u32.to_be_bytes()); // index_count
+    header.extend_from_slice(&0u32.to_be_bytes()); // data_size
+    header
+}
+
+/// Build a `newc`-format CPIO stream containing a directory entry for each distinct parent
+/// directory under the artifacts' install paths, followed by each artifact's synthesized ELF,
+/// then the `TRAILER!!!` end marker.
+fn build_newc_cpio(artifacts: &[ArtifactSpec]) -> Vec<u8> {
+    const S_IFDIR: u32 = 0o040_000;
+    const S_IFREG: u32 = 0o100_000;
+
+    let mut directories = BTreeSet::new();
+    for artifact in artifacts {
+        let mut ancestor = PathBuf::new();
+        if let Some(parent) = artifact.install_path.parent() {
+            for component in parent.components() {
+                ancestor.push(component);
+                directories.insert(ancestor.clone());
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    for directory in &directories {
+        write_cpio_entry(&mut out, &directory.to_string_lossy(), S_IFDIR | 0o755, &[]);
+    }
+    for artifact in artifacts {
+        let elf_bytes = build_elf(artifact);
+        write_cpio_entry(&mut out, &artifact.install_path.to_string_lossy(), S_IFREG | 0o755, &elf_bytes);
+    }
+    write_cpio_entry(&mut out, "TRAILER!!!", 0, &[]);
+    out
+}
+
+/// Write one `newc` CPIO entry: a 6-byte magic, 13 8-hex-digit fields, the NUL-terminated name,
+/// padding to a 4-byte boundary, the entry's data, then padding to a 4-byte boundary again.
+fn write_cpio_entry(out: &mut Vec<u8>, name: &str, mode: u32, data: &[u8]) {
+    let name_with_nul = format!("{name}\0");
+    out.extend_from_slice(b"070701");
+    let fields: [u32; 13] = [
+        0,                         // ino
+        mode,                      // mode
+        0,                         // uid
+        0,                         // gid
+        1,                         // nlink
+        0,                         // mtime
+        data.len() as u32,         // filesize
+        0,                         // devmajor
+        0,                         // devminor
+        0,                         // rdevmajor
+        0,                         // rdevminor
+        name_with_nul.len() as u32, // namesize
+        0,                         // check
+    ];
+    for field in fields {
+        out.extend_from_slice(format!("{field:08X}").as_bytes());
+    }
+    out.extend_from_slice(name_with_nul.as_bytes());
+    pad_to_4(out, 6 + 13 * 8 + name_with_nul.len());
+    out.extend_from_slice(data);
+    pad_to_4(out, data.len());
+}
+
+fn pad_to_4(out: &mut Vec<u8>, written_len: usize) {
+    let pad = (4 - written_len % 4) % 4;
+    out.extend(std::iter::repeat(0u8).take(pad));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::package::Elf;
+
+    #[test]
+    fn test_build_elf_round_trips_through_the_real_parser() {
+        let spec = ArtifactSpec::new(ArtifactKind::SharedLibrary, "usr/lib/libfoo.so.1")
+            .needed("libc.so.6")
+            .needed("libbar.so.2")
+            .rpath(RpathSetting::Runpath("$ORIGIN/../lib".to_string()));
+
+        let bytes = build_elf(&spec);
+        let elf = Elf::from_bytes(Path::new("libfoo.so.1"), &bytes).unwrap();
+
+        assert_eq!(elf.dependencies(), &["libc.so.6".to_string(), "libbar.so.2".to_string()]);
+        assert!(elf.rpath().is_empty());
+        assert_eq!(elf.runpath(), &["$ORIGIN/../lib".to_string()]);
+    }
+
+    #[test]
+    fn test_build_elf_executable_with_rpath() {
+        let spec = ArtifactSpec::new(ArtifactKind::Executable, "usr/bin/hello")
+            .needed("libfoo.so.1")
+            .rpath(RpathSetting::Rpath("/opt/lib".to_string()));
+
+        let bytes = build_elf(&spec);
+        let elf = Elf::from_bytes(Path::new("hello"), &bytes).unwrap();
+
+        assert_eq!(elf.dependencies(), &["libfoo.so.1".to_string()]);
+        assert_eq!(elf.rpath(), &["/opt/lib".to_string()]);
+        assert!(elf.runpath().is_empty());
+    }
+
+    #[test]
+    fn test_build_elf_with_no_dependencies_or_rpath() {
+        let spec = ArtifactSpec::new(ArtifactKind::SharedLibrary, "usr/lib/libplain.so");
+        let bytes = build_elf(&spec);
+        let elf = Elf::from_bytes(Path::new("libplain.so"), &bytes).unwrap();
+
+        assert!(elf.dependencies().is_empty());
+        assert!(elf.rpath().is_empty());
+        assert!(elf.runpath().is_empty());
+    }
+
+    #[test]
+    fn test_build_package_deb_writes_a_readable_ar_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("test.deb");
+        let artifacts = [
+            ArtifactSpec::new(ArtifactKind::Executable, "usr/bin/hello").needed("libhello.so.1"),
+            ArtifactSpec::new(ArtifactKind::SharedLibrary, "usr/lib/libhello.so.1"),
+        ];
+
+        build_package(PackageFormat::Deb, "test", "1.0.0", &artifacts, &dest).unwrap();
+
+        let mut archive = ar::Archive::new(std::fs::File::open(&dest).unwrap());
+        let mut member_names = Vec::new();
+        while let Some(entry) = archive.next_entry() {
+            let entry = entry.unwrap();
+            member_names.push(String::from_utf8_lossy(entry.header().identifier()).into_owned());
+        }
+        assert_eq!(member_names, vec!["debian-binary", "control.tar.gz", "data.tar.gz"]);
+    }
+
+    #[test]
+    fn test_build_package_rpm_produces_a_header_and_payload_this_crate_can_read() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("test.rpm");
+        let artifacts = [ArtifactSpec::new(ArtifactKind::Executable, "usr/bin/hello")];
+
+        build_package(PackageFormat::Rpm, "test", "1.0.0", &artifacts, &dest).unwrap();
+
+        let bytes = std::fs::read(&dest).unwrap();
+        assert!(bytes.len() > 96);
+        assert_eq!(&bytes[0..4], &[0xed, 0xab, 0xee, 0xdb]);
+        assert_eq!(&bytes[96..100], &[0x8E, 0xAD, 0xE8, 0x01]);
+    }
+}
@@ -10,9 +10,10 @@
 //! - Validate RPATH/RUNPATH settings for proper dependency resolution
 //! - Generate reports on dependency status
 
+pub mod fixtures;
 pub mod package;
 pub mod report;
 
 // Re-export key types for convenience
 pub use package::{Elf, ElfType, Package, PackageFile};
-pub use report::{Report, SystemDependencies};
+pub use report::{to_sarif, Report, SarifLog, SearchConfig, SystemDependencies, VersionBaseline};
@@ -2,25 +2,171 @@
 // This file is part of Checkmk (https://checkmk.com). It is subject to the terms and
 // conditions defined in the file COPYING, which is part of this source code package.
 mod args;
+mod cache;
+mod fix;
 
 use anyhow::{Context, Result};
 use clap::Parser;
-use std::fs::File;
+use std::fs::{self, File};
 use std::path::Path;
 
-use args::Args;
+use args::{Args, OutputFormat};
+use cache::{CachedVerdict, ValidationCache};
 use package_validator::package::Package;
-use package_validator::report::{summarize_report, validate_report, Report, SystemDependencies};
+use package_validator::report::{
+    print_dependency_requesters, print_dependency_tree, summarize_report, to_sarif,
+    validate_report, Report, SearchConfig, SystemDependencies, ValidationPolicy, VersionBaseline,
+};
 use std::path::PathBuf;
 
 fn main() -> Result<()> {
     let args = Args::parse();
+    if let Some(fix_dir) = &args.fix {
+        fix::fix_package(&args.package, fix_dir)?;
+    }
+
+    let format_tag = cache_format_tag(args.format);
+    let mut cache = match (!args.no_cache).then(|| args.cache_file.as_ref()).flatten() {
+        Some(cache_file) => {
+            let epoch_input = cache_epoch_input(&args)?;
+            Some(ValidationCache::load(cache_file, &epoch_input))
+        }
+        None => None,
+    };
+
+    if let Some(cache) = cache.as_mut() {
+        if let Some(outcome) = cache.lookup(&args.package, format_tag) {
+            eprintln!(
+                "Cache hit: package unchanged since last run, reusing prior report: package={}",
+                args.package.display()
+            );
+            fs::write(&args.report, &outcome.report_bytes).with_context(|| {
+                format!("Failed to write cached report to {}", args.report.display())
+            })?;
+            return match outcome.verdict {
+                CachedVerdict::Clean => Ok(()),
+                CachedVerdict::Failed => Err(anyhow::anyhow!(
+                    "Cached validation previously failed for {}; re-run with --no-cache for details",
+                    args.package.display()
+                )),
+            };
+        }
+    }
+
     let package = extract_package(&args.package)?;
-    let system_dependencies = create_system_dependencies(args.system_dependencies.as_ref())?;
-    let report = Report::new(&package, &system_dependencies)?;
-    write_report_to_file(&report, &args.report)?;
+    let system_dependencies = create_system_dependencies(
+        args.system_dependencies.as_ref(),
+        args.normalize_sonames,
+    )?;
+    let max_glibc = create_max_glibc_baseline(args.max_glibc.as_deref())?;
+    let search_config = SearchConfig::new(
+        args.sysroot.clone().unwrap_or_else(|| PathBuf::from("/")),
+        args.ld_library_path.clone(),
+    );
+    let report = Report::new(
+        &package,
+        &system_dependencies,
+        max_glibc.as_ref(),
+        &search_config,
+    )?;
+    write_report_to_file(&report, &args.report, args.format)?;
     summarize_report(&report);
-    validate_report(&report)
+    if args.tree {
+        println!();
+        print_dependency_tree(&report);
+    }
+    if args.by_dependency {
+        println!();
+        print_dependency_requesters(&report);
+    }
+    let policy = create_validation_policy(&args)?;
+    let validation_result = validate_report(&report, &policy);
+
+    if let Some(cache) = cache.as_mut() {
+        let report_bytes = fs::read(&args.report).with_context(|| {
+            format!("Failed to read report file for caching: {}", args.report.display())
+        })?;
+        let verdict = if validation_result.is_ok() {
+            CachedVerdict::Clean
+        } else {
+            CachedVerdict::Failed
+        };
+        cache.record(&args.package, format_tag, verdict, report_bytes)?;
+        if let Some(cache_file) = &args.cache_file {
+            cache.store(cache_file)?;
+        }
+    }
+
+    validation_result
+}
+
+fn cache_format_tag(format: OutputFormat) -> u8 {
+    match format {
+        OutputFormat::Json => 0,
+        OutputFormat::Sarif => 1,
+    }
+}
+
+/// Bytes that should invalidate the whole validation cache when they change -- every flag that
+/// can change a package's dependency resolution or validation outcome, so editing any of them
+/// forces full revalidation instead of silently serving a stale cached verdict. Each
+/// variable-length value is followed by a NUL separator so that e.g. an `--ignore-dependency` value ending where the next
+/// one begins can't collide with a different pair of values that happen to concatenate to the
+/// same bytes.
+///
+/// # Errors
+/// Returns an error if `--system-dependencies` or `--policy` is configured but cannot be read.
+fn cache_epoch_input(args: &Args) -> Result<Vec<u8>> {
+    let mut input = Vec::new();
+
+    if let Some(path) = &args.system_dependencies {
+        input.extend_from_slice(&fs::read(path).with_context(|| {
+            format!("Failed to read system dependencies file: {}", path.display())
+        })?);
+    }
+    input.push(0);
+    input.push(u8::from(args.normalize_sonames));
+    if let Some(max_glibc) = &args.max_glibc {
+        input.extend_from_slice(max_glibc.as_bytes());
+    }
+    input.push(0);
+    if let Some(sysroot) = &args.sysroot {
+        input.extend_from_slice(sysroot.to_string_lossy().as_bytes());
+    }
+    input.push(0);
+    for dir in &args.ld_library_path {
+        input.extend_from_slice(dir.to_string_lossy().as_bytes());
+        input.push(0);
+    }
+    for dependency in &args.ignore_dependency {
+        input.extend_from_slice(dependency.as_bytes());
+        input.push(0);
+    }
+    let max_missing = args
+        .max_missing
+        .map_or(u64::MAX, |n| u64::try_from(n).unwrap_or(u64::MAX));
+    input.extend_from_slice(&max_missing.to_le_bytes());
+    input.push(u8::from(args.downgrade_unknown_kind));
+    if let Some(path) = &args.policy {
+        input.extend_from_slice(
+            &fs::read(path)
+                .with_context(|| format!("Failed to read policy file: {}", path.display()))?,
+        );
+    }
+
+    Ok(input)
+}
+
+fn create_validation_policy(args: &Args) -> Result<ValidationPolicy> {
+    let policy = ValidationPolicy::new(
+        args.ignore_dependency.clone(),
+        args.max_missing,
+        args.downgrade_unknown_kind,
+    )?;
+    match &args.policy {
+        Some(policy_file) => policy.merge_from_file(policy_file),
+        None => Ok(policy),
+    }
 }
 
 /// Get the package from a filepath.
@@ -41,24 +187,43 @@ fn extract_package(path: &Path) -> Result<Package> {
     Ok(package)
 }
 
-fn create_system_dependencies(path: Option<&PathBuf>) -> Result<SystemDependencies> {
-    if let Some(system_dependencies) = path {
-        Ok(SystemDependencies::from_file(system_dependencies)
-            .with_context(|| "Failed to read system dependencies file")?)
+fn create_system_dependencies(
+    path: Option<&PathBuf>,
+    normalize_sonames: bool,
+) -> Result<SystemDependencies> {
+    let system_dependencies = if let Some(path) = path {
+        SystemDependencies::from_file(path).with_context(|| "Failed to read system dependencies file")?
     } else {
-        Ok(SystemDependencies::empty())
-    }
+        SystemDependencies::empty()
+    };
+    Ok(system_dependencies.with_soname_normalization(normalize_sonames))
+}
+
+fn create_max_glibc_baseline(max_glibc: Option<&str>) -> Result<Option<VersionBaseline>> {
+    max_glibc
+        .map(|version| {
+            VersionBaseline::parse(version)
+                .with_context(|| format!("Invalid --max-glibc version: {version}"))
+        })
+        .transpose()
 }
 
-/// Write the report to a file.
+/// Write the report to a file in the requested output format.
 ///
 /// # Errors
-/// Returns an error if the report cannot be serialized to JSON or if the file cannot be created.
-fn write_report_to_file(report: &Report<'_>, dest: &Path) -> Result<()> {
-    eprintln!("Writing report to file: file={}", dest.display());
+/// Returns an error if the report cannot be serialized or if the file cannot be created.
+fn write_report_to_file(report: &Report<'_>, dest: &Path, format: OutputFormat) -> Result<()> {
+    eprintln!(
+        "Writing report to file: file={}, format={format}",
+        dest.display()
+    );
     let file = File::create(dest)
-        .with_context(|| format!("Failed to create JSON output file: {}", dest.display()))?;
-    serde_json::to_writer_pretty(file, report)
-        .with_context(|| format!("Failed to serialize report to JSON: {}", dest.display()))?;
+        .with_context(|| format!("Failed to create output file: {}", dest.display()))?;
+    match format {
+        OutputFormat::Json => serde_json::to_writer_pretty(file, report)
+            .with_context(|| format!("Failed to serialize report to JSON: {}", dest.display()))?,
+        OutputFormat::Sarif => serde_json::to_writer_pretty(file, &to_sarif(report))
+            .with_context(|| format!("Failed to serialize report to SARIF: {}", dest.display()))?,
+    }
     Ok(())
 }
@@ -0,0 +1,225 @@
+// Copyright (C) 2026 Checkmk GmbH - License: GNU General Public License v2
+// This file is part of Checkmk (https://checkmk.com). It is subject to the terms and
+// conditions defined in the file COPYING, which is part of this source code package.
+
+//! Unifies `Elf`, `MachO`, and `Pe` behind a single `kind`/`dependencies`/`normalize_paths`
+//! surface, detected via `goblin::Object::parse`, so dependency/rpath-style analysis doesn't
+//! need to branch on which binary format a package ships.
+//!
+//! This is a parsing layer only: `PackageFile::new` still classifies ELF members as
+//! `PackageFile::Elf` specifically and only falls back to `Binary::from_bytes` (surfaced as
+//! `PackageFile::Binary`) for Mach-O/PE, since every downstream consumer (SONAME-based system
+//! dependency matching, the SARIF rule set, the declared-dependency cross-check) is built
+//! around ELF/DEB-RPM semantics that don't carry over to Mach-O or PE as-is.
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use super::elf::{Elf, ElfError};
+use super::macho::{MachO, MachOError};
+use super::pe::{Pe, PeError};
+
+type Result<T> = std::result::Result<T, BinaryError>;
+
+/// Errors that can occur when parsing a binary of any supported format.
+#[derive(Debug, Error)]
+pub(crate) enum BinaryError {
+    #[error("Failed to read file: {path:?}")]
+    ReadFailed {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Failed to determine binary format: {path:?}")]
+    FormatDetectionFailed {
+        path: PathBuf,
+        #[source]
+        source: goblin::error::Error,
+    },
+    #[error(transparent)]
+    Elf(#[from] ElfError),
+    #[error(transparent)]
+    MachO(#[from] MachOError),
+    #[error(transparent)]
+    Pe(#[from] PeError),
+    #[error("Unsupported or unrecognized binary format: {path:?}")]
+    UnsupportedFormat { path: PathBuf },
+}
+
+/// A parsed binary, in whichever of the supported formats it turned out to be.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum Binary {
+    Elf(Elf),
+    MachO(MachO),
+    Pe(Pe),
+}
+
+impl Binary {
+    /// Detect the format of `bytes` and parse it with the matching format-specific parser.
+    /// `path` is used only to label errors.
+    ///
+    /// # Errors
+    /// Returns an error if the format can't be determined, isn't one of ELF/Mach-O/PE, or the
+    /// matching format-specific parser fails.
+    pub(crate) fn from_bytes(path: &Path, bytes: &[u8]) -> Result<Self> {
+        match goblin::Object::parse(bytes).map_err(|e| BinaryError::FormatDetectionFailed {
+            path: path.to_path_buf(),
+            source: e,
+        })? {
+            goblin::Object::Elf(_) => Ok(Self::Elf(Elf::from_bytes(path, bytes)?)),
+            goblin::Object::Mach(_) => Ok(Self::MachO(MachO::from_bytes(path, bytes)?)),
+            goblin::Object::PE(_) => Ok(Self::Pe(Pe::from_bytes(path, bytes)?)),
+            _ => Err(BinaryError::UnsupportedFormat {
+                path: path.to_path_buf(),
+            }),
+        }
+    }
+
+    /// Validate the embedded library search path of the binary at `path`, sniffing its format
+    /// (ELF vs. Mach-O, including fat/universal images) and dispatching to the matching
+    /// validator, so callers don't need to know the format up front: `Elf::validate`-style rules
+    /// for ELF `RPATH`/`RUNPATH`, `MachO::validate` for `LC_RPATH`. PE has no equivalent embedded
+    /// search path, so it always validates cleanly.
+    ///
+    /// Unlike `from_bytes`, an invalid path list is not an error: it's returned as `Ok` so a
+    /// caller can report every invalid entry rather than stopping at the first file with one.
+    ///
+    /// # Errors
+    /// Returns an error if the file can't be read, its format can't be determined, or it fails
+    /// to parse as a binary of its detected format (e.g. a fat Mach-O image, which isn't yet
+    /// supported).
+    ///
+    /// Not yet called outside tests: `PackageFile::new` surfaces a Mach-O/PE member's kind and
+    /// dependencies but doesn't yet hard-fail package loading on an invalid Mach-O `LC_RPATH`
+    /// the way `Elf::from_path` does for ELF `RPATH`/`RUNPATH` — this is the front-end such a
+    /// check would use once that's decided on purpose rather than as a side effect of wiring.
+    #[allow(dead_code)]
+    pub(crate) fn validate_path(path: &Path) -> Result<Vec<String>> {
+        let bytes = fs::read(path).map_err(|e| BinaryError::ReadFailed {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        match goblin::Object::parse(&bytes).map_err(|e| BinaryError::FormatDetectionFailed {
+            path: path.to_path_buf(),
+            source: e,
+        })? {
+            goblin::Object::Elf(_) => match Elf::from_path(path) {
+                Ok(_) => Ok(Vec::new()),
+                Err(ElfError::InvalidPaths { paths }) => {
+                    Ok(paths.iter().map(ToString::to_string).collect())
+                }
+                Err(e) => Err(e.into()),
+            },
+            goblin::Object::Mach(_) => Ok(MachO::from_path(path)?.validate()),
+            goblin::Object::PE(_) => Ok(Vec::new()),
+            _ => Err(BinaryError::UnsupportedFormat {
+                path: path.to_path_buf(),
+            }),
+        }
+    }
+
+    /// Get this binary's dependencies: `DT_NEEDED` entries for ELF, dependent dylib paths for
+    /// Mach-O, or imported DLL names for PE.
+    #[must_use]
+    pub(crate) fn dependencies(&self) -> &[String] {
+        match self {
+            Self::Elf(elf) => elf.dependencies(),
+            Self::MachO(macho) => macho.dependencies(),
+            Self::Pe(pe) => pe.dependencies(),
+        }
+    }
+
+    /// Normalize this binary's embedded library search paths into absolute filesystem paths,
+    /// substituting each format's token family (`$ORIGIN`/`$LIB`/`$PLATFORM` for ELF,
+    /// `@loader_path`/`@executable_path`/`@rpath` for Mach-O). Always empty for PE, which has no
+    /// equivalent embedded search path.
+    #[must_use]
+    pub(crate) fn normalize_paths(&self, origin: &Path) -> Vec<PathBuf> {
+        match self {
+            Self::Elf(elf) => elf.normalize_paths(origin),
+            Self::MachO(macho) => macho.normalize_paths(origin),
+            Self::Pe(pe) => pe.normalize_paths(origin),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::package::elf::Elf;
+    use crate::package::macho::MachO;
+    use crate::package::pe::{Pe, PeType};
+
+    #[test]
+    fn test_dependencies_dispatches_to_elf() {
+        let binary = Binary::Elf(Elf::new_for_testing_with_dependencies(
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            vec!["libfoo.so".to_string()],
+        ));
+        assert_eq!(binary.dependencies(), &["libfoo.so".to_string()]);
+    }
+
+    #[test]
+    fn test_dependencies_dispatches_to_macho() {
+        let binary = Binary::MachO(MachO::new_for_testing(
+            Vec::new(),
+            vec!["@rpath/libFoo.dylib".to_string()],
+        ));
+        assert_eq!(binary.dependencies(), &["@rpath/libFoo.dylib".to_string()]);
+    }
+
+    #[test]
+    fn test_dependencies_dispatches_to_pe() {
+        let binary = Binary::Pe(Pe::new_for_testing(
+            PeType::Executable,
+            vec!["KERNEL32.dll".to_string()],
+        ));
+        assert_eq!(binary.dependencies(), &["KERNEL32.dll".to_string()]);
+    }
+
+    #[test]
+    fn test_normalize_paths_is_empty_for_pe() {
+        let binary = Binary::Pe(Pe::new_for_testing(PeType::Executable, Vec::new()));
+        assert!(binary.normalize_paths(Path::new("/tmp")).is_empty());
+    }
+
+    fn get_examples_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("examples")
+    }
+
+    /// Helper to skip tests when fixture files are missing.
+    /// Returns None if fixture is missing, Some(path) if it exists.
+    fn require_fixture(name: &str) -> Option<PathBuf> {
+        let path = get_examples_dir().join(name);
+        if path.exists() {
+            Some(path)
+        } else {
+            eprintln!(
+                "Skipping test: fixture '{}' not found. Run 'examples/generate-examples.sh' to generate it.",
+                name
+            );
+            None
+        }
+    }
+
+    #[test]
+    fn test_validate_path_dispatches_to_elf_and_accepts_valid_rpath() {
+        let Some(path) = require_fixture("test-elf-valid-absolute-rpath.elf") else {
+            return;
+        };
+        assert_eq!(Binary::validate_path(&path).unwrap(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_validate_path_dispatches_to_elf_and_reports_invalid_rpath() {
+        let Some(path) = require_fixture("test-elf-invalid-relative-rpath.elf") else {
+            return;
+        };
+        let invalid = Binary::validate_path(&path).unwrap();
+        assert_eq!(invalid.len(), 1);
+    }
+}
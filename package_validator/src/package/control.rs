@@ -0,0 +1,120 @@
+// Copyright (C) 2026 Checkmk GmbH - License: GNU General Public License v2
+// This file is part of Checkmk (https://checkmk.com). It is subject to the terms and
+// conditions defined in the file COPYING, which is part of this source code package.
+
+//! Parses a DEB package's control file (the `control` member of its `control.tar.*`) for its
+//! declared identity and dependency fields.
+
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// A DEB package's declared identity and dependency fields, parsed from its control file.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub(crate) struct ControlMetadata {
+    pub(crate) package: String,
+    pub(crate) version: String,
+    pub(crate) depends: Vec<String>,
+    pub(crate) recommends: Vec<String>,
+}
+
+impl ControlMetadata {
+    /// Parse a control file's text. Missing fields are left empty rather than erroring, since a
+    /// best-effort dependency cross-check is still useful from a partial control file.
+    pub(crate) fn parse(text: &str) -> Self {
+        let fields = Self::parse_fields(text);
+        Self {
+            package: fields.get("Package").cloned().unwrap_or_default(),
+            version: fields.get("Version").cloned().unwrap_or_default(),
+            depends: Self::split_dependency_list(fields.get("Depends")),
+            recommends: Self::split_dependency_list(fields.get("Recommends")),
+        }
+    }
+
+    /// Parse the control file's RFC 822-style fields: a field starts at a line with no leading
+    /// whitespace and a `Key: Value` shape, and continues onto any following lines that start
+    /// with whitespace (folded onto the field's value, space-separated).
+    fn parse_fields(text: &str) -> HashMap<String, String> {
+        let mut fields: HashMap<String, String> = HashMap::new();
+        let mut current_key: Option<String> = None;
+
+        for line in text.lines() {
+            if let Some(continuation) = line.strip_prefix(' ').or_else(|| line.strip_prefix('\t')) {
+                if let Some(value) = current_key.as_ref().and_then(|key| fields.get_mut(key)) {
+                    value.push(' ');
+                    value.push_str(continuation.trim());
+                }
+                continue;
+            }
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().to_string();
+            fields.insert(key.clone(), value.trim().to_string());
+            current_key = Some(key);
+        }
+
+        fields
+    }
+
+    /// Split a `Depends`/`Recommends` field into its declared package names, discarding version
+    /// constraints (`(>= 1.2)`) and collapsing alternatives (`a | b`) down to their first
+    /// choice, since we only need a name to correlate against, not the full constraint.
+    fn split_dependency_list(field: Option<&String>) -> Vec<String> {
+        let Some(field) = field else {
+            return Vec::new();
+        };
+        field
+            .split(',')
+            .filter_map(|entry| {
+                let first_alternative = entry.split('|').next()?.trim();
+                let name = first_alternative.split_whitespace().next()?;
+                (!name.is_empty()).then(|| name.to_string())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_extracts_package_and_version() {
+        let text = "Package: myapp\nVersion: 1.2.3\nArchitecture: amd64\n";
+        let metadata = ControlMetadata::parse(text);
+        assert_eq!(metadata.package, "myapp");
+        assert_eq!(metadata.version, "1.2.3");
+    }
+
+    #[test]
+    fn test_parse_splits_depends_list_and_strips_constraints() {
+        let text = "Package: myapp\nDepends: libc6 (>= 2.17), libssl1.1, libfoo1 | libfoo2\n";
+        let metadata = ControlMetadata::parse(text);
+        assert_eq!(
+            metadata.depends,
+            vec![
+                "libc6".to_string(),
+                "libssl1.1".to_string(),
+                "libfoo1".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_handles_folded_continuation_lines() {
+        let text = "Package: myapp\nDepends: libc6 (>= 2.17),\n libssl1.1\n";
+        let metadata = ControlMetadata::parse(text);
+        assert_eq!(
+            metadata.depends,
+            vec!["libc6".to_string(), "libssl1.1".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_parse_missing_fields_are_empty() {
+        let metadata = ControlMetadata::parse("Package: myapp\n");
+        assert!(metadata.version.is_empty());
+        assert!(metadata.depends.is_empty());
+        assert!(metadata.recommends.is_empty());
+    }
+}
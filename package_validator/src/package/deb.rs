@@ -2,31 +2,368 @@
 // This file is part of Checkmk (https://checkmk.com). It is subject to the terms and
 // conditions defined in the file COPYING, which is part of this source code package.
 
-//! Implements DEB package extraction using `dpkg-deb`.
+//! Implements DEB package extraction by streaming the embedded `data.tar.*` member directly
+//! out of the `ar` archive, entirely in-process. Also offers `dpkg-deb` as an opt-in fast path
+//! for callers who have it installed and want it (see `extract_via_dpkg_deb`).
 
+use path_clean::PathClean;
+use std::io::Read;
 use std::path::Path;
+use tar::EntryType;
 use tempfile::TempDir;
 
+use super::control::ControlMetadata;
 use super::extractor::{
     wait_with_timeout, PackageError, PackageExtractor, PackageResult, DEFAULT_EXTRACTION_TIMEOUT,
+    MAX_EXTRACTED_BYTES,
 };
+use super::files::PackageFile;
+use super::filter::ExtractionFilter;
 use super::PackageFiles;
 
 pub(crate) struct DebExtractor;
 
 impl PackageExtractor for DebExtractor {
     const EXTENSION: &'static str = "deb";
+    const IN_PROCESS: bool = true;
 
-    /// Extract a DEB package into a temporary directory.
+    /// Extract a DEB package by streaming its `data.tar.*` member out of the outer `ar`
+    /// archive, decompressing and unpacking it in-process.
     ///
     /// # Errors
-    /// Returns an error if the package cannot be extracted.
+    /// Returns an error if the package cannot be opened, has no `data.tar.*` member, uses an
+    /// unsupported compression, or a tar entry attempts to escape the package root.
     ///
-    /// # Timeout
-    /// This function enforces a timeout of 30 seconds for the `dpkg-deb` subprocess.
-    /// If extraction takes longer, the process will be killed and a `CommandTimeout`
-    /// error will be returned.
-    fn extract(package: &Path, dest: &TempDir) -> PackageResult<PackageFiles> {
+    /// # No timeout
+    /// Unlike RPM extraction, this never shells out to an external tool, so none of the
+    /// `wait_with_timeout`/`CommandNotFound`/`CommandTimeout` machinery applies here. A
+    /// malformed or oversized archive is instead bounded by `MAX_EXTRACTED_BYTES`.
+    fn extract(
+        package: &Path,
+        _dest: &TempDir,
+        filter: &ExtractionFilter,
+    ) -> PackageResult<PackageFiles> {
+        let file = std::fs::File::open(package).map_err(|e| PackageError::ExtractionFailed {
+            path: package.to_path_buf(),
+            reason: format!("Failed to open package: {e}"),
+        })?;
+
+        let mut archive = ar::Archive::new(file);
+        let mut files = PackageFiles::new();
+        let mut everything_filtered = false;
+        let mut bytes_read: u64 = 0;
+        let mut found_data_member = false;
+
+        while let Some(entry) = archive.next_entry() {
+            let entry = entry.map_err(|e| PackageError::ExtractionFailed {
+                path: package.to_path_buf(),
+                reason: format!("Failed to read .deb archive member: {e}"),
+            })?;
+            let member_name = String::from_utf8_lossy(entry.header().identifier()).into_owned();
+            if !member_name.starts_with("data.tar") {
+                continue;
+            }
+            found_data_member = true;
+            Self::extract_data_member(
+                &member_name,
+                entry,
+                package,
+                filter,
+                &mut files,
+                &mut everything_filtered,
+                &mut bytes_read,
+                None,
+            )?;
+            break; // A .deb has exactly one data.tar.* member.
+        }
+
+        if !found_data_member {
+            return Err(PackageError::ExtractionFailed {
+                path: package.to_path_buf(),
+                reason: "No data.tar.* member found in .deb archive".to_string(),
+            });
+        }
+
+        if files.is_empty() {
+            let reason = if everything_filtered {
+                "Extraction completed but every file was excluded by the extraction filter"
+            } else {
+                "Extraction completed but no files were found"
+            };
+            return Err(PackageError::ExtractionFailed {
+                path: package.to_path_buf(),
+                reason: reason.to_string(),
+            });
+        }
+
+        Ok(files)
+    }
+}
+
+impl DebExtractor {
+    /// Like `extract`, but also materializes every regular file and symlink under `dest` on
+    /// disk, so the result can be persisted (see `Package::new_with_persistence`). `extract`
+    /// itself stays disk-free (see `IN_PROCESS`) since that's the fast path `Package::new` uses
+    /// for every validation run; this variant is only for the uncommon caller that actually
+    /// wants the extraction tree kept around afterwards.
+    ///
+    /// # Errors
+    /// As `extract`, plus an error if writing a file or symlink to `dest` fails.
+    pub(crate) fn extract_persisting(
+        package: &Path,
+        dest: &TempDir,
+        filter: &ExtractionFilter,
+    ) -> PackageResult<PackageFiles> {
+        let file = std::fs::File::open(package).map_err(|e| PackageError::ExtractionFailed {
+            path: package.to_path_buf(),
+            reason: format!("Failed to open package: {e}"),
+        })?;
+
+        let mut archive = ar::Archive::new(file);
+        let mut files = PackageFiles::new();
+        let mut everything_filtered = false;
+        let mut bytes_read: u64 = 0;
+        let mut found_data_member = false;
+
+        while let Some(entry) = archive.next_entry() {
+            let entry = entry.map_err(|e| PackageError::ExtractionFailed {
+                path: package.to_path_buf(),
+                reason: format!("Failed to read .deb archive member: {e}"),
+            })?;
+            let member_name = String::from_utf8_lossy(entry.header().identifier()).into_owned();
+            if !member_name.starts_with("data.tar") {
+                continue;
+            }
+            found_data_member = true;
+            Self::extract_data_member(
+                &member_name,
+                entry,
+                package,
+                filter,
+                &mut files,
+                &mut everything_filtered,
+                &mut bytes_read,
+                Some(dest.path()),
+            )?;
+            break; // A .deb has exactly one data.tar.* member.
+        }
+
+        if !found_data_member {
+            return Err(PackageError::ExtractionFailed {
+                path: package.to_path_buf(),
+                reason: "No data.tar.* member found in .deb archive".to_string(),
+            });
+        }
+
+        if files.is_empty() {
+            let reason = if everything_filtered {
+                "Extraction completed but every file was excluded by the extraction filter"
+            } else {
+                "Extraction completed but no files were found"
+            };
+            return Err(PackageError::ExtractionFailed {
+                path: package.to_path_buf(),
+                reason: reason.to_string(),
+            });
+        }
+
+        Ok(files)
+    }
+
+    /// Write a single extracted entry to disk under `write_root`, joined with its
+    /// package-absolute `package_path`. Creates parent directories as needed.
+    fn write_entry_to_disk(
+        write_root: &Path,
+        package_path: &Path,
+        package: &Path,
+        contents: EntryContents<'_>,
+    ) -> PackageResult<()> {
+        // package_path is always absolute (rooted at "/"); join it as relative to write_root.
+        let relative = package_path.strip_prefix("/").unwrap_or(package_path);
+        let on_disk_path = write_root.join(relative);
+        if let Some(parent) = on_disk_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| PackageError::ExtractionFailed {
+                path: package.to_path_buf(),
+                reason: format!("Failed to create directory {}: {e}", parent.display()),
+            })?;
+        }
+        match contents {
+            EntryContents::File(bytes) => {
+                std::fs::write(&on_disk_path, bytes).map_err(|e| PackageError::ExtractionFailed {
+                    path: package.to_path_buf(),
+                    reason: format!("Failed to write {}: {e}", on_disk_path.display()),
+                })?;
+            }
+            EntryContents::Symlink(target) => {
+                std::os::unix::fs::symlink(target, &on_disk_path).map_err(|e| {
+                    PackageError::ExtractionFailed {
+                        path: package.to_path_buf(),
+                        reason: format!("Failed to symlink {}: {e}", on_disk_path.display()),
+                    }
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Pick a decompressor for an `ar` member (`data.tar.*` or `control.tar.*`) by its name
+    /// suffix.
+    fn decompress<'a>(
+        member_name: &str,
+        entry: impl Read + 'a,
+        package: &Path,
+    ) -> PackageResult<Box<dyn Read + 'a>> {
+        Ok(match member_name.rsplit_once('.').map(|(_, ext)| ext) {
+            Some("gz") => Box::new(flate2::read::GzDecoder::new(entry)),
+            Some("xz") => Box::new(xz2::read::XzDecoder::new(entry)),
+            Some("zst") => {
+                Box::new(zstd::stream::read::Decoder::new(entry).map_err(|e| {
+                    PackageError::ExtractionFailed {
+                        path: package.to_path_buf(),
+                        reason: format!("Failed to initialize zstd decoder: {e}"),
+                    }
+                })?)
+            }
+            Some("tar") | None => Box::new(entry),
+            Some(other) => {
+                return Err(PackageError::ExtractionFailed {
+                    path: package.to_path_buf(),
+                    reason: format!("Unsupported {member_name} compression: {other}"),
+                });
+            }
+        })
+    }
+
+    /// Stream the tar entries of `data.tar.*` into `files`. Writes nothing to disk unless
+    /// `write_root` is given, in which case each kept entry is also materialized under it (see
+    /// `extract_persisting`).
+    fn extract_data_member(
+        member_name: &str,
+        entry: impl Read,
+        package: &Path,
+        filter: &ExtractionFilter,
+        files: &mut PackageFiles,
+        everything_filtered: &mut bool,
+        bytes_read: &mut u64,
+        write_root: Option<&Path>,
+    ) -> PackageResult<()> {
+        let reader = Self::decompress(member_name, entry, package)?;
+        let mut tar = tar::Archive::new(reader);
+        let entries = tar.entries().map_err(|e| PackageError::ExtractionFailed {
+            path: package.to_path_buf(),
+            reason: format!("Failed to read data.tar entries: {e}"),
+        })?;
+
+        for tar_entry in entries {
+            let mut tar_entry = tar_entry.map_err(|e| PackageError::ExtractionFailed {
+                path: package.to_path_buf(),
+                reason: format!("Failed to read tar entry: {e}"),
+            })?;
+
+            let entry_type = tar_entry.header().entry_type();
+            if !matches!(entry_type, EntryType::Regular | EntryType::Symlink) {
+                continue;
+            }
+
+            let raw_path = tar_entry
+                .path()
+                .map_err(|e| PackageError::ExtractionFailed {
+                    path: package.to_path_buf(),
+                    reason: format!("Failed to read tar entry path: {e}"),
+                })?
+                .into_owned();
+            let relative_path = raw_path.strip_prefix("./").unwrap_or(&raw_path);
+            if PackageFile::path_escapes_root(relative_path) {
+                return Err(PackageError::ExtractionFailed {
+                    path: package.to_path_buf(),
+                    reason: format!(
+                        "Tar entry escapes package root: {}",
+                        relative_path.display()
+                    ),
+                });
+            }
+            let package_path = Path::new("/").join(relative_path).clean();
+
+            if !filter.matches_file(&package_path) {
+                *everything_filtered = true;
+                continue;
+            }
+
+            match entry_type {
+                EntryType::Symlink => {
+                    let raw_target = tar_entry
+                        .link_name()
+                        .map_err(|e| PackageError::ExtractionFailed {
+                            path: package.to_path_buf(),
+                            reason: format!("Failed to read symlink target: {e}"),
+                        })?
+                        .map(|target| target.into_owned())
+                        .unwrap_or_default();
+                    if let Some(write_root) = write_root {
+                        Self::write_entry_to_disk(
+                            write_root,
+                            &package_path,
+                            package,
+                            EntryContents::Symlink(&raw_target),
+                        )?;
+                    }
+                    files.insert(
+                        package_path.clone(),
+                        PackageFile::new_symlink(&package_path, raw_target),
+                    );
+                }
+                EntryType::Regular => {
+                    *bytes_read += tar_entry.header().size().unwrap_or(0);
+                    if *bytes_read > MAX_EXTRACTED_BYTES {
+                        return Err(PackageError::ExtractedSizeLimitExceeded {
+                            path: package.to_path_buf(),
+                            limit: MAX_EXTRACTED_BYTES,
+                        });
+                    }
+                    let mut buf = Vec::new();
+                    tar_entry.read_to_end(&mut buf).map_err(|e| {
+                        PackageError::ExtractionFailed {
+                            path: package.to_path_buf(),
+                            reason: format!("Failed to read tar entry contents: {e}"),
+                        }
+                    })?;
+                    if let Some(write_root) = write_root {
+                        Self::write_entry_to_disk(
+                            write_root,
+                            &package_path,
+                            package,
+                            EntryContents::File(&buf),
+                        )?;
+                    }
+                    let file = PackageFile::from_bytes(&package_path, &buf)?;
+                    files.insert(package_path, file);
+                }
+                _ => unreachable!("filtered to Regular | Symlink above"),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Extract a DEB package by shelling out to `dpkg-deb -x` into `dest`, then reusing the
+    /// shared `process()` walk to build the `PackageFiles` map, so ELF discovery is identical
+    /// to the in-process backend above.
+    ///
+    /// This is an opt-in fast path for callers that have `dpkg-deb` installed and prefer it;
+    /// it is not wired into `extract()` (the default used by `Package::new` and friends), since
+    /// `dpkg-deb` populates `dest` on disk while the in-process backend never does (this
+    /// extension is declared `IN_PROCESS`). `Package::new_with_persistence` does not use this
+    /// either; it calls `extract_persisting`.
+    ///
+    /// # Errors
+    /// Returns `CommandNotFound` if `dpkg-deb` isn't on `PATH`, `CommandTimeout` if it doesn't
+    /// finish within `DEFAULT_EXTRACTION_TIMEOUT`, or `ExtractionFailed` if it exits non-zero.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn extract_via_dpkg_deb(
+        package: &Path,
+        dest: &TempDir,
+        filter: &ExtractionFilter,
+    ) -> PackageResult<PackageFiles> {
         let mut child = match std::process::Command::new("dpkg-deb")
             .arg("-x")
             .arg(package)
@@ -49,27 +386,100 @@ impl PackageExtractor for DebExtractor {
             }
         };
 
-        let exit_status =
+        let status =
             wait_with_timeout(&mut child, DEFAULT_EXTRACTION_TIMEOUT, "dpkg-deb", package)?;
 
-        if exit_status.success() {
-            Self::process(dest, package)
+        if status.success() {
+            Self::process(dest, package, filter)
         } else {
             Err(PackageError::ExtractionFailed {
                 path: package.to_path_buf(),
                 reason: format!(
                     "dpkg-deb exited with non-zero status: {}",
-                    exit_status.code().unwrap_or(-1)
+                    status.code().unwrap_or(-1)
                 ),
             })
         }
     }
+
+    /// Parse the package's control file (the `control` member of its `control.tar.*`), if
+    /// present, into its declared identity and dependency fields.
+    ///
+    /// Returns `Ok(None)` rather than an error if no `control.tar.*` member or no `control`
+    /// entry within it is found, since a DEB without a control file is malformed but that's not
+    /// this function's concern to diagnose.
+    ///
+    /// # Errors
+    /// Returns an error if the package cannot be opened or a `control.tar.*` member is found
+    /// but fails to decompress or unpack.
+    pub(crate) fn read_control_metadata(package: &Path) -> PackageResult<Option<ControlMetadata>> {
+        let file = std::fs::File::open(package).map_err(|e| PackageError::ExtractionFailed {
+            path: package.to_path_buf(),
+            reason: format!("Failed to open package: {e}"),
+        })?;
+        let mut archive = ar::Archive::new(file);
+
+        while let Some(entry) = archive.next_entry() {
+            let entry = entry.map_err(|e| PackageError::ExtractionFailed {
+                path: package.to_path_buf(),
+                reason: format!("Failed to read .deb archive member: {e}"),
+            })?;
+            let member_name = String::from_utf8_lossy(entry.header().identifier()).into_owned();
+            if !member_name.starts_with("control.tar") {
+                continue;
+            }
+
+            let reader = Self::decompress(&member_name, entry, package)?;
+            let mut tar = tar::Archive::new(reader);
+            let entries = tar.entries().map_err(|e| PackageError::ExtractionFailed {
+                path: package.to_path_buf(),
+                reason: format!("Failed to read control.tar entries: {e}"),
+            })?;
+
+            for tar_entry in entries {
+                let mut tar_entry = tar_entry.map_err(|e| PackageError::ExtractionFailed {
+                    path: package.to_path_buf(),
+                    reason: format!("Failed to read control.tar entry: {e}"),
+                })?;
+                let entry_path = tar_entry
+                    .path()
+                    .map_err(|e| PackageError::ExtractionFailed {
+                        path: package.to_path_buf(),
+                        reason: format!("Failed to read control.tar entry path: {e}"),
+                    })?
+                    .into_owned();
+                if entry_path.file_name().and_then(|name| name.to_str()) != Some("control") {
+                    continue;
+                }
+
+                let mut text = String::new();
+                tar_entry
+                    .read_to_string(&mut text)
+                    .map_err(|e| PackageError::ExtractionFailed {
+                        path: package.to_path_buf(),
+                        reason: format!("Failed to read control file contents: {e}"),
+                    })?;
+                return Ok(Some(ControlMetadata::parse(&text)));
+            }
+            break; // Found control.tar.* but no "control" member inside it.
+        }
+
+        Ok(None)
+    }
+}
+
+/// What to write to disk for a single extracted entry, passed to `write_entry_to_disk`.
+enum EntryContents<'a> {
+    File(&'a [u8]),
+    Symlink(&'a Path),
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::package::{Package, PackageFile};
-    use std::path::PathBuf;
+    use super::DebExtractor;
+    use crate::package::{ExtractionFilter, Package, PackageFile};
+    use std::path::{Path, PathBuf};
+    use tempfile::TempDir;
 
     fn get_examples_dir() -> PathBuf {
         PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("examples")
@@ -118,4 +528,114 @@ mod tests {
             "Package should contain at least one ELF file"
         );
     }
+
+    #[test]
+    fn test_read_control_metadata_from_example_package() {
+        let deb_path = get_examples_dir().join("test.deb");
+        if !deb_path.exists() {
+            eprintln!(
+                "Skipping test: DEB test file not found at {}. Run 'examples/generate-examples.sh' to generate it.",
+                deb_path.display()
+            );
+            return;
+        }
+
+        let metadata = DebExtractor::read_control_metadata(&deb_path)
+            .expect("reading control metadata should not error");
+        if let Some(metadata) = metadata {
+            assert!(!metadata.package.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_dpkg_deb_and_native_backends_produce_identical_files() {
+        let deb_path = get_examples_dir().join("test.deb");
+        if !deb_path.exists() {
+            eprintln!(
+                "Skipping test: DEB test file not found at {}. Run 'examples/generate-examples.sh' to generate it.",
+                deb_path.display()
+            );
+            return;
+        }
+        if std::process::Command::new("dpkg-deb")
+            .arg("--version")
+            .output()
+            .is_err()
+        {
+            eprintln!("Skipping test: dpkg-deb not installed");
+            return;
+        }
+
+        let filter = ExtractionFilter::unfiltered();
+        let native_dest = TempDir::new().unwrap();
+        let native_files = DebExtractor::extract(&deb_path, &native_dest, &filter)
+            .expect("native extraction should succeed");
+
+        let dpkg_deb_dest = TempDir::new().unwrap();
+        let dpkg_deb_files = DebExtractor::extract_via_dpkg_deb(&deb_path, &dpkg_deb_dest, &filter)
+            .expect("dpkg-deb extraction should succeed");
+
+        assert_eq!(native_files, dpkg_deb_files);
+    }
+
+    /// Build a minimal `.deb` (an `ar` archive with `debian-binary` and a `data.tar.gz` holding
+    /// one entry at `data_tar_entry_path`) without going through `examples/generate-examples.sh`,
+    /// so the path-traversal guard below doesn't depend on example fixtures existing.
+    fn build_deb_with_data_tar_entry(data_tar_entry_path: &str) -> tempfile::NamedTempFile {
+        let mut data_tar_gz = Vec::new();
+        {
+            let encoder =
+                flate2::write::GzEncoder::new(&mut data_tar_gz, flate2::Compression::default());
+            let mut builder = tar::Builder::new(encoder);
+            let contents = b"evil";
+            let mut header = tar::Header::new_gnu();
+            header.set_path(data_tar_entry_path).unwrap();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append(&header, &contents[..]).unwrap();
+            builder.into_inner().unwrap().finish().unwrap();
+        }
+
+        let deb_file = tempfile::NamedTempFile::new().unwrap();
+        let mut builder = ar::Builder::new(deb_file.reopen().unwrap());
+        builder
+            .append(&ar::Header::new(b"debian-binary".to_vec(), 4), &b"2.0\n"[..])
+            .unwrap();
+        builder
+            .append(
+                &ar::Header::new(b"data.tar.gz".to_vec(), data_tar_gz.len() as u64),
+                data_tar_gz.as_slice(),
+            )
+            .unwrap();
+        deb_file
+    }
+
+    #[test]
+    fn test_extract_rejects_interspersed_parent_refs_escaping_root() {
+        // "docs/.." cancels out, leaving the same net three-level climb above the package root
+        // as a leading-only "../../../etc/cron.d/evil" path -- the guard at the call site of
+        // `PackageFile::path_escapes_root` must catch this regardless of where the `..` run
+        // falls, not just when it's a leading prefix.
+        let deb_file = build_deb_with_data_tar_entry("docs/../../../../etc/cron.d/evil");
+        let filter = ExtractionFilter::unfiltered();
+        let dest = TempDir::new().unwrap();
+
+        let result = DebExtractor::extract(deb_file.path(), &dest, &filter);
+        assert!(
+            result.is_err(),
+            "Tar entry with an interspersed parent-ref escape should be rejected"
+        );
+    }
+
+    #[test]
+    fn test_extract_accepts_well_behaved_interspersed_parent_refs() {
+        let deb_file = build_deb_with_data_tar_entry("docs/../usr/bin/myapp");
+        let filter = ExtractionFilter::unfiltered();
+        let dest = TempDir::new().unwrap();
+
+        let files = DebExtractor::extract(deb_file.path(), &dest, &filter)
+            .expect("A path that cancels out to a well-behaved location should extract fine");
+        assert!(files.contains_key(Path::new("/usr/bin/myapp")));
+    }
 }
@@ -4,10 +4,11 @@
 
 //! Parses ELF files to extract dependencies, `RPATH`, and `RUNPATH` entries. Uses the `goblin` crate for ELF parsing.
 
+use goblin::elf::sym::{STB_GLOBAL, STB_WEAK};
 use goblin::elf::Elf as GoblinElf;
 use path_clean::PathClean;
 use serde::Serialize;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io;
 use std::io::{Read, Seek};
@@ -44,8 +45,44 @@ pub enum ElfError {
     },
     #[error("Unknown ELF type in file: {path:?}")]
     UnknownElfType { path: PathBuf },
-    #[error("Invalid (RPATH or RUNPATH) paths: {paths:?}")]
-    InvalidPaths { paths: Vec<String> },
+    #[error(
+        "Invalid (RPATH or RUNPATH) paths: {}",
+        paths.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+    )]
+    InvalidPaths { paths: Vec<InvalidPath> },
+}
+
+/// An invalid RPATH/RUNPATH entry, along with the corrected form it most likely should have
+/// been, when one can be confidently guessed. The validator prints `suggestion` as a "did you
+/// mean" hint; `--fix` does not apply it (it only rewrites absolute entries of valid ELFs).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidPath {
+    message: String,
+    suggestion: Option<String>,
+}
+
+impl InvalidPath {
+    /// The human-readable description of why this entry is invalid, e.g. `RPATH: ../lib is
+    /// invalid`.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The suggested correction, if one could be confidently computed.
+    #[must_use]
+    pub fn suggestion(&self) -> Option<&str> {
+        self.suggestion.as_deref()
+    }
+}
+
+impl std::fmt::Display for InvalidPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.suggestion {
+            Some(suggestion) => write!(f, "{}; did you mean `{suggestion}`?", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
 }
 
 /// ELF file type (wrapper around `goblin::elf::header::e_type`).
@@ -59,8 +96,9 @@ pub enum ElfType {
 }
 
 /// Result type for RPATH/RUNPATH validation.
-/// Ok(()) means valid, Err contains list of invalid path error messages.
-type ValidationResult = std::result::Result<(), Vec<String>>;
+/// Ok(()) means valid, Err contains the list of invalid entries, each with a suggested fix
+/// where one could be computed.
+type ValidationResult = std::result::Result<(), Vec<InvalidPath>>;
 
 /// Parsed ELF file information.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -69,6 +107,31 @@ pub struct Elf {
     dependencies: Vec<String>,
     rpath: Vec<String>,
     runpath: Vec<String>,
+    /// `$LIB`/`${LIB}` substitution: `"lib64"` for 64-bit ELF classes, `"lib"` for 32-bit.
+    lib_dir: &'static str,
+    /// `$PLATFORM`/`${PLATFORM}` substitution: the architecture name for `e_machine`.
+    platform: String,
+    /// Required symbol versions from the `.gnu.version_r` (verneed) section, e.g.
+    /// `GLIBC_2.27`, `GLIBCXX_3.4.25`.
+    version_requirements: Vec<String>,
+    /// The same requirements as `version_requirements`, grouped by the `DT_NEEDED` soname each
+    /// verneed entry requires them from (e.g. `"libc.so.6" -> ["GLIBC_2.34"]`), so a resolved
+    /// dependency's own definitions can be checked against only what it's actually required to
+    /// provide.
+    version_requirements_by_dependency: HashMap<String, Vec<String>>,
+    /// Defined global/weak dynamic symbols this ELF exports, each suffixed with `@VERSION` if
+    /// the `.gnu.version_d` section assigns it a GNU symbol version.
+    exported_symbols: Vec<String>,
+    /// Undefined *global*-bound dynamic symbols this ELF expects a dependency to provide, each
+    /// suffixed with `@VERSION` if the `.gnu.version_r` section requires a specific version. A
+    /// global undefined symbol that no dependency provides is a `MissingSymbol` error: the
+    /// binary is guaranteed to fail to load (or crash on first call).
+    undefined_symbols: Vec<String>,
+    /// Undefined *weak*-bound dynamic symbols this ELF may use if a dependency happens to
+    /// provide them, versioned the same way as `undefined_symbols`. Unlike a global undefined
+    /// symbol, the dynamic linker resolves an unsatisfied weak symbol to a null address instead
+    /// of failing, so these are tracked separately and never reported as `MissingSymbol` errors.
+    weak_undefined_symbols: Vec<String>,
 }
 
 // ELF files typically don't have extensions (aside from .so, .so.x, .so.x.y, etc.), so this is safe.
@@ -97,13 +160,26 @@ impl Elf {
     ///
     /// # Errors
     /// Returns an error if the file is not an ELF file, or if the RPATH or RUNPATH entries are invalid.
-    pub(crate) fn from_path(path: &Path) -> Result<Self> {
+    pub fn from_path(path: &Path) -> Result<Self> {
         let elf = Self::parse(path)?;
         Self::validate(&elf.rpath, &elf.runpath)
             .map_err(|paths| ElfError::InvalidPaths { paths })?;
         Ok(elf)
     }
 
+    /// Parse an ELF file from bytes already held in memory, e.g. streamed directly out of an
+    /// archive without ever touching disk. `path` is used only to label errors.
+    ///
+    /// # Errors
+    /// Returns an error if the bytes are not an ELF file, or if the RPATH or RUNPATH entries are invalid.
+    pub(crate) fn from_bytes(path: &Path, bytes: &[u8]) -> Result<Self> {
+        Self::check_magic(path, bytes)?;
+        let elf = Self::parse_bytes(path, bytes)?;
+        Self::validate(&elf.rpath, &elf.runpath)
+            .map_err(|paths| ElfError::InvalidPaths { paths })?;
+        Ok(elf)
+    }
+
     /// Get the ELF file type (executable, shared object, etc.).
     #[must_use]
     pub fn kind(&self) -> &ElfType {
@@ -128,6 +204,53 @@ impl Elf {
         &self.runpath
     }
 
+    /// Get the required symbol versions from the `.gnu.version_r` (verneed) section, e.g.
+    /// `GLIBC_2.27`, `GLIBCXX_3.4.25`.
+    #[must_use]
+    pub fn version_requirements(&self) -> &[String] {
+        &self.version_requirements
+    }
+
+    /// Get the required symbol versions grouped by the `DT_NEEDED` soname they're required
+    /// from, e.g. `"libc.so.6" -> ["GLIBC_2.34"]`.
+    #[must_use]
+    pub fn version_requirements_by_dependency(&self) -> &HashMap<String, Vec<String>> {
+        &self.version_requirements_by_dependency
+    }
+
+    /// Get the set of GNU symbol versions this ELF defines via `.gnu.version_d`, derived from
+    /// the `@VERSION` suffixes already carried by `exported_symbols`.
+    #[must_use]
+    pub fn defined_versions(&self) -> HashSet<&str> {
+        self.exported_symbols
+            .iter()
+            .filter_map(|symbol| symbol.split_once('@').map(|(_, version)| version))
+            .collect()
+    }
+
+    /// Get the defined global/weak dynamic symbols this ELF exports, each suffixed with
+    /// `@VERSION` if `.gnu.version_d` assigns it a GNU symbol version.
+    #[must_use]
+    pub fn exported_symbols(&self) -> &[String] {
+        &self.exported_symbols
+    }
+
+    /// Get the undefined *global*-bound dynamic symbols this ELF expects a dependency to
+    /// provide, each suffixed with `@VERSION` if `.gnu.version_r` requires a specific version.
+    /// Doesn't include weak undefined symbols; see `weak_undefined_symbols`.
+    #[must_use]
+    pub fn undefined_symbols(&self) -> &[String] {
+        &self.undefined_symbols
+    }
+
+    /// Get the undefined *weak*-bound dynamic symbols this ELF may use if a dependency happens
+    /// to provide them, versioned the same way as `undefined_symbols`. An unresolved weak symbol
+    /// isn't a loading error, so these are kept separate from `undefined_symbols`.
+    #[must_use]
+    pub fn weak_undefined_symbols(&self) -> &[String] {
+        &self.weak_undefined_symbols
+    }
+
     /// Normalize and resolve RPATH and RUNPATH entries into absolute filesystem paths.
     ///
     /// This function processes both `DT_RPATH` and `DT_RUNPATH` entries from the ELF file's
@@ -138,13 +261,15 @@ impl Elf {
     ///
     /// Paths are normalized according to the following rules:
     ///
-    /// 1. **`$ORIGIN` substitution**: The special token `$ORIGIN` (or `${ORIGIN}`) is replaced
-    ///    with the directory containing the ELF binary. This allows paths to be relative to
-    ///    the executable's location, enabling portable applications. For example, `$ORIGIN/../lib`
-    ///    resolves to the `lib` directory one level up from the binary's location.
+    /// 1. **Dynamic string token substitution**: `$ORIGIN`/`${ORIGIN}` is replaced with the
+    ///    directory containing the ELF binary, `$LIB`/`${LIB}` with `lib64`/`lib` (depending on
+    ///    the ELF class), and `$PLATFORM`/`${PLATFORM}` with the architecture name. This allows
+    ///    paths to be relative to the executable's location, enabling portable applications.
+    ///    For example, `$ORIGIN/../lib` resolves to the `lib` directory one level up from the
+    ///    binary's location.
     ///
-    /// 2. **Absolute paths**: Paths starting with `/` are preserved as-is (after normalization
-    ///    of any `$ORIGIN` tokens they may contain).
+    /// 2. **Absolute paths**: Paths starting with `/` are preserved as-is (after substitution
+    ///    of any dynamic string tokens they may contain).
     ///
     /// 3. **Relative paths without `$ORIGIN`**: These are **filtered out** and not included in
     ///    the result. Relative paths without `$ORIGIN` are resolved by the dynamic linker
@@ -177,40 +302,154 @@ impl Elf {
             // Do not parallelize this, as order is important, the list is typically too small to benefit from it anyway.
             self.runpath
                 .iter()
-                .filter_map(|path| Self::normalize_path(origin, path))
+                .filter_map(|path| self.normalize_path(origin, path))
                 .collect()
         } else if !self.rpath.is_empty() {
             // Do not parallelize this, as order is important, the list is typically too small to benefit from it anyway.
             self.rpath
                 .iter()
-                .filter_map(|path| Self::normalize_path(origin, path))
+                .filter_map(|path| self.normalize_path(origin, path))
                 .collect()
         } else {
             Vec::new()
         }
     }
 
-    fn normalize_path(origin: &Path, path: &str) -> Option<PathBuf> {
-        // Optimize: only convert origin to string and perform replacement if needed.
-        // The patterns $ORIGIN and ${ORIGIN} are mutually exclusive (different chars after $).
-        let resolved = if path.contains("${ORIGIN}") {
-            path.replace("${ORIGIN}", &origin.to_string_lossy())
-        } else if path.contains("$ORIGIN") {
-            path.replace("$ORIGIN", &origin.to_string_lossy())
-        } else {
-            path.to_string()
-        };
+    /// Normalize this ELF's `RPATH` entries alone, ignoring any `RUNPATH`.
+    ///
+    /// Unlike `normalize_paths` (which applies the dynamic linker's "RUNPATH wins outright"
+    /// precedence for resolving *this* object's own dependencies), callers tracking RPATH
+    /// inheritance down a dependency chain need the RPATH list on its own: `DT_RPATH` applies to
+    /// every descendant in the chain, not just the object that declares it.
+    #[must_use]
+    pub(crate) fn normalized_rpath(&self, origin: &Path) -> Vec<PathBuf> {
+        self.rpath
+            .iter()
+            .filter_map(|path| self.normalize_path(origin, path))
+            .collect()
+    }
+
+    /// Normalize this ELF's `RUNPATH` entries alone, ignoring any `RPATH`. See
+    /// `normalized_rpath` for why callers need the two kept separate.
+    #[must_use]
+    pub(crate) fn normalized_runpath(&self, origin: &Path) -> Vec<PathBuf> {
+        self.runpath
+            .iter()
+            .filter_map(|path| self.normalize_path(origin, path))
+            .collect()
+    }
+
+    /// Substitute the dynamic string tokens the loader recognizes in RPATH/RUNPATH entries:
+    /// `$ORIGIN`/`${ORIGIN}` (directory containing this ELF), `$LIB`/`${LIB}` (`lib`/`lib64`
+    /// depending on ELF class), and `$PLATFORM`/`${PLATFORM}` (the architecture name).
+    fn normalize_path(&self, origin: &Path, path: &str) -> Option<PathBuf> {
+        let resolved = self.expand_tokens(origin, path);
 
         // Absolute paths are always valid.
         if resolved.starts_with('/') {
             return Some(PathBuf::from(resolved).clean());
         }
-        // Since we already resolved the $ORIGIN, any path that is still
+        // Since we already resolved the dynamic tokens, any path that is still
         // relative is considered invalid.
         // These cases are handled in the constructor of the Elf struct.
         None
     }
 
+    /// Expand every RPATH and RUNPATH entry's `$ORIGIN`/`$LIB`/`$PLATFORM` tokens into the
+    /// concrete directory they'd resolve to, the ELF class/architecture already having been
+    /// recorded on `self` at parse time (so, unlike the dynamic linker's own token expansion,
+    /// no `is_64bit`/`platform` arguments are needed here).
+    ///
+    /// Unlike `normalize_paths`, which drops any entry that isn't anchored at `$ORIGIN` (the
+    /// dynamic linker would resolve it relative to the unknown process CWD instead), this keeps
+    /// every entry so diagnostics can show exactly what an invalid, unanchored entry like
+    /// `foo/$LIB` would have expanded to.
+    #[must_use]
+    pub fn expand_search_paths(&self, origin: &Path) -> Vec<PathBuf> {
+        self.rpath
+            .iter()
+            .chain(self.runpath.iter())
+            .map(|path| PathBuf::from(self.expand_tokens(origin, path)).clean())
+            .collect()
+    }
+
+    fn expand_tokens(&self, origin: &Path, path: &str) -> String {
+        let resolved = Self::substitute_token(path, "ORIGIN", &origin.to_string_lossy());
+        let resolved = Self::substitute_token(&resolved, "LIB", self.lib_dir);
+        Self::substitute_token(&resolved, "PLATFORM", &self.platform)
+    }
+
+    /// Compute a minimal, portable RPATH for this ELF, the way rustc's own `get_rpaths` builds
+    /// one for relocatable installs: for each directory in `lib_dirs`, walk up from
+    /// `binary_dir` to their common ancestor with a `..` per step, then back down into the
+    /// target, and prefix the result with `$ORIGIN/` so it resolves relative to wherever the
+    /// binary ends up on disk rather than to a hardcoded absolute path. Entries are
+    /// deduplicated, preserving the order `lib_dirs` was given in.
+    ///
+    /// A `lib_dirs` entry sharing no common ancestor with `binary_dir` (e.g. a different
+    /// Windows drive) cannot be expressed relative to it, so it's emitted as an absolute path
+    /// unchanged.
+    ///
+    /// This is pure path arithmetic: it neither reads `self`'s existing RPATH/RUNPATH nor
+    /// touches the filesystem. Callers write the result back with `patchelf` and re-run
+    /// `Elf::from_path` to confirm it validates.
+    #[must_use]
+    pub fn fix_rpaths(&self, binary_dir: &Path, lib_dirs: &[PathBuf]) -> Vec<String> {
+        let mut seen = HashSet::new();
+        lib_dirs
+            .iter()
+            .map(|lib_dir| Self::relative_rpath_entry(binary_dir, lib_dir))
+            .filter(|entry| seen.insert(entry.clone()))
+            .collect()
+    }
+
+    /// Compute one `$ORIGIN`-relative (or, failing that, absolute) RPATH entry for `lib_dir`,
+    /// relative to `binary_dir`. See `fix_rpaths`.
+    fn relative_rpath_entry(binary_dir: &Path, lib_dir: &Path) -> String {
+        let binary_components: Vec<_> = binary_dir.components().collect();
+        let lib_components: Vec<_> = lib_dir.components().collect();
+
+        let common_len = binary_components
+            .iter()
+            .zip(lib_components.iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        // No shared ancestor at all: no relative path can express this, so fall back to the
+        // absolute directory unchanged.
+        if common_len == 0 {
+            return lib_dir.to_string_lossy().into_owned();
+        }
+
+        let mut relative = PathBuf::new();
+        for _ in 0..(binary_components.len() - common_len) {
+            relative.push("..");
+        }
+        for component in &lib_components[common_len..] {
+            relative.push(component);
+        }
+
+        if relative.as_os_str().is_empty() {
+            "$ORIGIN".to_string()
+        } else {
+            format!("$ORIGIN/{}", relative.to_string_lossy())
+        }
+    }
+
+    /// Replace `$<token>` or `${<token>}` with `value` wherever it appears in `path`.
+    /// The two forms are mutually exclusive per occurrence (different character after `$`).
+    fn substitute_token(path: &str, token: &str, value: &str) -> String {
+        let braced = format!("${{{token}}}");
+        let bare = format!("${token}");
+        if path.contains(&braced) {
+            path.replace(&braced, value)
+        } else if path.contains(&bare) {
+            path.replace(&bare, value)
+        } else {
+            path.to_string()
+        }
+    }
+
     /// Reads the entire file at path into bytes if the file is an ELF file.
     ///
     /// # Errors
@@ -274,13 +513,42 @@ impl Elf {
         Ok(bytes)
     }
 
+    /// Check the ELF magic bytes and minimum size of an in-memory buffer.
+    ///
+    /// # Errors
+    /// Returns an error if the buffer is too small, or doesn't start with the ELF magic bytes.
+    fn check_magic(path: &Path, bytes: &[u8]) -> Result<()> {
+        // ELF magic bytes: 0x7f followed by ASCII "ELF"
+        const ELF_MAGIC: [u8; 4] = [0x7f, 0x45, 0x4c, 0x46];
+
+        if bytes.len() < 64 {
+            return Err(ElfError::FileTooSmall {
+                path: path.to_path_buf(),
+            });
+        }
+        if bytes[..4] != ELF_MAGIC {
+            return Err(ElfError::NotElfFile {
+                path: path.to_path_buf(),
+            });
+        }
+        Ok(())
+    }
+
     /// Parse an ELF file from a path.
     ///
     /// # Errors
     /// Returns an error if the file is not an ELF file or cannot be read.
     fn parse(path: &Path) -> Result<Self> {
         let bytes = Self::read(path)?;
-        let elf = GoblinElf::parse(&bytes).map_err(|e| ElfError::ParseFailed {
+        Self::parse_bytes(path, &bytes)
+    }
+
+    /// Parse an ELF file from an in-memory buffer. `path` is used only to label errors.
+    ///
+    /// # Errors
+    /// Returns an error if the bytes cannot be parsed as an ELF file.
+    fn parse_bytes(path: &Path, bytes: &[u8]) -> Result<Self> {
+        let elf = GoblinElf::parse(bytes).map_err(|e| ElfError::ParseFailed {
             path: path.to_path_buf(),
             source: e,
         })?;
@@ -329,6 +597,28 @@ impl Elf {
             }
         }
 
+        let mut version_requirements = Vec::new();
+        let mut version_requirements_by_dependency: HashMap<String, Vec<String>> = HashMap::new();
+        if let Some(verneed) = &elf.verneed {
+            for need in verneed.iter() {
+                let soname = elf.dynstrtab.get_at(need.vn_file as usize);
+                for aux in need.iter() {
+                    if let Some(name) = elf.dynstrtab.get_at(aux.vna_name as usize) {
+                        version_requirements.push(name.to_string());
+                        if let Some(soname) = soname {
+                            version_requirements_by_dependency
+                                .entry(soname.to_string())
+                                .or_default()
+                                .push(name.to_string());
+                        }
+                    }
+                }
+            }
+        }
+
+        let (exported_symbols, undefined_symbols, weak_undefined_symbols) =
+            Self::parse_dynamic_symbols(&elf);
+
         Ok(Self {
             kind: match elf.header.e_type {
                 goblin::elf::header::ET_NONE => ElfType::None,
@@ -345,19 +635,142 @@ impl Elf {
             dependencies,
             rpath,
             runpath,
+            lib_dir: if elf.is_64 { "lib64" } else { "lib" },
+            platform: Self::platform_name(elf.header.e_machine),
+            version_requirements,
+            version_requirements_by_dependency,
+            exported_symbols,
+            undefined_symbols,
+            weak_undefined_symbols,
         })
     }
 
+    /// Classify every global/weak dynamic symbol as exported (defined), undefined, or weak
+    /// undefined, matching each to its GNU symbol version (if any) via `versym`. Local symbols
+    /// and the reserved empty symbol at index 0 are skipped, since neither is part of the
+    /// dynamic interface. Exported weak symbols are not distinguished from exported global ones,
+    /// since both satisfy a dependent's undefined symbol the same way; only the undefined side
+    /// needs the global/weak split (see `undefined_symbols` vs. `weak_undefined_symbols`).
+    fn parse_dynamic_symbols(elf: &GoblinElf<'_>) -> (Vec<String>, Vec<String>, Vec<String>) {
+        let verneed_versions = Self::verneed_versions_by_index(elf);
+        let verdef_versions = Self::verdef_versions_by_index(elf);
+
+        let mut exported_symbols = Vec::new();
+        let mut undefined_symbols = Vec::new();
+        let mut weak_undefined_symbols = Vec::new();
+        for (sym_idx, sym) in elf.dynsyms.iter().enumerate() {
+            if sym.st_name == 0 {
+                continue;
+            }
+            let bind = sym.st_bind();
+            if bind != STB_GLOBAL && bind != STB_WEAK {
+                continue;
+            }
+            let Some(name) = elf.dynstrtab.get_at(sym.st_name) else {
+                continue;
+            };
+
+            // `st_shndx == SHN_UNDEF` (0) means the symbol has no definition in this file.
+            if sym.st_shndx == 0 {
+                let version = Self::versioned_symbol_name(elf, sym_idx, name, &verneed_versions);
+                if bind == STB_WEAK {
+                    weak_undefined_symbols.push(version);
+                } else {
+                    undefined_symbols.push(version);
+                }
+            } else {
+                let version = Self::versioned_symbol_name(elf, sym_idx, name, &verdef_versions);
+                exported_symbols.push(version);
+            }
+        }
+        (exported_symbols, undefined_symbols, weak_undefined_symbols)
+    }
+
+    /// Look up `sym_idx`'s GNU symbol version (via `versym`) in `versions_by_index`, and append
+    /// it to `name` as `name@VERSION` if found; otherwise return `name` unversioned.
+    fn versioned_symbol_name(
+        elf: &GoblinElf<'_>,
+        sym_idx: usize,
+        name: &str,
+        versions_by_index: &HashMap<u16, String>,
+    ) -> String {
+        let Some(versym) = &elf.versym else {
+            return name.to_string();
+        };
+        let Some(entry) = versym.iter().nth(sym_idx) else {
+            return name.to_string();
+        };
+        // Version indices 0 (local) and 1 (global, unversioned) never carry a GNU symbol
+        // version: only indices >= 2 reference an actual verneed/verdef entry.
+        if entry.is_local() || entry.is_global() {
+            return name.to_string();
+        }
+        match versions_by_index.get(&entry.version()) {
+            Some(version) => format!("{name}@{version}"),
+            None => name.to_string(),
+        }
+    }
+
+    /// Map each `DT_VERNEED` version index to its required symbol version string (e.g.
+    /// `GLIBC_2.27`), for matching against `versym` entries of undefined symbols.
+    fn verneed_versions_by_index(elf: &GoblinElf<'_>) -> HashMap<u16, String> {
+        let mut versions = HashMap::new();
+        if let Some(verneed) = &elf.verneed {
+            for need in verneed.iter() {
+                for aux in need.iter() {
+                    if let Some(name) = elf.dynstrtab.get_at(aux.vna_name as usize) {
+                        versions.insert(aux.vna_other, name.to_string());
+                    }
+                }
+            }
+        }
+        versions
+    }
+
+    /// Map each `DT_VERDEF` version index to its defining symbol version string, for matching
+    /// against `versym` entries of exported symbols.
+    fn verdef_versions_by_index(elf: &GoblinElf<'_>) -> HashMap<u16, String> {
+        let mut versions = HashMap::new();
+        if let Some(verdef) = &elf.verdef {
+            for def in verdef.iter() {
+                // The first aux entry is the version's own name; any further entries are
+                // parent versions it depends on, not relevant for matching an export's own version.
+                if let Some(aux) = def.iter().next() {
+                    if let Some(name) = elf.dynstrtab.get_at(aux.vda_name as usize) {
+                        versions.insert(def.vd_ndx, name.to_string());
+                    }
+                }
+            }
+        }
+        versions
+    }
+
+    /// Map an ELF `e_machine` value to the architecture name the dynamic linker substitutes
+    /// for `$PLATFORM`/`${PLATFORM}`.
+    fn platform_name(e_machine: u16) -> String {
+        match e_machine {
+            goblin::elf::header::EM_X86_64 => "x86_64".to_string(),
+            goblin::elf::header::EM_386 => "i386".to_string(),
+            goblin::elf::header::EM_AARCH64 => "aarch64".to_string(),
+            goblin::elf::header::EM_ARM => "arm".to_string(),
+            goblin::elf::header::EM_PPC64 => "ppc64".to_string(),
+            goblin::elf::header::EM_S390 => "s390x".to_string(),
+            goblin::elf::header::EM_RISCV => "riscv".to_string(),
+            other => format!("unknown-{other}"),
+        }
+    }
+
     /// Validate RPATH and RUNPATH entries.
     ///
     /// This function checks that all RPATH and RUNPATH entries are valid according to the following rules:
     ///
     /// 1. **Absolute paths**: Paths starting with `/` are always valid.
-    /// 2. **`$ORIGIN` paths**: Paths containing `$ORIGIN` or `${ORIGIN}` are valid, as they can be
-    ///    resolved relative to the ELF binary's location.
-    /// 3. **Relative paths**: Relative paths without `$ORIGIN` are invalid, as they are resolved
-    ///    relative to the process's current working directory, which is unknown at analysis time
-    ///    and creates security risks (binary planting attacks).
+    /// 2. **Rtld token paths**: Paths starting with `$ORIGIN`, `$LIB`, or `$PLATFORM` (or their
+    ///    `${...}` forms) are valid, since the dynamic linker substitutes all three before the
+    ///    path is ever resolved relative to anything.
+    /// 3. **Relative paths**: Relative paths without a leading rtld token are invalid, as they
+    ///    are resolved relative to the process's current working directory, which is unknown at
+    ///    analysis time and creates security risks (binary planting attacks).
     ///
     /// # RPATH vs RUNPATH
     ///
@@ -373,16 +786,25 @@ impl Elf {
     /// Returns `Ok(())` if all paths are valid, or `Err` with a list of error messages describing
     /// which paths are invalid.
     fn validate(rpath: &[String], runpath: &[String]) -> ValidationResult {
+        // Entries already valid in either list are candidates for "did you mean" suggestions on
+        // the invalid ones, since both lists are substituted and searched the same way.
+        let valid_entries: Vec<String> = rpath
+            .iter()
+            .chain(runpath.iter())
+            .filter(|path| !Self::invalid_path(path))
+            .cloned()
+            .collect();
+
         let mut invalid_paths = Vec::new();
 
         // Validate RUNPATH if present
         if !runpath.is_empty() {
-            invalid_paths.extend(Self::collect_invalid_paths(runpath, "RUNPATH"));
+            invalid_paths.extend(Self::collect_invalid_paths(runpath, "RUNPATH", &valid_entries));
         }
 
         // Validate RPATH if present (even if RUNPATH is also present, as both exist in the ELF)
         if !rpath.is_empty() {
-            invalid_paths.extend(Self::collect_invalid_paths(rpath, "RPATH"));
+            invalid_paths.extend(Self::collect_invalid_paths(rpath, "RPATH", &valid_entries));
         }
 
         if invalid_paths.is_empty() {
@@ -392,70 +814,260 @@ impl Elf {
         }
     }
 
-    /// Collect invalid path error messages from a path list.
+    /// Collect invalid entries from a path list, each paired with a suggested correction.
     ///
     /// # Arguments
     ///
     /// * `paths` - The paths to validate
     /// * `prefix` - The prefix to use in error messages (e.g., "RPATH" or "RUNPATH")
-    ///
-    /// # Returns
-    ///
-    /// A vector of error messages for invalid paths.
-    fn collect_invalid_paths(paths: &[String], prefix: &str) -> Vec<String> {
+    /// * `valid_entries` - Entries already known valid in this binary, used as fallback
+    ///   suggestion candidates; see `suggest_correction`.
+    fn collect_invalid_paths(
+        paths: &[String],
+        prefix: &str,
+        valid_entries: &[String],
+    ) -> Vec<InvalidPath> {
         paths
             .iter()
             .filter(|path| Self::invalid_path(path))
-            .map(|path| format!("{prefix}: {path} is invalid"))
+            .map(|path| InvalidPath {
+                message: format!("{prefix}: {path} is invalid"),
+                suggestion: Self::suggest_correction(path, valid_entries),
+            })
             .collect()
     }
 
+    /// Suggest a corrected form for an invalid RPATH/RUNPATH entry.
+    ///
+    /// Recognizes the common mistakes first: a plain relative path (`../lib`, `./lib`) is
+    /// anchored at `$ORIGIN`; a literal directory prefix before an otherwise-valid token
+    /// (`build/$ORIGIN/../lib`) is dropped, keeping the token onward. For anything else, falls
+    /// back to the closest (by Levenshtein distance) already-valid entry in the same binary, or
+    /// the canonical `$ORIGIN/<path>` form, only suggesting it if it's close enough to plausibly
+    /// be what was meant rather than just the least-bad guess.
+    fn suggest_correction(path: &str, valid_entries: &[String]) -> Option<String> {
+        if let Some(rest) = path.strip_prefix("../") {
+            return Some(format!("$ORIGIN/../{rest}"));
+        }
+        if let Some(rest) = path.strip_prefix("./") {
+            return Some(format!("$ORIGIN/{rest}"));
+        }
+
+        let token_pos = Self::RTLD_TOKENS
+            .iter()
+            .filter_map(|token| {
+                path.find(&format!("${token}"))
+                    .into_iter()
+                    .chain(path.find(&format!("${{{token}}}")))
+                    .min()
+            })
+            .min();
+        if let Some(pos) = token_pos {
+            if pos > 0 {
+                return Some(path[pos..].to_string());
+            }
+        }
+
+        const MAX_SUGGESTION_DISTANCE: usize = 3;
+        let canonical = format!("$ORIGIN/{path}");
+        let mut best_distance = usize::MAX;
+        let mut best_candidate = String::new();
+        for candidate in valid_entries
+            .iter()
+            .map(String::as_str)
+            .chain(std::iter::once(canonical.as_str()))
+        {
+            let distance = levenshtein(path, candidate);
+            if distance < best_distance {
+                best_distance = distance;
+                best_candidate = candidate.to_string();
+            }
+        }
+
+        (best_distance <= MAX_SUGGESTION_DISTANCE).then_some(best_candidate)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn new_for_testing(rpath: Vec<String>, runpath: Vec<String>) -> Self {
+        Self::new_for_testing_with_version_requirements(rpath, runpath, Vec::new())
+    }
+
+    #[cfg(test)]
+    pub(crate) fn new_for_testing_with_version_requirements(
+        rpath: Vec<String>,
+        runpath: Vec<String>,
+        version_requirements: Vec<String>,
+    ) -> Self {
+        Self::new_for_testing_with_dependencies(rpath, runpath, version_requirements, Vec::new())
+    }
+
+    #[cfg(test)]
+    pub(crate) fn new_for_testing_with_dependencies(
+        rpath: Vec<String>,
+        runpath: Vec<String>,
+        version_requirements: Vec<String>,
+        dependencies: Vec<String>,
+    ) -> Self {
+        Self::new_for_testing_with_symbols(
+            rpath,
+            runpath,
+            version_requirements,
+            dependencies,
+            Vec::new(),
+            Vec::new(),
+        )
+    }
+
+    #[cfg(test)]
+    pub(crate) fn new_for_testing_with_symbols(
+        rpath: Vec<String>,
+        runpath: Vec<String>,
+        version_requirements: Vec<String>,
+        dependencies: Vec<String>,
+        exported_symbols: Vec<String>,
+        undefined_symbols: Vec<String>,
+    ) -> Self {
+        Self::new_for_testing_with_weak_symbols(
+            rpath,
+            runpath,
+            version_requirements,
+            dependencies,
+            exported_symbols,
+            undefined_symbols,
+            Vec::new(),
+        )
+    }
+
+    #[cfg(test)]
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new_for_testing_with_weak_symbols(
+        rpath: Vec<String>,
+        runpath: Vec<String>,
+        version_requirements: Vec<String>,
+        dependencies: Vec<String>,
+        exported_symbols: Vec<String>,
+        undefined_symbols: Vec<String>,
+        weak_undefined_symbols: Vec<String>,
+    ) -> Self {
+        Self::new_for_testing_with_version_requirements_by_dependency(
+            rpath,
+            runpath,
+            version_requirements,
+            dependencies,
+            exported_symbols,
+            undefined_symbols,
+            weak_undefined_symbols,
+            HashMap::new(),
+        )
+    }
+
+    #[cfg(test)]
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new_for_testing_with_version_requirements_by_dependency(
+        rpath: Vec<String>,
+        runpath: Vec<String>,
+        version_requirements: Vec<String>,
+        dependencies: Vec<String>,
+        exported_symbols: Vec<String>,
+        undefined_symbols: Vec<String>,
+        weak_undefined_symbols: Vec<String>,
+        version_requirements_by_dependency: HashMap<String, Vec<String>>,
+    ) -> Self {
+        Self {
+            kind: ElfType::Executable,
+            dependencies,
+            rpath,
+            runpath,
+            lib_dir: "lib64",
+            platform: "x86_64".to_string(),
+            version_requirements,
+            version_requirements_by_dependency,
+            exported_symbols,
+            undefined_symbols,
+            weak_undefined_symbols,
+        }
+    }
+
+    /// Rtld tokens the dynamic linker substitutes before a relative RPATH/RUNPATH entry is
+    /// resolved against anything else, so a path starting with one of them (in either its bare
+    /// `$TOKEN` or braced `${TOKEN}` form) is anchored rather than CWD-relative.
+    const RTLD_TOKENS: &'static [&'static str] = &["ORIGIN", "LIB", "PLATFORM"];
+
     /// Check if a path is invalid.
     ///
-    /// A path is invalid if it is a relative path without `$ORIGIN` substitution, or if
-    /// `$ORIGIN` appears after relative path components (like `../` or `./`).
+    /// A path is invalid if it is a relative path without a leading rtld token substitution, or
+    /// if a token appears after relative path components (like `../` or `./`).
     ///
-    /// The dynamic linker substitutes `$ORIGIN` with the absolute path of the binary's directory.
-    /// However, if relative components (like `../` or `./`) appear before `$ORIGIN`, those
+    /// The dynamic linker substitutes `$ORIGIN`/`$LIB`/`$PLATFORM` with an absolute directory
+    /// (the binary's own directory, for `$ORIGIN`) before the path is resolved any further.
+    /// However, if relative components (like `../` or `./`) appear before the token, those
     /// components are resolved relative to the current working directory first, which creates
     /// security risks and unpredictable behavior.
     ///
     /// Valid paths:
     /// - Absolute paths: `/usr/lib`
-    /// - Paths with `$ORIGIN` at start: `$ORIGIN/../lib`, `${ORIGIN}/lib`
+    /// - Paths with a token at start: `$ORIGIN/../lib`, `${ORIGIN}/lib`, `$LIB/subdir`
     ///
     /// Invalid paths:
-    /// - Relative paths without `$ORIGIN`: `../lib`, `./lib`, `lib`
-    /// - Paths with relative components before `$ORIGIN`: `../${ORIGIN}/lib`, `./$ORIGIN/lib`
+    /// - Relative paths without a leading token: `../lib`, `./lib`, `lib`
+    /// - Paths with relative components before the token: `../${ORIGIN}/lib`, `./$ORIGIN/lib`
     fn invalid_path(path: &str) -> bool {
         // Absolute paths are always valid
         if path.starts_with('/') {
             return false;
         }
 
-        // Check if path contains $ORIGIN or ${ORIGIN}
-        let origin_pos = path.find("$ORIGIN").or_else(|| path.find("${ORIGIN}"));
-
-        if let Some(pos) = origin_pos {
-            // For non-absolute paths, $ORIGIN must be at the very start (byte position 0)
-            // Any text before $ORIGIN would be resolved relative to CWD first,
-            // checking byte position is safe here since $ORIGIN is ASCII and it's at the start of the string.
+        // Find the earliest occurrence of any rtld token, in either its bare or braced form.
+        let token_pos = Self::RTLD_TOKENS
+            .iter()
+            .filter_map(|token| {
+                path.find(&format!("${token}"))
+                    .into_iter()
+                    .chain(path.find(&format!("${{{token}}}")))
+                    .min()
+            })
+            .min();
+
+        if let Some(pos) = token_pos {
+            // For non-absolute paths, the token must be at the very start (byte position 0).
+            // Any text before it would be resolved relative to CWD first; checking byte position
+            // is safe here since every token is ASCII and anchored at the start of the string.
             if pos != 0 {
-                // Any content before $ORIGIN in a relative path is invalid
-                // because it would be resolved relative to CWD before $ORIGIN substitution
+                // Any content before the token in a relative path is invalid because it would be
+                // resolved relative to CWD before the token is substituted.
                 return true;
             }
-            // $ORIGIN at the start is valid
+            // Token at the start is valid.
             return false;
         }
 
-        // Relative paths without $ORIGIN are invalid
+        // Relative paths without a leading rtld token are invalid.
         // They are resolved relative to current working directory of the process,
         // which is unknown at analysis time and creates security risks.
         true
     }
 }
 
+/// Levenshtein (single-character insert/delete/substitute) edit distance between two strings.
+/// Used by `Elf::suggest_correction` to find the closest already-valid RPATH/RUNPATH entry to an
+/// invalid one.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for (i, a_ch) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, b_ch) in b.iter().enumerate() {
+            let cost = usize::from(a_ch != b_ch);
+            curr[j + 1] = (prev[j + 1] + 1).min(curr[j] + 1).min(prev[j] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -480,9 +1092,9 @@ mod tests {
     fn test_normalize_path_absolute() {
         let path = PathBuf::from("/usr/bin/test_binary");
         let origin = path.parent().unwrap_or_else(|| Path::new("/"));
-        let rpath = "/usr/lib".to_string();
+        let elf = Elf::new_for_testing(Vec::new(), Vec::new());
 
-        let result = Elf::normalize_path(origin, &rpath);
+        let result = elf.normalize_path(origin, "/usr/lib");
         assert_eq!(result, Some(PathBuf::from("/usr/lib")));
     }
 
@@ -490,9 +1102,9 @@ mod tests {
     fn test_normalize_rpath_relative() {
         let path = PathBuf::from("/usr/bin/test_binary");
         let origin = path.parent().unwrap_or_else(|| Path::new("/"));
-        let rpath = "../lib".to_string();
+        let elf = Elf::new_for_testing(Vec::new(), Vec::new());
 
-        let result = Elf::normalize_path(origin, &rpath);
+        let result = elf.normalize_path(origin, "../lib");
         // Relative paths without $ORIGIN return None
         assert_eq!(result, None);
     }
@@ -501,27 +1113,16 @@ mod tests {
     fn test_normalize_path_origin_not_at_start() {
         let path = PathBuf::from("/usr/bin/test_binary");
         let origin = path.parent().unwrap_or_else(|| Path::new("/"));
+        let elf = Elf::new_for_testing(Vec::new(), Vec::new());
 
         // Paths with $ORIGIN not at the start should return None (invalid)
-        assert_eq!(
-            Elf::normalize_path(origin, &"../$ORIGIN/lib".to_string()),
-            None
-        );
-        assert_eq!(
-            Elf::normalize_path(origin, &"./$ORIGIN/lib".to_string()),
-            None
-        );
-        assert_eq!(
-            Elf::normalize_path(origin, &"../${ORIGIN}/lib".to_string()),
-            None
-        );
-        assert_eq!(
-            Elf::normalize_path(origin, &"prefix/$ORIGIN/lib".to_string()),
-            None
-        );
+        assert_eq!(elf.normalize_path(origin, "../$ORIGIN/lib"), None);
+        assert_eq!(elf.normalize_path(origin, "./$ORIGIN/lib"), None);
+        assert_eq!(elf.normalize_path(origin, "../${ORIGIN}/lib"), None);
+        assert_eq!(elf.normalize_path(origin, "prefix/$ORIGIN/lib"), None);
 
         // But $ORIGIN at the start should work
-        let result = Elf::normalize_path(origin, &"$ORIGIN/../lib".to_string());
+        let result = elf.normalize_path(origin, "$ORIGIN/../lib");
         assert!(result.is_some());
         let resolved = result.unwrap();
         assert_eq!(resolved.to_string_lossy(), "/usr/lib");
@@ -531,9 +1132,9 @@ mod tests {
     fn test_normalize_rpath_origin() {
         let path = PathBuf::from("/usr/bin/test_binary");
         let origin = path.parent().unwrap_or_else(|| Path::new("/"));
-        let rpath = "$ORIGIN/../lib".to_string();
+        let elf = Elf::new_for_testing(Vec::new(), Vec::new());
 
-        let resolved = Elf::normalize_path(origin, &rpath);
+        let resolved = elf.normalize_path(origin, "$ORIGIN/../lib");
         assert!(resolved.is_some());
         let resolved = resolved.unwrap();
         // $ORIGIN/../lib with origin /usr/bin resolves to /usr/bin/../lib which cleans to /usr/lib
@@ -544,27 +1145,59 @@ mod tests {
     fn test_normalize_path_origin_braces() {
         let path = PathBuf::from("/usr/bin/test_binary");
         let origin = path.parent().unwrap_or_else(|| Path::new("/"));
-        let rpath = "${ORIGIN}/../lib".to_string();
+        let elf = Elf::new_for_testing(Vec::new(), Vec::new());
 
-        let resolved = Elf::normalize_path(origin, &rpath);
+        let resolved = elf.normalize_path(origin, "${ORIGIN}/../lib");
         assert!(resolved.is_some());
         let resolved = resolved.unwrap();
         // ${ORIGIN}/../lib with origin /usr/bin resolves to /usr/bin/../lib which cleans to /usr/lib
         assert_eq!(resolved, PathBuf::from("/usr/lib"));
     }
 
+    #[test]
+    fn test_normalize_path_lib_token() {
+        let path = PathBuf::from("/usr/bin/test_binary");
+        let origin = path.parent().unwrap_or_else(|| Path::new("/"));
+        let elf = Elf::new_for_testing(Vec::new(), Vec::new());
+
+        let resolved = elf
+            .normalize_path(origin, "$ORIGIN/../$LIB")
+            .expect("should resolve");
+        assert_eq!(resolved, PathBuf::from("/usr/lib64"));
+
+        let resolved = elf
+            .normalize_path(origin, "$ORIGIN/../${LIB}")
+            .expect("should resolve");
+        assert_eq!(resolved, PathBuf::from("/usr/lib64"));
+    }
+
+    #[test]
+    fn test_normalize_path_platform_token() {
+        let path = PathBuf::from("/usr/bin/test_binary");
+        let origin = path.parent().unwrap_or_else(|| Path::new("/"));
+        let elf = Elf::new_for_testing(Vec::new(), Vec::new());
+
+        let resolved = elf
+            .normalize_path(origin, "$ORIGIN/../lib/$PLATFORM")
+            .expect("should resolve");
+        assert_eq!(resolved, PathBuf::from("/usr/lib/x86_64"));
+
+        let resolved = elf
+            .normalize_path(origin, "$ORIGIN/../lib/${PLATFORM}")
+            .expect("should resolve");
+        assert_eq!(resolved, PathBuf::from("/usr/lib/x86_64"));
+    }
+
     #[test]
     fn test_normalize_paths() {
         let path = PathBuf::from("/usr/bin/test_binary");
         let origin = path.parent().unwrap_or_else(|| Path::new("/"));
 
         // Test with RUNPATH (takes precedence over RPATH)
-        let elf = Elf {
-            kind: ElfType::Executable,
-            dependencies: Vec::new(),
-            rpath: vec!["/usr/lib".to_string()],
-            runpath: vec!["/opt/lib".to_string()],
-        };
+        let elf = Elf::new_for_testing(
+            vec!["/usr/lib".to_string()],
+            vec!["/opt/lib".to_string()],
+        );
 
         let normalized = elf.normalize_paths(origin);
         // When RUNPATH is present, only RUNPATH is processed (RPATH is ignored)
@@ -572,18 +1205,118 @@ mod tests {
         assert_eq!(normalized, vec![PathBuf::from("/opt/lib")]);
 
         // Test with only RPATH
-        let elf_rpath_only = Elf {
-            kind: ElfType::Executable,
-            dependencies: Vec::new(),
-            rpath: vec!["/usr/lib".to_string()],
-            runpath: Vec::new(),
-        };
+        let elf_rpath_only = Elf::new_for_testing(vec!["/usr/lib".to_string()], Vec::new());
 
         let normalized_rpath = elf_rpath_only.normalize_paths(origin);
         assert_eq!(normalized_rpath.len(), 1);
         assert_eq!(normalized_rpath, vec![PathBuf::from("/usr/lib")]);
     }
 
+    #[test]
+    fn test_normalized_rpath_and_runpath_are_kept_separate() {
+        let path = PathBuf::from("/usr/bin/test_binary");
+        let origin = path.parent().unwrap_or_else(|| Path::new("/"));
+        let elf = Elf::new_for_testing(vec!["/usr/lib".to_string()], vec!["/opt/lib".to_string()]);
+
+        // Unlike `normalize_paths`, both lists are available independently, regardless of which
+        // one the dynamic linker would actually honor for this object's own dependencies.
+        assert_eq!(elf.normalized_rpath(origin), vec![PathBuf::from("/usr/lib")]);
+        assert_eq!(elf.normalized_runpath(origin), vec![PathBuf::from("/opt/lib")]);
+    }
+
+    #[test]
+    fn test_expand_search_paths_substitutes_all_three_tokens() {
+        let elf = Elf::new_for_testing(
+            vec!["$ORIGIN/../$LIB/$PLATFORM".to_string()],
+            Vec::new(),
+        );
+        let origin = Path::new("/opt/app/bin");
+
+        assert_eq!(
+            elf.expand_search_paths(origin),
+            vec![PathBuf::from("/opt/app/lib64/x86_64")]
+        );
+    }
+
+    #[test]
+    fn test_expand_search_paths_keeps_unanchored_relative_entries() {
+        // Unlike `normalize_paths`, an entry the dynamic linker would reject (no leading
+        // `$ORIGIN`/`$LIB`/`$PLATFORM`) is still expanded and returned, for diagnostics.
+        let elf = Elf::new_for_testing(vec!["foo/$LIB".to_string()], Vec::new());
+        let origin = Path::new("/opt/app/bin");
+
+        assert_eq!(elf.expand_search_paths(origin), vec![PathBuf::from("foo/lib64")]);
+    }
+
+    #[test]
+    fn test_expand_search_paths_includes_both_rpath_and_runpath() {
+        let elf = Elf::new_for_testing(vec!["/usr/lib".to_string()], vec!["/opt/lib".to_string()]);
+        let origin = Path::new("/usr/bin");
+
+        assert_eq!(
+            elf.expand_search_paths(origin),
+            vec![PathBuf::from("/usr/lib"), PathBuf::from("/opt/lib")]
+        );
+    }
+
+    #[test]
+    fn test_fix_rpaths_walks_up_to_common_ancestor() {
+        let elf = Elf::new_for_testing(Vec::new(), Vec::new());
+        let binary_dir = Path::new("/opt/app/bin");
+        let lib_dirs = vec![PathBuf::from("/opt/app/lib")];
+
+        assert_eq!(elf.fix_rpaths(binary_dir, &lib_dirs), vec!["$ORIGIN/../lib"]);
+    }
+
+    #[test]
+    fn test_fix_rpaths_deeper_target_descends_from_origin() {
+        let elf = Elf::new_for_testing(Vec::new(), Vec::new());
+        let binary_dir = Path::new("/opt/app/bin");
+        let lib_dirs = vec![PathBuf::from("/opt/app/bin/plugins")];
+
+        assert_eq!(
+            elf.fix_rpaths(binary_dir, &lib_dirs),
+            vec!["$ORIGIN/plugins"]
+        );
+    }
+
+    #[test]
+    fn test_fix_rpaths_same_directory_as_binary_is_origin() {
+        let elf = Elf::new_for_testing(Vec::new(), Vec::new());
+        let binary_dir = Path::new("/opt/app/bin");
+        let lib_dirs = vec![PathBuf::from("/opt/app/bin")];
+
+        assert_eq!(elf.fix_rpaths(binary_dir, &lib_dirs), vec!["$ORIGIN"]);
+    }
+
+    #[test]
+    fn test_fix_rpaths_deduplicates_preserving_order() {
+        let elf = Elf::new_for_testing(Vec::new(), Vec::new());
+        let binary_dir = Path::new("/opt/app/bin");
+        let lib_dirs = vec![
+            PathBuf::from("/opt/app/lib"),
+            PathBuf::from("/usr/lib"),
+            PathBuf::from("/opt/app/lib"),
+        ];
+
+        assert_eq!(
+            elf.fix_rpaths(binary_dir, &lib_dirs),
+            vec!["$ORIGIN/../lib", "$ORIGIN/../../../usr/lib"]
+        );
+    }
+
+    #[test]
+    fn test_fix_rpaths_no_common_ancestor_falls_back_to_absolute() {
+        let elf = Elf::new_for_testing(Vec::new(), Vec::new());
+        let binary_dir = Path::new("relative/bin");
+        let lib_dirs = vec![PathBuf::from("/opt/app/lib")];
+
+        assert_eq!(
+            elf.fix_rpaths(binary_dir, &lib_dirs),
+            vec!["/opt/app/lib"]
+        );
+    }
+
     #[test]
     fn test_validate_absolute_paths() {
         let rpath = vec!["/usr/lib".to_string(), "/opt/lib".to_string()];
@@ -598,14 +1331,26 @@ mod tests {
         assert!(Elf::validate(&rpath, &runpath).is_ok());
     }
 
+    #[test]
+    fn test_validate_lib_and_platform_paths() {
+        let rpath = vec![
+            "$LIB/subdir".to_string(),
+            "${LIB}/subdir".to_string(),
+            "$PLATFORM/subdir".to_string(),
+            "${PLATFORM}/subdir".to_string(),
+        ];
+        let runpath = Vec::new();
+        assert!(Elf::validate(&rpath, &runpath).is_ok());
+    }
+
     #[test]
     fn test_validate_invalid_relative_paths() {
         let rpath = vec!["../lib".to_string(), "./lib".to_string()];
         let runpath = Vec::new();
         let errors = Elf::validate(&rpath, &runpath).expect_err("Expected invalid paths");
         assert_eq!(errors.len(), 2);
-        assert!(errors.iter().any(|e| e.contains("../lib")));
-        assert!(errors.iter().any(|e| e.contains("./lib")));
+        assert!(errors.iter().any(|e| e.message().contains("../lib")));
+        assert!(errors.iter().any(|e| e.message().contains("./lib")));
     }
 
     #[test]
@@ -617,10 +1362,10 @@ mod tests {
         assert_eq!(errors.len(), 2); // One from RPATH, one from RUNPATH
         assert!(errors
             .iter()
-            .any(|e| e.contains("RPATH") && e.contains("../lib")));
+            .any(|e| e.message().contains("RPATH") && e.message().contains("../lib")));
         assert!(errors
             .iter()
-            .any(|e| e.contains("RUNPATH") && e.contains("./lib")));
+            .any(|e| e.message().contains("RUNPATH") && e.message().contains("./lib")));
     }
 
     #[test]
@@ -629,7 +1374,7 @@ mod tests {
         let runpath = Vec::new();
         let errors = Elf::validate(&rpath, &runpath).expect_err("Expected invalid paths");
         assert_eq!(errors.len(), 1);
-        assert!(errors.iter().any(|e| e.contains("../lib")));
+        assert!(errors.iter().any(|e| e.message().contains("../lib")));
     }
 
     #[test]
@@ -647,11 +1392,92 @@ mod tests {
         let errors = Elf::validate(&rpath, &runpath)
             .expect_err("Expected invalid paths with content before $ORIGIN");
         assert_eq!(errors.len(), 5);
-        assert!(errors.iter().any(|e| e.contains("../${ORIGIN}")));
-        assert!(errors.iter().any(|e| e.contains("./$ORIGIN")));
-        assert!(errors.iter().any(|e| e.contains("../$ORIGIN")));
-        assert!(errors.iter().any(|e| e.contains("some/path/$ORIGIN")));
-        assert!(errors.iter().any(|e| e.contains("prefix/${ORIGIN}")));
+        assert!(errors.iter().any(|e| e.message().contains("../${ORIGIN}")));
+        assert!(errors.iter().any(|e| e.message().contains("./$ORIGIN")));
+        assert!(errors.iter().any(|e| e.message().contains("../$ORIGIN")));
+        assert!(errors.iter().any(|e| e.message().contains("some/path/$ORIGIN")));
+        assert!(errors.iter().any(|e| e.message().contains("prefix/${ORIGIN}")));
+    }
+
+    #[test]
+    fn test_validate_lib_with_relative_prefix() {
+        // Same rule applies to $LIB/$PLATFORM as to $ORIGIN: content before the token is invalid.
+        let rpath = vec!["../$LIB/lib".to_string(), "some/path/$PLATFORM/lib".to_string()];
+        let runpath = Vec::new();
+        let errors = Elf::validate(&rpath, &runpath)
+            .expect_err("Expected invalid paths with content before $LIB/$PLATFORM");
+        assert_eq!(errors.len(), 2);
+    }
+
+    #[test]
+    fn test_version_requirements_accessor() {
+        let elf = Elf::new_for_testing_with_version_requirements(
+            Vec::new(),
+            Vec::new(),
+            vec!["GLIBC_2.27".to_string(), "GLIBCXX_3.4.25".to_string()],
+        );
+        assert_eq!(
+            elf.version_requirements(),
+            &["GLIBC_2.27".to_string(), "GLIBCXX_3.4.25".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_version_requirements_by_dependency_accessor() {
+        let mut by_dependency = HashMap::new();
+        by_dependency.insert("libc.so.6".to_string(), vec!["GLIBC_2.34".to_string()]);
+        let elf = Elf::new_for_testing_with_version_requirements_by_dependency(
+            Vec::new(),
+            Vec::new(),
+            vec!["GLIBC_2.34".to_string()],
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            by_dependency.clone(),
+        );
+        assert_eq!(elf.version_requirements_by_dependency(), &by_dependency);
+    }
+
+    #[test]
+    fn test_defined_versions_derives_from_exported_symbol_suffixes() {
+        let elf = Elf::new_for_testing_with_symbols(
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            vec!["malloc@GLIBC_2.34".to_string(), "free@GLIBC_2.2.5".to_string()],
+            Vec::new(),
+        );
+        let defined = elf.defined_versions();
+        assert!(defined.contains("GLIBC_2.34"));
+        assert!(defined.contains("GLIBC_2.2.5"));
+        assert_eq!(defined.len(), 2);
+    }
+
+    #[test]
+    fn test_exported_and_undefined_symbols_accessors() {
+        let elf = Elf::new_for_testing_with_symbols(
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            vec!["my_exported_fn".to_string()],
+            vec!["malloc@GLIBC_2.2.5".to_string()],
+        );
+        assert_eq!(elf.exported_symbols(), &["my_exported_fn".to_string()]);
+        assert_eq!(elf.undefined_symbols(), &["malloc@GLIBC_2.2.5".to_string()]);
+    }
+
+    #[test]
+    fn test_dependencies_accessor_with_testing_constructor() {
+        let elf = Elf::new_for_testing_with_dependencies(
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            vec!["libm.so.6".to_string()],
+        );
+        assert_eq!(elf.dependencies(), &["libm.so.6".to_string()]);
     }
 
     #[test]
@@ -666,6 +1492,55 @@ mod tests {
         assert!(Elf::validate(&rpath, &runpath).is_ok());
     }
 
+    #[test]
+    fn test_suggest_correction_anchors_dot_dot_relative_path() {
+        let rpath = vec!["../lib".to_string()];
+        let errors = Elf::validate(&rpath, &Vec::new()).expect_err("Expected invalid path");
+        assert_eq!(errors[0].suggestion(), Some("$ORIGIN/../lib"));
+    }
+
+    #[test]
+    fn test_suggest_correction_anchors_dot_relative_path() {
+        let rpath = vec!["./lib".to_string()];
+        let errors = Elf::validate(&rpath, &Vec::new()).expect_err("Expected invalid path");
+        assert_eq!(errors[0].suggestion(), Some("$ORIGIN/lib"));
+    }
+
+    #[test]
+    fn test_suggest_correction_drops_stray_prefix_before_token() {
+        let rpath = vec!["build/$ORIGIN/../lib".to_string()];
+        let errors = Elf::validate(&rpath, &Vec::new()).expect_err("Expected invalid path");
+        assert_eq!(errors[0].suggestion(), Some("$ORIGIN/../lib"));
+    }
+
+    #[test]
+    fn test_suggest_correction_falls_back_to_closest_valid_sibling_entry() {
+        // "$ORIGIN/lib" is already valid; "$ORIGI/lib" (missing the trailing "N") doesn't match
+        // any of the known-mistake patterns, so it falls back to the nearest valid sibling,
+        // one insertion away.
+        let rpath = vec!["$ORIGIN/lib".to_string(), "$ORIGI/lib".to_string()];
+        let errors = Elf::validate(&rpath, &Vec::new()).expect_err("Expected invalid path");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].suggestion(), Some("$ORIGIN/lib"));
+    }
+
+    #[test]
+    fn test_suggest_correction_withholds_distant_guesses() {
+        let rpath = vec!["completely/unrelated/nonsense/path".to_string()];
+        let errors = Elf::validate(&rpath, &Vec::new()).expect_err("Expected invalid path");
+        assert_eq!(errors[0].suggestion(), None);
+    }
+
+    #[test]
+    fn test_invalid_path_display_includes_suggestion() {
+        let rpath = vec!["../lib".to_string()];
+        let errors = Elf::validate(&rpath, &Vec::new()).expect_err("Expected invalid path");
+        assert_eq!(
+            errors[0].to_string(),
+            "RPATH: ../lib is invalid; did you mean `$ORIGIN/../lib`?"
+        );
+    }
+
     /// Helper to skip tests when fixture files are missing.
     /// Returns None if fixture is missing, Some(path) if it exists.
     fn require_fixture(name: &str) -> Option<PathBuf> {
@@ -746,7 +1621,7 @@ mod tests {
         match result {
             Err(ElfError::InvalidPaths { paths }) => {
                 assert!(
-                    paths.iter().any(|p| p.contains("../lib")),
+                    paths.iter().any(|p| p.message().contains("../lib")),
                     "Error should mention '../lib', got: {:?}",
                     paths
                 );
@@ -768,7 +1643,7 @@ mod tests {
         match result {
             Err(ElfError::InvalidPaths { paths }) => {
                 assert!(
-                    paths.iter().any(|p| p.contains("./lib")),
+                    paths.iter().any(|p| p.message().contains("./lib")),
                     "Error should mention './lib', got: {:?}",
                     paths
                 );
@@ -792,7 +1667,7 @@ mod tests {
                 assert!(
                     paths
                         .iter()
-                        .any(|p| p.contains("../$ORIGIN") || p.contains("RPATH")),
+                        .any(|p| p.message().contains("../$ORIGIN") || p.message().contains("RPATH")),
                     "Error should mention '../$ORIGIN' or 'RPATH', got: {:?}",
                     paths
                 );
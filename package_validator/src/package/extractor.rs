@@ -15,11 +15,17 @@ use walkdir::WalkDir;
 
 use super::elf::ElfError;
 use super::files::PackageFile;
+use super::filter::ExtractionFilter;
 use super::PackageFiles;
 
 /// Default timeout for package extraction commands (30 seconds).
 pub(crate) const DEFAULT_EXTRACTION_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// Upper bound on the total number of bytes an in-process extractor will read out of an
+/// archive, guarding against zip-bomb style packages. Command-based extractors rely on the
+/// timeout above instead, since the external tool owns the decompression.
+pub(crate) const MAX_EXTRACTED_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
 /// Result type for package operations.
 pub type PackageResult<T> = std::result::Result<T, PackageError>;
 
@@ -64,6 +70,14 @@ pub enum PackageError {
     },
     #[error("Elf error: {0}")]
     ElfError(#[from] ElfError),
+    #[error("Invalid glob pattern: {pattern}")]
+    InvalidGlobPattern {
+        pattern: String,
+        #[source]
+        source: globset::Error,
+    },
+    #[error("Extracted data exceeded the {limit}-byte limit: {path:?}")]
+    ExtractedSizeLimitExceeded { path: PathBuf, limit: u64 },
 }
 
 /// Wait for a child process to complete with a timeout.
@@ -160,34 +174,63 @@ impl<'a> ExtractedFile<'a> {
 pub(crate) trait PackageExtractor {
     const EXTENSION: &'static str; // Packages are identified by their extension.
 
+    /// Whether this extractor runs entirely in-process (no external command, no on-disk
+    /// extraction tree) as opposed to shelling out into `dest`. Declarative only: no code reads
+    /// it; `Package::new_with_persistence` picks each extension's persisting variant by name.
+    const IN_PROCESS: bool = false;
+
     /// Extract package contents to a destination directory.
     ///
     /// # Errors
     /// Returns an error if extraction fails.
-    fn extract(package: &Path, dest: &TempDir) -> PackageResult<PackageFiles>;
+    fn extract(package: &Path, dest: &TempDir, filter: &ExtractionFilter) -> PackageResult<PackageFiles>;
 
-    /// Walk the extracted directory and collect files.
+    /// Walk the extracted directory and collect files matching `filter`.
+    ///
+    /// Directories whose path prefix cannot match any include pattern are pruned via
+    /// `WalkDir::filter_entry` so excluded subtrees are never descended into.
     ///
     /// # Errors
     /// Returns an error if walking the directory fails or no files are found.
-    fn process(dest: &TempDir, package: &Path) -> PackageResult<PackageFiles> {
+    fn process(dest: &TempDir, package: &Path, filter: &ExtractionFilter) -> PackageResult<PackageFiles> {
         let mut files = PackageFiles::new();
-        for entry in WalkDir::new(dest.path()) {
+        let mut everything_filtered = false;
+
+        let walker = WalkDir::new(dest.path()).into_iter().filter_entry(|entry| {
+            if entry.file_type().is_dir() {
+                let extracted_dir = ExtractedFile::new(dest, entry.path());
+                filter.could_contain_match(&extracted_dir.package_path())
+            } else {
+                true
+            }
+        });
+
+        for entry in walker {
             let e = entry.map_err(|e| PackageError::WalkDirFailed {
                 path: package.to_path_buf(),
                 source: e,
             })?;
             if e.file_type().is_file() || e.file_type().is_symlink() {
                 let extracted_file = ExtractedFile::new(dest, e.path());
+                let package_path = extracted_file.package_path();
+                if !filter.matches_file(&package_path) {
+                    everything_filtered = true;
+                    continue;
+                }
                 let file = PackageFile::new(&extracted_file)?;
-                files.insert(extracted_file.package_path(), file);
+                files.insert(package_path, file);
             }
         }
 
         if files.is_empty() {
+            let reason = if everything_filtered {
+                "Extraction completed but every file was excluded by the extraction filter"
+            } else {
+                "Extraction completed but no files were found"
+            };
             Err(PackageError::ExtractionFailed {
                 path: package.to_path_buf(),
-                reason: "Extraction completed but no files were found".to_string(),
+                reason: reason.to_string(),
             })
         } else {
             Ok(files)
@@ -2,22 +2,85 @@
 // This file is part of Checkmk (https://checkmk.com). It is subject to the terms and
 // conditions defined in the file COPYING, which is part of this source code package.
 
-//! Defines types for different package file types (ELF, Symlink, Other).
+//! Defines types for different package file types (ELF, Mach-O/PE, Symlink, Other).
 
 use path_clean::PathClean;
 use serde::Serialize;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
+use super::binary::Binary;
 use super::elf::{Elf, ElfError};
 use super::extractor::{ExtractedFile, PackageError, PackageResult};
 
+/// A symlink's resolved target, plus enough information about the raw (pre-clean)
+/// target to detect when it path-traverses above the package root.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SymlinkTarget {
+    /// Cleaned, absolute target path, used to look the target up in the package.
+    normalized: PathBuf,
+    /// Target exactly as read from the symlink (before joining with the parent
+    /// directory or cleaning), used to reconstruct what `PathClean` discarded.
+    raw: PathBuf,
+    /// How many `..` components anywhere in `raw` remained unresolved after
+    /// accounting for the symlink's parent depth, i.e. how far above the package
+    /// root the raw target climbed. Zero for well-behaved symlinks.
+    excess_parent_refs: usize,
+}
+
+impl SymlinkTarget {
+    pub(crate) fn new(normalized: PathBuf, raw: PathBuf, excess_parent_refs: usize) -> Self {
+        Self {
+            normalized,
+            raw,
+            excess_parent_refs,
+        }
+    }
+
+    /// The cleaned, absolute target path.
+    #[must_use]
+    pub(crate) fn as_path(&self) -> &Path {
+        &self.normalized
+    }
+
+    /// The raw target exactly as read from the symlink, before joining or cleaning.
+    #[must_use]
+    pub(crate) fn raw(&self) -> &Path {
+        &self.raw
+    }
+
+    /// Whether the raw target climbed above the package root before normalization
+    /// clamped it back down.
+    #[must_use]
+    pub(crate) fn escapes_root(&self) -> bool {
+        self.excess_parent_refs > 0
+    }
+
+    #[cfg(test)]
+    pub(crate) fn new_for_testing(normalized: PathBuf) -> Self {
+        Self {
+            raw: normalized.clone(),
+            normalized,
+            excess_parent_refs: 0,
+        }
+    }
+}
+
 /// Represents a file in a package.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub enum PackageFile {
     File,
-    Symlink(PathBuf), // Stores the normalized target path of the symlink.
+    Symlink(SymlinkTarget),
     Elf(Elf),
+    /// A Mach-O or PE binary. Parsed far enough to report its kind and dependencies (see
+    /// `package::binary`), but not fed through the ELF-shaped SONAME/declared-dependency
+    /// machinery in `report`, which assumes ELF semantics throughout. Kept distinct from
+    /// `PackageFile::Elf` for that reason rather than folded into it.
+    Binary(Binary),
+    /// A license/copyright artifact (a Debian machine-readable `copyright`, an RPM
+    /// `usr/share/licenses/*` entry, or a bare `LICENSE`/`COPYING` file), captured with its raw
+    /// text so `report::totals::license` can derive an SPDX expression from it.
+    License(String),
 }
 
 impl PackageFile {
@@ -28,31 +91,232 @@ impl PackageFile {
     pub(crate) fn new(extracted_file: &ExtractedFile) -> PackageResult<Self> {
         let path = extracted_file.path();
         if path.is_symlink() {
-            let target = fs::read_link(path).map_err(|e| PackageError::ReadSymlinkFailed {
+            let raw_target = fs::read_link(path).map_err(|e| PackageError::ReadSymlinkFailed {
                 path: path.to_path_buf(),
                 source: e,
             })?;
-            // Resolve relative targets relative to the symlink's parent directory
-            let resolved_target = if target.is_absolute() {
-                target
-            } else {
-                // The target path should be relative within the package not the extraction directory.
-                extracted_file
-                    .package_path()
-                    .parent()
-                    .unwrap_or_else(|| Path::new("/"))
-                    .join(&target)
-            };
-            let normalized_target = resolved_target.clean();
-            return Ok(Self::Symlink(normalized_target));
+            return Ok(Self::new_symlink(&extracted_file.package_path(), raw_target));
+        }
+        if Self::is_license_artifact(&extracted_file.package_path()) {
+            return Ok(Self::License(fs::read_to_string(path).unwrap_or_default()));
         }
         if !Elf::is_invalid_extension(path) {
-            return match Elf::from_path(path) {
-                Ok(elf) => Ok(Self::Elf(elf)),
-                Err(ElfError::NotElfFile { .. } | ElfError::FileTooSmall { .. }) => Ok(Self::File),
-                Err(e) => Err(PackageError::ElfError(e)),
-            };
+            match Elf::from_path(path) {
+                Ok(elf) => return Ok(Self::Elf(elf)),
+                Err(ElfError::NotElfFile { .. } | ElfError::FileTooSmall { .. }) => {}
+                Err(e) => return Err(PackageError::ElfError(e)),
+            }
+            if let Ok(bytes) = fs::read(path) {
+                if let Ok(binary @ (Binary::MachO(_) | Binary::Pe(_))) =
+                    Binary::from_bytes(path, &bytes)
+                {
+                    return Ok(Self::Binary(binary));
+                }
+            }
+        }
+        Ok(Self::File)
+    }
+
+    /// Build a symlink package file from its package-absolute path and its raw (unresolved,
+    /// pre-clean) link target. Shared by on-disk extraction (`new`) and in-memory archive
+    /// extraction, which never materializes a symlink on disk to read back.
+    pub(crate) fn new_symlink(package_path: &Path, raw_target: PathBuf) -> Self {
+        let parent = package_path
+            .parent()
+            .unwrap_or_else(|| Path::new("/"))
+            .to_path_buf();
+        // Resolve relative targets relative to the symlink's parent directory
+        let resolved_target = if raw_target.is_absolute() {
+            raw_target.clone()
+        } else {
+            // The target path should be relative within the package not the extraction directory.
+            parent.join(&raw_target)
+        };
+        let normalized_target = resolved_target.clean();
+        let excess_parent_refs = if raw_target.is_absolute() {
+            0
+        } else {
+            Self::excess_parent_refs(&parent, &raw_target)
+        };
+        Self::Symlink(SymlinkTarget::new(
+            normalized_target,
+            raw_target,
+            excess_parent_refs,
+        ))
+    }
+
+    /// Build a regular-file package file from bytes already held in memory, e.g. streamed
+    /// directly out of an archive entry without ever touching disk.
+    ///
+    /// # Errors
+    /// Returns an error if the bytes look like an ELF file but fail to parse.
+    pub(crate) fn from_bytes(package_path: &Path, bytes: &[u8]) -> PackageResult<Self> {
+        if Self::is_license_artifact(package_path) {
+            return Ok(Self::License(String::from_utf8_lossy(bytes).into_owned()));
+        }
+        if !Elf::is_invalid_extension(package_path) {
+            match Elf::from_bytes(package_path, bytes) {
+                Ok(elf) => return Ok(Self::Elf(elf)),
+                Err(ElfError::NotElfFile { .. } | ElfError::FileTooSmall { .. }) => {}
+                Err(e) => return Err(PackageError::ElfError(e)),
+            }
+            if let Ok(binary @ (Binary::MachO(_) | Binary::Pe(_))) =
+                Binary::from_bytes(package_path, bytes)
+            {
+                return Ok(Self::Binary(binary));
+            }
         }
         Ok(Self::File)
     }
+
+    /// Whether a package-relative path climbs above the package root (`/`) via `..`
+    /// components anywhere in it, i.e. the same escape check used for symlink targets but applied to a path
+    /// that is already anchored at the root instead of a symlink's parent directory.
+    pub(crate) fn path_escapes_root(raw_path: &Path) -> bool {
+        Self::excess_parent_refs(Path::new("/"), raw_path) > 0
+    }
+
+    /// Whether a package-relative path is a license/copyright artifact: a Debian
+    /// machine-readable `usr/share/doc/*/copyright`, an RPM `usr/share/licenses/*` entry, or a
+    /// bare `LICENSE`/`COPYING` file anywhere in the package.
+    fn is_license_artifact(package_path: &Path) -> bool {
+        let file_name = package_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("");
+        if file_name.eq_ignore_ascii_case("copyright")
+            && package_path.components().any(|c| c.as_os_str() == "doc")
+        {
+            return true;
+        }
+        if package_path.components().any(|c| c.as_os_str() == "licenses") {
+            return true;
+        }
+        matches!(
+            file_name.to_ascii_uppercase().as_str(),
+            "LICENSE" | "LICENSE.TXT" | "LICENSE.MD" | "COPYING" | "COPYING.TXT" | "COPYING.LESSER"
+        )
+    }
+
+    /// Walk every component of a relative `raw_target`, tracking a virtual depth counter that
+    /// starts at `parent`'s own depth (the same stack-based resolution `PathClean` performs
+    /// internally), and count each `..` applied once that counter has already bottomed out at
+    /// zero, i.e. how far above the package root (`/`) the target would climb before `PathClean`
+    /// clamps it.
+    ///
+    /// Must walk the whole path rather than just its leading `..` run: an interspersed target
+    /// like `docs/../../../../etc/passwd` nets the same three-level climb as a leading-only
+    /// `../../../etc/passwd` once `docs/..` cancels out, and a leading-run-only check would
+    /// miss it entirely.
+    fn excess_parent_refs(parent: &Path, raw_target: &Path) -> usize {
+        let mut depth = parent
+            .components()
+            .filter(|c| matches!(c, Component::Normal(_)))
+            .count();
+        let mut excess = 0usize;
+        for component in raw_target.components() {
+            match component {
+                Component::ParentDir => {
+                    if depth == 0 {
+                        excess += 1;
+                    } else {
+                        depth -= 1;
+                    }
+                }
+                Component::Normal(_) => depth += 1,
+                Component::RootDir | Component::CurDir | Component::Prefix(_) => {}
+            }
+        }
+        excess
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_license_artifact_matches_debian_copyright() {
+        assert!(PackageFile::is_license_artifact(Path::new(
+            "/usr/share/doc/myapp/copyright"
+        )));
+    }
+
+    #[test]
+    fn test_is_license_artifact_matches_rpm_licenses_dir() {
+        assert!(PackageFile::is_license_artifact(Path::new(
+            "/usr/share/licenses/myapp/LICENSE"
+        )));
+    }
+
+    #[test]
+    fn test_is_license_artifact_matches_bare_license_file() {
+        assert!(PackageFile::is_license_artifact(Path::new(
+            "/opt/myapp/LICENSE"
+        )));
+        assert!(PackageFile::is_license_artifact(Path::new(
+            "/opt/myapp/COPYING"
+        )));
+    }
+
+    #[test]
+    fn test_is_license_artifact_ignores_unrelated_files() {
+        assert!(!PackageFile::is_license_artifact(Path::new(
+            "/usr/bin/myapp"
+        )));
+        assert!(!PackageFile::is_license_artifact(Path::new(
+            "/usr/share/doc/myapp/changelog.gz"
+        )));
+    }
+
+    #[test]
+    fn test_from_bytes_captures_license_text() {
+        let file = PackageFile::from_bytes(
+            Path::new("/usr/share/doc/myapp/copyright"),
+            b"Format: https://www.debian.org/doc/packaging-manuals/copyright-format/1.0/",
+        )
+        .unwrap();
+        assert!(matches!(file, PackageFile::License(text) if text.contains("Format:")));
+    }
+
+    #[test]
+    fn test_new_symlink_leading_parent_refs_escape_root() {
+        let file = PackageFile::new_symlink(
+            Path::new("/usr/bin/A"),
+            PathBuf::from("../../../../etc/passwd"),
+        );
+        match file {
+            PackageFile::Symlink(target) => assert!(target.escapes_root()),
+            other => panic!("Expected Symlink, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_new_symlink_interspersed_parent_refs_escape_root() {
+        // "docs/.." cancels out, leaving a net three-level climb from /usr/bin (one level above
+        // the root) that still escapes like the leading-only case above -- a check that only looked at the
+        // leading `..` run would miss this, since the first component here is `Normal("docs")`.
+        let file = PackageFile::new_symlink(
+            Path::new("/usr/bin/A"),
+            PathBuf::from("docs/../../../../etc/passwd"),
+        );
+        match file {
+            PackageFile::Symlink(target) => assert!(target.escapes_root()),
+            other => panic!("Expected Symlink, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_path_escapes_root_detects_interspersed_parent_refs() {
+        assert!(PackageFile::path_escapes_root(Path::new(
+            "docs/../../../../etc/passwd"
+        )));
+    }
+
+    #[test]
+    fn test_path_escapes_root_allows_well_behaved_relative_path() {
+        assert!(!PackageFile::path_escapes_root(Path::new(
+            "docs/../usr/bin/myapp"
+        )));
+    }
 }
@@ -0,0 +1,195 @@
+// Copyright (C) 2026 Checkmk GmbH - License: GNU General Public License v2
+// This file is part of Checkmk (https://checkmk.com). It is subject to the terms and
+// conditions defined in the file COPYING, which is part of this source code package.
+
+//! Include/exclude glob filtering applied while walking extracted package trees.
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::path::{Component, Path};
+
+use super::extractor::{PackageError, PackageResult};
+
+/// Path-based include/exclude filter applied during package extraction.
+///
+/// Patterns are compiled once into `GlobSet`s so matching a file against them is
+/// O(pattern count) regardless of how many files the package contains.
+pub struct ExtractionFilter {
+    includes: Option<GlobSet>,
+    excludes: Option<GlobSet>,
+    // Kept alongside `includes` to let `could_contain_match` test literal (non-glob)
+    // path prefixes, which `GlobSet` itself has no API for.
+    include_patterns: Vec<String>,
+}
+
+impl ExtractionFilter {
+    /// Create a filter that keeps every file (no include/exclude patterns).
+    #[must_use]
+    pub fn unfiltered() -> Self {
+        Self {
+            includes: None,
+            excludes: None,
+            include_patterns: Vec::new(),
+        }
+    }
+
+    /// Compile include/exclude glob patterns (e.g. `/usr/bin/**`, `/usr/share/doc/**`).
+    ///
+    /// A file is kept if it matches an include pattern (or no includes were given)
+    /// and matches no exclude pattern.
+    ///
+    /// # Errors
+    /// Returns an error if any pattern fails to compile.
+    pub fn new(includes: &[&str], excludes: &[&str]) -> PackageResult<Self> {
+        Ok(Self {
+            includes: Self::compile(includes)?,
+            excludes: Self::compile(excludes)?,
+            include_patterns: includes.iter().map(|p| (*p).to_string()).collect(),
+        })
+    }
+
+    fn compile(patterns: &[&str]) -> PackageResult<Option<GlobSet>> {
+        if patterns.is_empty() {
+            return Ok(None);
+        }
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            let glob = Glob::new(pattern).map_err(|e| PackageError::InvalidGlobPattern {
+                pattern: (*pattern).to_string(),
+                source: e,
+            })?;
+            builder.add(glob);
+        }
+        let set = builder
+            .build()
+            .map_err(|e| PackageError::InvalidGlobPattern {
+                pattern: patterns.join(", "),
+                source: e,
+            })?;
+        Ok(Some(set))
+    }
+
+    /// Whether a file at `package_path` (an absolute in-package path) should be kept.
+    #[must_use]
+    pub(crate) fn matches_file(&self, package_path: &Path) -> bool {
+        let included = self
+            .includes
+            .as_ref()
+            .is_none_or(|set| set.is_match(package_path));
+        let excluded = self
+            .excludes
+            .as_ref()
+            .is_some_and(|set| set.is_match(package_path));
+        included && !excluded
+    }
+
+    /// Whether `dir_path` could still contain files matching an include pattern.
+    ///
+    /// Used by `WalkDir::filter_entry` to prune whole subtrees: if no include
+    /// pattern's literal (non-glob) path prefix is compatible with `dir_path`,
+    /// nothing underneath it can ever match, so the traversal never descends into it.
+    /// Exclude patterns are intentionally not consulted here; exclusion is a file-level
+    /// decision so a broad include isn't short-circuited by a narrower exclude higher
+    /// up the tree (e.g. `/usr/share/doc/pkg/copyright` excluded, but a sibling
+    /// `/usr/share/doc/pkg/changelog.gz` still reachable under a different exclude).
+    #[must_use]
+    pub(crate) fn could_contain_match(&self, dir_path: &Path) -> bool {
+        if self.includes.is_none() {
+            return true;
+        }
+        self.include_patterns
+            .iter()
+            .any(|pattern| Self::literal_prefix_compatible(pattern, dir_path))
+    }
+
+    /// Check whether `dir_path` is compatible with the literal (non-glob) leading
+    /// components of `pattern`, i.e. whether `dir_path` could be a prefix of some
+    /// path that matches `pattern`.
+    fn literal_prefix_compatible(pattern: &str, dir_path: &Path) -> bool {
+        let pattern_components: Vec<&str> =
+            pattern.split('/').filter(|c| !c.is_empty()).collect();
+        // Skip the root `/`: the pattern side drops it too (empty split parts).
+        let dir_components: Vec<_> = dir_path
+            .components()
+            .filter(|c| !matches!(c, Component::RootDir))
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect();
+
+        for (pattern_component, dir_component) in
+            pattern_components.iter().zip(dir_components.iter())
+        {
+            if Self::is_glob_component(pattern_component) {
+                // Reached a wildcard segment: everything underneath is a candidate.
+                return true;
+            }
+            if *pattern_component != dir_component.as_ref() {
+                return false;
+            }
+        }
+        // Either `dir_path` ran out of components first (it's an ancestor of the
+        // pattern's literal prefix) or the pattern did (dir_path is at least as deep
+        // as the literal prefix and every shared component matched above).
+        true
+    }
+
+    fn is_glob_component(component: &str) -> bool {
+        component.contains(['*', '?', '[', '{'])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_unfiltered_matches_everything() {
+        let filter = ExtractionFilter::unfiltered();
+        assert!(filter.matches_file(&PathBuf::from("/usr/share/doc/readme")));
+        assert!(filter.could_contain_match(&PathBuf::from("/usr/share/doc")));
+    }
+
+    #[test]
+    fn test_include_only() {
+        let filter = ExtractionFilter::new(&["/usr/bin/**"], &[]).unwrap();
+        assert!(filter.matches_file(&PathBuf::from("/usr/bin/foo")));
+        assert!(!filter.matches_file(&PathBuf::from("/usr/share/doc/readme")));
+    }
+
+    #[test]
+    fn test_exclude_only() {
+        let filter = ExtractionFilter::new(&[], &["/usr/share/doc/**"]).unwrap();
+        assert!(filter.matches_file(&PathBuf::from("/usr/bin/foo")));
+        assert!(!filter.matches_file(&PathBuf::from("/usr/share/doc/readme")));
+    }
+
+    #[test]
+    fn test_include_and_exclude() {
+        let filter = ExtractionFilter::new(&["/usr/**"], &["/usr/share/doc/**"]).unwrap();
+        assert!(filter.matches_file(&PathBuf::from("/usr/bin/foo")));
+        assert!(!filter.matches_file(&PathBuf::from("/usr/share/doc/readme")));
+    }
+
+    #[test]
+    fn test_invalid_pattern_errors() {
+        let result = ExtractionFilter::new(&["["], &[]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_could_contain_match_prunes_unrelated_prefix() {
+        let filter = ExtractionFilter::new(&["/usr/bin/**"], &[]).unwrap();
+        assert!(filter.could_contain_match(&PathBuf::from("/usr")));
+        assert!(filter.could_contain_match(&PathBuf::from("/usr/bin")));
+        assert!(!filter.could_contain_match(&PathBuf::from("/usr/share")));
+        assert!(!filter.could_contain_match(&PathBuf::from("/etc")));
+    }
+
+    #[test]
+    fn test_could_contain_match_stops_pruning_at_wildcard() {
+        let filter = ExtractionFilter::new(&["/usr/lib/*/libfoo.so"], &[]).unwrap();
+        assert!(filter.could_contain_match(&PathBuf::from("/usr/lib/x86_64-linux-gnu")));
+        assert!(filter.could_contain_match(&PathBuf::from(
+            "/usr/lib/x86_64-linux-gnu/anything"
+        )));
+    }
+}
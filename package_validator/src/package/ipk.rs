@@ -0,0 +1,151 @@
+// Copyright (C) 2026 Checkmk GmbH - License: GNU General Public License v2
+// This file is part of Checkmk (https://checkmk.com). It is subject to the terms and
+// conditions defined in the file COPYING, which is part of this source code package.
+
+//! Implements IPK package extraction (the `.ipk` format used by webOS and OpenWrt). Like a
+//! `.deb`, an `.ipk` is an `ar` archive with a `data.tar.*` member, but unlike `DebExtractor`
+//! this unpacks that member onto disk into `dest` and reuses the shared `process()` walk, since
+//! embedded-device packages are typically small enough that the in-process streaming
+//! optimization `DebExtractor` uses isn't worth a second bespoke implementation.
+
+use std::path::Path;
+use tempfile::TempDir;
+
+use super::extractor::{PackageError, PackageExtractor, PackageResult};
+use super::filter::ExtractionFilter;
+use super::PackageFiles;
+
+pub(crate) struct IpkExtractor;
+
+impl PackageExtractor for IpkExtractor {
+    const EXTENSION: &'static str = "ipk";
+
+    /// Extract an IPK package by unpacking its `data.tar.*` member out of the outer `ar`
+    /// archive into `dest`, then walking `dest` the same way every other extractor does.
+    ///
+    /// # Errors
+    /// Returns an error if the package cannot be opened, has no `data.tar.*` member, uses an
+    /// unsupported compression, or the tarball fails to unpack.
+    fn extract(package: &Path, dest: &TempDir, filter: &ExtractionFilter) -> PackageResult<PackageFiles> {
+        let file = std::fs::File::open(package).map_err(|e| PackageError::ExtractionFailed {
+            path: package.to_path_buf(),
+            reason: format!("Failed to open package: {e}"),
+        })?;
+
+        let mut archive = ar::Archive::new(file);
+        let mut found_data_member = false;
+
+        while let Some(entry) = archive.next_entry() {
+            let entry = entry.map_err(|e| PackageError::ExtractionFailed {
+                path: package.to_path_buf(),
+                reason: format!("Failed to read .ipk archive member: {e}"),
+            })?;
+            let member_name = String::from_utf8_lossy(entry.header().identifier()).into_owned();
+            if !member_name.starts_with("data.tar") {
+                continue;
+            }
+            found_data_member = true;
+            Self::unpack_data_member(&member_name, entry, package, dest)?;
+            break; // An .ipk has exactly one data.tar.* member.
+        }
+
+        if !found_data_member {
+            return Err(PackageError::ExtractionFailed {
+                path: package.to_path_buf(),
+                reason: "No data.tar.* member found in .ipk archive".to_string(),
+            });
+        }
+
+        Self::process(dest, package, filter)
+    }
+}
+
+impl IpkExtractor {
+    /// Pick a decompressor for the `data.tar.*` member by its name suffix. Mirrors
+    /// `DebExtractor::decompress`.
+    fn decompress<'a>(
+        member_name: &str,
+        entry: impl std::io::Read + 'a,
+        package: &Path,
+    ) -> PackageResult<Box<dyn std::io::Read + 'a>> {
+        Ok(match member_name.rsplit_once('.').map(|(_, ext)| ext) {
+            Some("gz") => Box::new(flate2::read::GzDecoder::new(entry)),
+            Some("xz") => Box::new(xz2::read::XzDecoder::new(entry)),
+            Some("zst") => {
+                Box::new(zstd::stream::read::Decoder::new(entry).map_err(|e| {
+                    PackageError::ExtractionFailed {
+                        path: package.to_path_buf(),
+                        reason: format!("Failed to initialize zstd decoder: {e}"),
+                    }
+                })?)
+            }
+            Some("tar") | None => Box::new(entry),
+            Some(other) => {
+                return Err(PackageError::ExtractionFailed {
+                    path: package.to_path_buf(),
+                    reason: format!("Unsupported {member_name} compression: {other}"),
+                });
+            }
+        })
+    }
+
+    /// Decompress and unpack `data.tar.*` directly onto disk under `dest`.
+    fn unpack_data_member(
+        member_name: &str,
+        entry: impl std::io::Read,
+        package: &Path,
+        dest: &TempDir,
+    ) -> PackageResult<()> {
+        let reader = Self::decompress(member_name, entry, package)?;
+        let mut tar = tar::Archive::new(reader);
+        tar.unpack(dest.path())
+            .map_err(|e| PackageError::ExtractionFailed {
+                path: package.to_path_buf(),
+                reason: format!("Failed to unpack data.tar: {e}"),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IpkExtractor;
+    use crate::package::{ExtractionFilter, Package, PackageFile};
+    use std::path::PathBuf;
+
+    fn get_examples_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("examples")
+    }
+
+    #[test]
+    fn test_ipk_package_extract() {
+        let ipk_path = get_examples_dir().join("test.ipk");
+        if !ipk_path.exists() {
+            eprintln!(
+                "Skipping test: IPK test file not found at {}. Run 'examples/generate-examples.sh' to generate it.",
+                ipk_path.display()
+            );
+            return;
+        }
+
+        let package = Package::new(ipk_path).expect("Should extract IPK package");
+        let files = package.files();
+        assert!(
+            !files.is_empty(),
+            "Package should contain files after extraction"
+        );
+
+        let elf_count = files
+            .values()
+            .filter(|f| matches!(f, PackageFile::Elf(_)))
+            .count();
+        assert!(
+            elf_count > 0,
+            "Package should contain at least one ELF file"
+        );
+    }
+
+    #[test]
+    fn test_ipk_extension_constant() {
+        assert_eq!(IpkExtractor::EXTENSION, "ipk");
+    }
+}
@@ -0,0 +1,325 @@
+// Copyright (C) 2026 Checkmk GmbH - License: GNU General Public License v2
+// This file is part of Checkmk (https://checkmk.com). It is subject to the terms and
+// conditions defined in the file COPYING, which is part of this source code package.
+
+//! Parses Mach-O files to extract dylib dependencies and `LC_RPATH` entries. Uses the `goblin`
+//! crate for Mach-O parsing, mirroring `package::elf`'s approach for ELF.
+//!
+//! Reached only through `super::binary::Binary`, which `PackageFile::new` dispatches to for
+//! non-ELF package members (see that module's doc comment).
+
+use goblin::mach::load_command::CommandVariant;
+use goblin::mach::{Mach, MachO as GoblinMachO};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+type Result<T> = std::result::Result<T, MachOError>;
+
+/// Errors that can occur when parsing Mach-O files.
+#[derive(Debug, Error)]
+pub(crate) enum MachOError {
+    #[error("File is not a Mach-O file: {path:?}")]
+    NotMachOFile { path: PathBuf },
+    #[error("Failed to read file: {path:?}")]
+    ReadFailed {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Failed to parse Mach-O file: {path:?}")]
+    ParseFailed {
+        path: PathBuf,
+        #[source]
+        source: goblin::error::Error,
+    },
+    #[error("Fat (universal) Mach-O binaries are not yet supported: {path:?}")]
+    FatBinaryUnsupported { path: PathBuf },
+}
+
+/// Mach-O file type (wrapper around `goblin::mach::header::filetype`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum MachOType {
+    Executable,
+    Dylib,
+    Bundle,
+    Other,
+}
+
+/// Parsed Mach-O file information.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MachO {
+    kind: MachOType,
+    /// Dependent dylib paths (from `LC_LOAD_DYLIB`/`LC_LOAD_WEAK_DYLIB`), e.g.
+    /// `@rpath/libFoo.dylib` or `/usr/lib/libSystem.B.dylib`.
+    dependencies: Vec<String>,
+    /// `LC_RPATH` entries, before any `@loader_path`/`@executable_path` substitution.
+    rpath: Vec<String>,
+}
+
+impl MachO {
+    /// Parse a Mach-O file from a path.
+    ///
+    /// # Errors
+    /// Returns an error if the file can't be read, or if it isn't a (non-fat) Mach-O file.
+    pub(crate) fn from_path(path: &Path) -> Result<Self> {
+        let bytes = fs::read(path).map_err(|e| MachOError::ReadFailed {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        Self::from_bytes(path, &bytes)
+    }
+
+    /// Parse a Mach-O file from an in-memory buffer. `path` is used only to label errors.
+    ///
+    /// # Errors
+    /// Returns an error if the bytes cannot be parsed as a (non-fat) Mach-O file.
+    pub(crate) fn from_bytes(path: &Path, bytes: &[u8]) -> Result<Self> {
+        match Mach::parse(bytes).map_err(|e| MachOError::ParseFailed {
+            path: path.to_path_buf(),
+            source: e,
+        })? {
+            Mach::Binary(macho) => Ok(Self::from_goblin(&macho)),
+            Mach::Fat(_) => Err(MachOError::FatBinaryUnsupported {
+                path: path.to_path_buf(),
+            }),
+        }
+    }
+
+    fn from_goblin(macho: &GoblinMachO<'_>) -> Self {
+        let kind = Self::kind_from_filetype(macho.header.filetype);
+        // `libs` already excludes the object's own install name (`LC_ID_DYLIB`), unlike raw
+        // `LC_LOAD_DYLIB` enumeration; only goblin's leading "self" placeholder is dropped here.
+        let dependencies = macho
+            .libs
+            .iter()
+            .filter(|lib| **lib != "self")
+            .map(|lib| (*lib).to_string())
+            .collect();
+
+        let mut rpath = Vec::new();
+        for load_command in &macho.load_commands {
+            if let CommandVariant::Rpath(rpath_command) = &load_command.command {
+                if let Ok(path) = rpath_command.path.to_string(&load_command.data) {
+                    rpath.push(path.to_string());
+                }
+            }
+        }
+
+        Self {
+            kind,
+            dependencies,
+            rpath,
+        }
+    }
+
+    fn kind_from_filetype(filetype: u32) -> MachOType {
+        match filetype {
+            goblin::mach::header::MH_EXECUTE => MachOType::Executable,
+            goblin::mach::header::MH_DYLIB => MachOType::Dylib,
+            goblin::mach::header::MH_BUNDLE => MachOType::Bundle,
+            _ => MachOType::Other,
+        }
+    }
+
+    /// Get the Mach-O file type.
+    #[must_use]
+    pub(crate) fn kind(&self) -> &MachOType {
+        &self.kind
+    }
+
+    /// Get the list of dependent dylib paths.
+    #[must_use]
+    pub(crate) fn dependencies(&self) -> &[String] {
+        &self.dependencies
+    }
+
+    /// Get the raw `LC_RPATH` entries, before `@loader_path`/`@executable_path` substitution.
+    #[must_use]
+    pub(crate) fn rpath(&self) -> &[String] {
+        &self.rpath
+    }
+
+    /// Normalize this Mach-O's search paths into absolute filesystem paths, substituting the
+    /// dyld token family analogous to ELF's `$ORIGIN`:
+    ///
+    /// - `@loader_path` is the directory containing *this* Mach-O file.
+    /// - `@executable_path` is the directory containing the main executable of the process. This
+    ///   isn't knowable from a single file in isolation, so as a best-effort approximation (this
+    ///   tool analyzes one object at a time, not a running process) it is treated the same as
+    ///   `@loader_path`.
+    /// - `@rpath` itself isn't a literal substitution: dyld resolves it by trying each of the
+    ///   object's own `LC_RPATH` entries in order (themselves first substituted the same way)
+    ///   until one contains the requested library. This returns only those substituted
+    ///   `LC_RPATH` directories; dependencies like `@rpath/libFoo.dylib` are not expanded here,
+    ///   and an `LC_RPATH` entry that itself begins with `@rpath` yields no path.
+    #[must_use]
+    pub(crate) fn normalize_paths(&self, origin: &Path) -> Vec<PathBuf> {
+        self.rpath
+            .iter()
+            .filter_map(|path| Self::substitute_tokens(origin, path))
+            .collect()
+    }
+
+    fn substitute_tokens(origin: &Path, path: &str) -> Option<PathBuf> {
+        let origin_str = origin.to_string_lossy();
+        let resolved = path
+            .replace("@loader_path", &origin_str)
+            .replace("@executable_path", &origin_str);
+
+        if resolved.starts_with('/') {
+            return Some(PathBuf::from(resolved));
+        }
+        // A relative RPATH entry with no anchoring token is resolved by dyld relative to the
+        // process's current working directory, which is unknown at analysis time.
+        None
+    }
+
+    /// The dyld token family substituted before a relative `LC_RPATH` entry is resolved against
+    /// anything else: `@loader_path` (this Mach-O's own directory), `@executable_path` (the main
+    /// executable's directory), and `@rpath` (resolved by trying each of the object's own
+    /// `LC_RPATH` entries in turn). Mirrors `Elf::RTLD_TOKENS`.
+    const DYLD_TOKENS: &'static [&'static str] = &["@loader_path", "@executable_path", "@rpath"];
+
+    /// Validate this Mach-O's `LC_RPATH` entries, mirroring `Elf::validate`: an entry is valid if
+    /// it's absolute or begins with one of the dyld token family, and invalid if any literal
+    /// directory component precedes such a token (it would be resolved relative to dyld's CWD
+    /// first, which is unknown at analysis time).
+    ///
+    /// Returns a list of human-readable messages describing each invalid entry, empty if all are
+    /// valid.
+    #[must_use]
+    pub(crate) fn validate(&self) -> Vec<String> {
+        self.rpath
+            .iter()
+            .filter(|path| Self::invalid_path(path))
+            .map(|path| format!("LC_RPATH: {path} is invalid"))
+            .collect()
+    }
+
+    /// Check if an `LC_RPATH` entry is invalid. See `validate`.
+    fn invalid_path(path: &str) -> bool {
+        if path.starts_with('/') {
+            return false;
+        }
+
+        let token_pos = Self::DYLD_TOKENS
+            .iter()
+            .filter_map(|token| path.find(token))
+            .min();
+
+        match token_pos {
+            // A token at the very start is valid; anything before it would be resolved
+            // relative to CWD first.
+            Some(0) => false,
+            Some(_) | None => true,
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn new_for_testing(rpath: Vec<String>, dependencies: Vec<String>) -> Self {
+        Self {
+            kind: MachOType::Executable,
+            dependencies,
+            rpath,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_paths_substitutes_loader_path() {
+        let macho = MachO::new_for_testing(vec!["@loader_path/../lib".to_string()], Vec::new());
+        let origin = Path::new("/Applications/App.app/Contents/MacOS");
+
+        let normalized = macho.normalize_paths(origin);
+        assert_eq!(
+            normalized,
+            vec![PathBuf::from(
+                "/Applications/App.app/Contents/MacOS/../lib"
+            )]
+        );
+    }
+
+    #[test]
+    fn test_normalize_paths_substitutes_executable_path_as_loader_path() {
+        let macho = MachO::new_for_testing(vec!["@executable_path/../lib".to_string()], Vec::new());
+        let origin = Path::new("/Applications/App.app/Contents/MacOS");
+
+        let normalized = macho.normalize_paths(origin);
+        assert_eq!(
+            normalized,
+            vec![PathBuf::from(
+                "/Applications/App.app/Contents/MacOS/../lib"
+            )]
+        );
+    }
+
+    #[test]
+    fn test_normalize_paths_keeps_absolute_rpath() {
+        let macho = MachO::new_for_testing(vec!["/usr/local/lib".to_string()], Vec::new());
+        let origin = Path::new("/usr/bin");
+
+        assert_eq!(
+            macho.normalize_paths(origin),
+            vec![PathBuf::from("/usr/local/lib")]
+        );
+    }
+
+    #[test]
+    fn test_normalize_paths_drops_unanchored_relative_rpath() {
+        let macho = MachO::new_for_testing(vec!["../lib".to_string()], Vec::new());
+        let origin = Path::new("/usr/bin");
+
+        assert!(macho.normalize_paths(origin).is_empty());
+    }
+
+    #[test]
+    fn test_validate_accepts_absolute_and_token_anchored_rpaths() {
+        let macho = MachO::new_for_testing(
+            vec![
+                "/usr/local/lib".to_string(),
+                "@loader_path/../lib".to_string(),
+                "@executable_path/../lib".to_string(),
+                "@rpath".to_string(),
+            ],
+            Vec::new(),
+        );
+        assert!(macho.validate().is_empty());
+    }
+
+    #[test]
+    fn test_validate_rejects_unanchored_relative_rpath() {
+        let macho = MachO::new_for_testing(vec!["../lib".to_string()], Vec::new());
+        let invalid = macho.validate();
+        assert_eq!(invalid.len(), 1);
+        assert!(invalid[0].contains("../lib"));
+    }
+
+    #[test]
+    fn test_validate_rejects_literal_prefix_before_token() {
+        let macho = MachO::new_for_testing(vec!["../@loader_path/lib".to_string()], Vec::new());
+        assert_eq!(macho.validate().len(), 1);
+    }
+
+    #[test]
+    fn test_kind_from_filetype() {
+        assert_eq!(
+            MachO::kind_from_filetype(goblin::mach::header::MH_EXECUTE),
+            MachOType::Executable
+        );
+        assert_eq!(
+            MachO::kind_from_filetype(goblin::mach::header::MH_DYLIB),
+            MachOType::Dylib
+        );
+        assert_eq!(
+            MachO::kind_from_filetype(goblin::mach::header::MH_BUNDLE),
+            MachOType::Bundle
+        );
+        assert_eq!(MachO::kind_from_filetype(0), MachOType::Other);
+    }
+}
@@ -4,34 +4,48 @@
 
 //! Manages package lifecycle including extraction directory. Provides API for accessing package files, ELF files, and symlinks.
 
+mod binary;
+mod control;
 mod deb;
 mod elf;
 mod extractor;
 mod files;
+mod filter;
+mod ipk;
+mod macho;
+mod pe;
 mod rpm;
 
 use std::{
     collections::HashMap,
+    fs,
+    io::Read,
     path::{Path, PathBuf},
 };
-use tempfile::TempDir;
+use tempfile::{Builder, TempDir};
 
+pub(crate) use control::ControlMetadata;
 use deb::DebExtractor;
 pub use elf::{Elf, ElfType};
 use extractor::PackageExtractor;
 use extractor::{PackageError, PackageResult};
-pub use files::PackageFile;
+pub use files::{PackageFile, SymlinkTarget};
+pub use filter::ExtractionFilter;
+use ipk::IpkExtractor;
 use rpm::RpmExtractor;
 
 /// Collection of files in a package, keyed by their path.
 pub type PackageFiles = HashMap<PathBuf, PackageFile>;
-pub(crate) type PackageSymlinks<'a> = HashMap<&'a Path, &'a Path>;
+pub(crate) type PackageSymlinks<'a> = HashMap<&'a Path, &'a SymlinkTarget>;
 pub(crate) type PackageElfs<'a> = HashMap<&'a Path, &'a Elf>;
 
 /// Package struct that manages package life-cycle including extraction directory.
 pub struct Package {
     path: PathBuf,
     files: PackageFiles,
+    // Parsed DEB control file (declared name, version, `Depends`/`Recommends`). `None` for RPM
+    // packages, or a DEB whose control file couldn't be found/parsed.
+    control_metadata: Option<ControlMetadata>,
 }
 
 impl Package {
@@ -40,8 +54,54 @@ impl Package {
     /// # Errors
     /// Returns an error if the package type cannot be determined or is unsupported.
     pub fn new(path: PathBuf) -> PackageResult<Self> {
-        let files = Self::extract(&path)?;
-        Ok(Self { path, files })
+        Self::new_with_filter(path, &ExtractionFilter::unfiltered())
+    }
+
+    /// Create a new package from a filepath, restricting collection to files matching
+    /// `filter`.
+    ///
+    /// # Errors
+    /// Returns an error if the package type cannot be determined or is unsupported.
+    pub fn new_with_filter(path: PathBuf, filter: &ExtractionFilter) -> PackageResult<Self> {
+        let files = Self::extract(&path, filter)?;
+        let control_metadata = Self::read_control_metadata(&path)?;
+        Ok(Self {
+            path,
+            files,
+            control_metadata,
+        })
+    }
+
+    /// Create a new package from a filepath, persisting the on-disk extraction tree to
+    /// `persist_to` instead of discarding it once analysis is done.
+    ///
+    /// This lets a caller key `persist_to` on a content hash of `path` and skip re-extraction
+    /// the next time the same artifact is analyzed. The package is extracted into a scratch
+    /// directory that is a sibling of `persist_to` (so they're on the same filesystem), and only
+    /// `rename`d into place once extraction has fully succeeded, so observers never see a
+    /// partially populated directory. If `persist_to`'s parent doesn't exist yet, it is created
+    /// before the scratch directory is made in it.
+    ///
+    /// Extractors that run entirely in-process (see `PackageExtractor::IN_PROCESS`) never
+    /// populate the scratch directory on their normal (`Package::new`) path, so this uses each
+    /// such extractor's persisting variant instead (e.g. `DebExtractor::extract_persisting`) to
+    /// materialize the tree on disk here too.
+    ///
+    /// # Errors
+    /// Returns an error if the package type cannot be determined, extraction fails, or
+    /// persisting the extracted tree fails.
+    pub fn new_with_persistence(
+        path: PathBuf,
+        filter: &ExtractionFilter,
+        persist_to: &Path,
+    ) -> PackageResult<Self> {
+        let files = Self::extract_persisted(&path, filter, persist_to)?;
+        let control_metadata = Self::read_control_metadata(&path)?;
+        Ok(Self {
+            path,
+            files,
+            control_metadata,
+        })
     }
 
     /// Get the path to the package.
@@ -56,6 +116,13 @@ impl Package {
         &self.files
     }
 
+    /// Get the package's parsed DEB control metadata (declared name, version, and
+    /// `Depends`/`Recommends` fields), if this is a DEB package with a parseable control file.
+    #[must_use]
+    pub(crate) fn control_metadata(&self) -> Option<&ControlMetadata> {
+        self.control_metadata.as_ref()
+    }
+
     /// Get subset of ELF files.
     #[must_use]
     pub(crate) fn elfs(&self) -> PackageElfs<'_> {
@@ -74,25 +141,20 @@ impl Package {
         self.files
             .iter()
             .filter_map(|(path, file)| match file {
-                PackageFile::Symlink(symlink) => Some((path.as_path(), symlink.as_path())),
+                PackageFile::Symlink(symlink) => Some((path.as_path(), symlink)),
                 _ => None,
             })
             .collect()
     }
 
-    fn extract(path: &Path) -> PackageResult<PackageFiles> {
+    fn extract(path: &Path, filter: &ExtractionFilter) -> PackageResult<PackageFiles> {
         let dest = TempDir::new().map_err(|e| PackageError::TempDirFailed { source: e })?;
 
-        let extension = path
-            .extension()
-            .and_then(|ext| ext.to_str())
-            .ok_or_else(|| PackageError::UnsupportedPackageType {
-                extension: "unknown".to_string(),
-            })?;
-
+        let extension = Self::detect_extension(path)?;
         let result = match extension {
-            DebExtractor::EXTENSION => DebExtractor::extract(path, &dest),
-            RpmExtractor::EXTENSION => RpmExtractor::extract(path, &dest),
+            DebExtractor::EXTENSION => DebExtractor::extract(path, &dest, filter),
+            RpmExtractor::EXTENSION => RpmExtractor::extract(path, &dest, filter),
+            IpkExtractor::EXTENSION => IpkExtractor::extract(path, &dest, filter),
             _ => {
                 return Err(PackageError::UnsupportedPackageType {
                     extension: extension.to_string(),
@@ -105,10 +167,218 @@ impl Package {
         result
     }
 
+    /// Like `extract`, but extracts into a scratch directory alongside `persist_to` and renames
+    /// it into place on success instead of discarding it. See `new_with_persistence`.
+    fn extract_persisted(
+        path: &Path,
+        filter: &ExtractionFilter,
+        persist_to: &Path,
+    ) -> PackageResult<PackageFiles> {
+        let parent = persist_to.parent().unwrap_or_else(|| Path::new("."));
+        let make_scratch = || {
+            Builder::new()
+                .prefix(".package-validator-extract-")
+                .tempdir_in(parent)
+        };
+        // The scratch dir lives in `parent`, so a missing parent is created before retrying.
+        let scratch = match make_scratch() {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                fs::create_dir_all(parent)
+                    .map_err(|e| PackageError::TempDirFailed { source: e })?;
+                make_scratch()
+            }
+            other => other,
+        }
+        .map_err(|e| PackageError::TempDirFailed { source: e })?;
+
+        let extension = Self::detect_extension(path)?;
+        let files = match extension {
+            // DebExtractor::extract is IN_PROCESS (see PackageExtractor::IN_PROCESS) and never
+            // touches `scratch`, so persisting it would silently rename an empty directory into
+            // place; extract_persisting is the disk-writing variant built for exactly this.
+            DebExtractor::EXTENSION => DebExtractor::extract_persisting(path, &scratch, filter),
+            RpmExtractor::EXTENSION => RpmExtractor::extract(path, &scratch, filter),
+            IpkExtractor::EXTENSION => IpkExtractor::extract(path, &scratch, filter),
+            _ => {
+                return Err(PackageError::UnsupportedPackageType {
+                    extension: extension.to_string(),
+                })
+            }
+        }?;
+
+        Self::persist(scratch, persist_to, parent)?;
+        Ok(files)
+    }
+
+    /// Rename the scratch extraction directory into its final location. If `parent` doesn't
+    /// exist yet, create it and retry once.
+    fn persist(scratch: TempDir, persist_to: &Path, parent: &Path) -> PackageResult<()> {
+        match fs::rename(scratch.path(), persist_to) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                fs::create_dir_all(parent).map_err(|e| PackageError::TempDirFailed { source: e })?;
+                fs::rename(scratch.path(), persist_to)
+                    .map_err(|e| PackageError::TempDirFailed { source: e })
+            }
+            Err(e) => Err(PackageError::TempDirFailed { source: e }),
+        }
+    }
+
+    /// Parse the package's DEB control metadata, if it is a DEB package. `None` for any other
+    /// package type, since only DEB control files are understood today.
+    fn read_control_metadata(path: &Path) -> PackageResult<Option<ControlMetadata>> {
+        if Self::detect_extension(path)? == DebExtractor::EXTENSION {
+            DebExtractor::read_control_metadata(path)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Determine the package type from its file extension.
+    fn extension(path: &Path) -> PackageResult<&str> {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .ok_or_else(|| PackageError::UnsupportedPackageType {
+                extension: "unknown".to_string(),
+            })
+    }
+
+    /// `ar` archive magic, followed by the `debian-binary` member that's always first in a
+    /// `.deb` (its ar header's 16-byte name field, left-justified and space-padded).
+    const AR_MAGIC: &'static [u8] = b"!<arch>\n";
+    const DEB_FIRST_MEMBER: &'static [u8] = b"debian-binary";
+
+    /// RPM lead magic (`RPMLEAD_MAGIC` in the RPM file format spec).
+    const RPM_MAGIC: [u8; 4] = [0xED, 0xAB, 0xEE, 0xDB];
+
+    /// Determine the package type by sniffing its magic bytes, falling back to the file
+    /// extension when the content doesn't match a known format. This makes extraction robust
+    /// to mislabeled or extension-less inputs (e.g. a `.deb` renamed to `.pkg`, or an artifact
+    /// streamed out of a CI pipeline without a useful name).
+    fn detect_extension(path: &Path) -> PackageResult<&str> {
+        let mut magic = [0u8; Self::AR_MAGIC.len() + Self::DEB_FIRST_MEMBER.len()];
+        let bytes_read = Self::read_prefix(path, &mut magic)?;
+        let magic = &magic[..bytes_read];
+
+        if magic.starts_with(&Self::RPM_MAGIC) {
+            return Ok(RpmExtractor::EXTENSION);
+        }
+        if magic.starts_with(Self::AR_MAGIC)
+            && magic[Self::AR_MAGIC.len()..].starts_with(Self::DEB_FIRST_MEMBER)
+        {
+            return Ok(DebExtractor::EXTENSION);
+        }
+
+        Self::extension(path)
+    }
+
+    /// Read up to `buf.len()` bytes from the start of `path`, returning how many were read.
+    /// Short files (smaller than `buf`) are not an error here, since magic-matching against a
+    /// truncated prefix simply fails and falls back to the extension.
+    fn read_prefix(path: &Path, buf: &mut [u8]) -> PackageResult<usize> {
+        let mut file = fs::File::open(path).map_err(|e| PackageError::ExtractionFailed {
+            path: path.to_path_buf(),
+            reason: format!("Failed to open package: {e}"),
+        })?;
+        let mut total = 0;
+        while total < buf.len() {
+            match file.read(&mut buf[total..]) {
+                Ok(0) => break,
+                Ok(n) => total += n,
+                Err(e) => {
+                    return Err(PackageError::ExtractionFailed {
+                        path: path.to_path_buf(),
+                        reason: format!("Failed to read package header: {e}"),
+                    })
+                }
+            }
+        }
+        Ok(total)
+    }
+
     #[cfg(test)]
     /// Create a test package with the given files.
     /// This is only available in test builds.
     pub(crate) fn new_for_testing(path: PathBuf, files: PackageFiles) -> Self {
-        Self { path, files }
+        Self::new_for_testing_with_control_metadata(path, files, None)
+    }
+
+    #[cfg(test)]
+    /// Create a test package with the given files and DEB control metadata.
+    /// This is only available in test builds.
+    pub(crate) fn new_for_testing_with_control_metadata(
+        path: PathBuf,
+        files: PackageFiles,
+        control_metadata: Option<ControlMetadata>,
+    ) -> Self {
+        Self {
+            path,
+            files,
+            control_metadata,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Package;
+    use crate::fixtures::{build_package, ArtifactKind, ArtifactSpec, PackageFormat};
+    use crate::package::ExtractionFilter;
+    use std::io::Write;
+    use tempfile::Builder;
+
+    #[test]
+    fn test_new_with_persistence_materializes_deb_extraction_tree_on_disk() {
+        let deb_dir = tempfile::tempdir().unwrap();
+        let deb_path = deb_dir.path().join("fixture.deb");
+        let artifacts = [ArtifactSpec::new(ArtifactKind::Executable, "usr/bin/hello")];
+        build_package(PackageFormat::Deb, "fixture", "1.0.0", &artifacts, &deb_path).unwrap();
+
+        let persist_root = tempfile::tempdir().unwrap();
+        let persist_to = persist_root.path().join("extracted");
+        let package = Package::new_with_persistence(deb_path, &ExtractionFilter::unfiltered(), &persist_to)
+            .expect("persisted extraction of a synthetic .deb should succeed");
+
+        assert!(!package.files().is_empty());
+        assert!(
+            persist_to.join("usr/bin/hello").is_file(),
+            "extracted file should actually be materialized on disk at the persistence path"
+        );
+    }
+
+    #[test]
+    fn test_detect_extension_deb_magic_ignores_extension() {
+        let mut file = Builder::new().suffix(".pkg").tempfile().unwrap();
+        file.write_all(b"!<arch>\ndebian-binary   ").unwrap();
+        file.flush().unwrap();
+
+        assert_eq!(Package::detect_extension(file.path()).unwrap(), "deb");
+    }
+
+    #[test]
+    fn test_detect_extension_rpm_magic_ignores_extension() {
+        let mut file = Builder::new().suffix(".pkg").tempfile().unwrap();
+        file.write_all(&[0xED, 0xAB, 0xEE, 0xDB]).unwrap();
+        file.flush().unwrap();
+
+        assert_eq!(Package::detect_extension(file.path()).unwrap(), "rpm");
+    }
+
+    #[test]
+    fn test_detect_extension_falls_back_to_extension_when_magic_unrecognized() {
+        let mut file = Builder::new().suffix(".rpm").tempfile().unwrap();
+        file.write_all(b"not a known package format").unwrap();
+        file.flush().unwrap();
+
+        assert_eq!(Package::detect_extension(file.path()).unwrap(), "rpm");
+    }
+
+    #[test]
+    fn test_detect_extension_unrecognized_content_and_extension_errors() {
+        let mut file = Builder::new().tempfile().unwrap();
+        file.write_all(b"not a known package format").unwrap();
+        file.flush().unwrap();
+
+        assert!(Package::detect_extension(file.path()).is_err());
     }
 }
@@ -0,0 +1,111 @@
+// Copyright (C) 2026 Checkmk GmbH - License: GNU General Public License v2
+// This file is part of Checkmk (https://checkmk.com). It is subject to the terms and
+// conditions defined in the file COPYING, which is part of this source code package.
+
+//! Parses PE (Windows) files to extract imported DLL dependencies. Uses the `goblin` crate for
+//! PE parsing, mirroring `package::elf`'s approach for ELF.
+//!
+//! Reached only through `super::binary::Binary`, which `PackageFile::new` dispatches to for
+//! non-ELF package members (see that module's doc comment).
+
+use goblin::pe::PE as GoblinPe;
+use serde::Serialize;
+use std::path::PathBuf;
+use thiserror::Error;
+
+type Result<T> = std::result::Result<T, PeError>;
+
+/// `IMAGE_FILE_DLL`, from the PE COFF header's `Characteristics` field (winnt.h).
+const IMAGE_FILE_DLL: u16 = 0x2000;
+
+/// Errors that can occur when parsing PE files.
+#[derive(Debug, Error)]
+pub(crate) enum PeError {
+    #[error("Failed to parse PE file: {path:?}")]
+    ParseFailed {
+        path: PathBuf,
+        #[source]
+        source: goblin::error::Error,
+    },
+}
+
+/// PE file type: whether the `IMAGE_FILE_DLL` characteristic is set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum PeType {
+    Executable,
+    Dll,
+}
+
+/// Parsed PE file information.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Pe {
+    kind: PeType,
+    /// Imported DLL names, from the import table.
+    dependencies: Vec<String>,
+}
+
+impl Pe {
+    /// Parse a PE file from an in-memory buffer. `path` is used only to label errors.
+    ///
+    /// # Errors
+    /// Returns an error if the bytes cannot be parsed as a PE file.
+    pub(crate) fn from_bytes(path: &std::path::Path, bytes: &[u8]) -> Result<Self> {
+        let pe = GoblinPe::parse(bytes).map_err(|e| PeError::ParseFailed {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+
+        let kind = if pe.header.coff_header.characteristics & IMAGE_FILE_DLL != 0 {
+            PeType::Dll
+        } else {
+            PeType::Executable
+        };
+        let dependencies = pe.libraries.iter().map(|lib| (*lib).to_string()).collect();
+
+        Ok(Self { kind, dependencies })
+    }
+
+    /// Get the PE file type.
+    #[must_use]
+    pub(crate) fn kind(&self) -> &PeType {
+        &self.kind
+    }
+
+    /// Get the list of imported DLL names.
+    #[must_use]
+    pub(crate) fn dependencies(&self) -> &[String] {
+        &self.dependencies
+    }
+
+    /// PE has no RPATH/RUNPATH equivalent embedded in the file: the Windows loader's DLL search
+    /// order is determined by well-known system/application directories and `SafeDllSearchMode`,
+    /// not by metadata shipped in the binary itself, so there is nothing to normalize here.
+    #[must_use]
+    pub(crate) fn normalize_paths(&self, _origin: &std::path::Path) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn new_for_testing(kind: PeType, dependencies: Vec<String>) -> Self {
+        Self { kind, dependencies }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_normalize_paths_is_always_empty() {
+        let pe = Pe::new_for_testing(PeType::Executable, vec!["KERNEL32.dll".to_string()]);
+        assert!(pe.normalize_paths(Path::new("/tmp")).is_empty());
+    }
+
+    #[test]
+    fn test_accessors() {
+        let pe = Pe::new_for_testing(PeType::Dll, vec!["KERNEL32.dll".to_string(), "USER32.dll".to_string()]);
+        assert_eq!(pe.kind(), &PeType::Dll);
+        assert_eq!(pe.dependencies(), &["KERNEL32.dll".to_string(), "USER32.dll".to_string()]);
+    }
+}
@@ -2,18 +2,37 @@
 // This file is part of Checkmk (https://checkmk.com). It is subject to the terms and
 // conditions defined in the file COPYING, which is part of this source code package.
 
-//! Implements RPM package extraction using `rpm2cpio` and `cpio`.
+//! Implements RPM package extraction. Prefers a native path that parses the RPM lead and header
+//! sections, decompresses the payload, and unpacks its `newc` CPIO archive entirely in-process,
+//! so extraction never depends on `rpm2cpio`/`cpio` being installed. Falls back to shelling out
+//! to that pipeline if the native path can't handle a particular package (e.g. an unrecognized
+//! payload compressor or a malformed header).
 
-use std::path::Path;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::time::Instant;
 use tempfile::TempDir;
 
 use super::extractor::{
     wait_with_timeout, PackageError, PackageExtractor, PackageResult, DEFAULT_EXTRACTION_TIMEOUT,
+    MAX_EXTRACTED_BYTES,
 };
+use super::files::PackageFile;
+use super::filter::ExtractionFilter;
 use super::PackageFiles;
 
+/// Size of the fixed RPM lead that precedes the signature and main headers.
+const LEAD_SIZE: usize = 96;
+/// The 4-byte magic (`0x8EADE801`) that starts both the signature and main header sections.
+const HEADER_MAGIC: [u8; 4] = [0x8E, 0xAD, 0xE8, 0x01];
+/// `RPMTAG_PAYLOADCOMPRESSOR`: the header tag whose string value names the payload's
+/// compression codec (`"gzip"`, `"xz"`, `"zstd"`, ...).
+const PAYLOAD_COMPRESSOR_TAG: u32 = 1125;
+const CPIO_NEWC_MAGIC: &[u8; 6] = b"070701";
+const CPIO_NEWC_CRC_MAGIC: &[u8; 6] = b"070702";
+const CPIO_TRAILER_NAME: &str = "TRAILER!!!";
+
 pub(crate) struct RpmExtractor;
 
 impl PackageExtractor for RpmExtractor {
@@ -21,6 +40,322 @@ impl PackageExtractor for RpmExtractor {
 
     /// Extract an RPM package into a temporary directory.
     ///
+    /// Prefers parsing the RPM natively (see `extract_native`); if that fails for any reason
+    /// (an unrecognized payload compressor, a header this parser doesn't understand, ...), falls
+    /// back to the `rpm2cpio | cpio` subprocess pipeline this extractor used exclusively before.
+    ///
+    /// # Errors
+    /// Returns an error if both the native path and the subprocess fallback fail to extract the
+    /// package.
+    fn extract(package: &Path, dest: &TempDir, filter: &ExtractionFilter) -> PackageResult<PackageFiles> {
+        match Self::extract_native(package, dest, filter) {
+            Ok(files) => Ok(files),
+            Err(native_error) => {
+                eprintln!(
+                    "Native RPM extraction failed, falling back to rpm2cpio/cpio: package={}, reason={native_error}",
+                    package.display()
+                );
+                Self::extract_via_subprocess(package, dest, filter)
+            }
+        }
+    }
+}
+
+impl RpmExtractor {
+    /// Extract an RPM package without shelling out: parse the lead and header sections to find
+    /// the payload's compressor, decompress the payload, and unpack its `newc` CPIO archive
+    /// directly into `dest`.
+    ///
+    /// # Errors
+    /// Returns an error if the file is too small or malformed to contain RPM lead/header
+    /// sections, the payload compressor isn't one of gzip/xz/lzma/zstd, or the CPIO payload is
+    /// truncated or uses an unsupported format.
+    fn extract_native(package: &Path, dest: &TempDir, filter: &ExtractionFilter) -> PackageResult<PackageFiles> {
+        let bytes = std::fs::read(package).map_err(|e| PackageError::ExtractionFailed {
+            path: package.to_path_buf(),
+            reason: format!("Failed to read package: {e}"),
+        })?;
+        if bytes.len() < LEAD_SIZE {
+            return Err(PackageError::ExtractionFailed {
+                path: package.to_path_buf(),
+                reason: "File is too small to contain an RPM lead".to_string(),
+            });
+        }
+
+        let signature_header = Self::parse_header(&bytes, LEAD_SIZE, package)?;
+        // The signature header's index + data store is padded with NULs to an 8-byte boundary
+        // before the main header begins.
+        let signature_len = signature_header.end - LEAD_SIZE;
+        let padding = (8 - signature_len % 8) % 8;
+        let main_header = Self::parse_header(&bytes, signature_header.end + padding, package)?;
+
+        // Absence of an explicit compressor tag means the historical RPM default: gzip.
+        let compressor =
+            Self::header_string(&main_header, PAYLOAD_COMPRESSOR_TAG).unwrap_or_else(|| "gzip".to_string());
+        let payload = &bytes[main_header.end..];
+        let reader = Self::decompress_payload(&compressor, payload, package)?;
+        Self::unpack_newc_cpio(reader, package, dest)?;
+
+        Self::process(dest, package, filter)
+    }
+
+    /// Parse an RPM header section (the signature header or the main header share the same
+    /// layout) starting at `start`: an 8-byte-aligned magic+reserved prefix, an index entry
+    /// count and data store size, the index entries themselves, then the data store they
+    /// reference into.
+    fn parse_header<'a>(bytes: &'a [u8], start: usize, package: &Path) -> PackageResult<RpmHeader<'a>> {
+        let read = |offset: usize, len: usize| -> PackageResult<&'a [u8]> {
+            bytes
+                .get(offset..offset + len)
+                .ok_or_else(|| PackageError::ExtractionFailed {
+                    path: package.to_path_buf(),
+                    reason: "RPM header section is truncated".to_string(),
+                })
+        };
+        let read_u32 = |offset: usize| -> PackageResult<u32> {
+            Ok(u32::from_be_bytes(read(offset, 4)?.try_into().unwrap()))
+        };
+
+        if read(start, 4)? != HEADER_MAGIC {
+            return Err(PackageError::ExtractionFailed {
+                path: package.to_path_buf(),
+                reason: format!("Invalid RPM header magic at offset {start}"),
+            });
+        }
+        let index_count = read_u32(start + 8)? as usize;
+        let data_size = read_u32(start + 12)? as usize;
+
+        let index_start = start + 16;
+        let data_start = index_start + index_count * 16;
+        let data = read(data_start, data_size)?;
+
+        let entries = (0..index_count)
+            .map(|i| {
+                let entry = read(index_start + i * 16, 16)?;
+                Ok(RpmHeaderEntry {
+                    tag: u32::from_be_bytes(entry[0..4].try_into().unwrap()),
+                    offset: u32::from_be_bytes(entry[8..12].try_into().unwrap()) as usize,
+                })
+            })
+            .collect::<PackageResult<Vec<_>>>()?;
+
+        Ok(RpmHeader {
+            entries,
+            data,
+            end: data_start + data_size,
+        })
+    }
+
+    /// Read a NUL-terminated string value out of `header`'s data store for the entry tagged
+    /// `tag`, if present.
+    fn header_string(header: &RpmHeader<'_>, tag: u32) -> Option<String> {
+        let entry = header.entries.iter().find(|entry| entry.tag == tag)?;
+        let bytes = header.data.get(entry.offset..)?;
+        let end = bytes.iter().position(|&b| b == 0)?;
+        Some(String::from_utf8_lossy(&bytes[..end]).into_owned())
+    }
+
+    /// Pick a decompressor for the RPM payload by its `PAYLOADCOMPRESSOR` tag value.
+    fn decompress_payload<'a>(
+        compressor: &str,
+        payload: &'a [u8],
+        package: &Path,
+    ) -> PackageResult<Box<dyn Read + 'a>> {
+        Ok(match compressor {
+            "gzip" => Box::new(flate2::read::GzDecoder::new(payload)),
+            "xz" => Box::new(xz2::read::XzDecoder::new(payload)),
+            // Legacy `.lzma` (LZMA_Alone) streams aren't `.xz` containers and need their own decoder.
+            "lzma" => {
+                let stream = xz2::stream::Stream::new_lzma_decoder(u64::MAX).map_err(|e| {
+                    PackageError::ExtractionFailed {
+                        path: package.to_path_buf(),
+                        reason: format!("Failed to initialize lzma decoder: {e}"),
+                    }
+                })?;
+                Box::new(xz2::read::XzDecoder::new_stream(payload, stream))
+            }
+            "zstd" => {
+                Box::new(zstd::stream::read::Decoder::new(payload).map_err(|e| {
+                    PackageError::ExtractionFailed {
+                        path: package.to_path_buf(),
+                        reason: format!("Failed to initialize zstd decoder: {e}"),
+                    }
+                })?)
+            }
+            other => {
+                return Err(PackageError::ExtractionFailed {
+                    path: package.to_path_buf(),
+                    reason: format!("Unsupported RPM payload compressor: {other}"),
+                });
+            }
+        })
+    }
+
+    /// Unpack a `newc`-format CPIO stream (the only format RPM payloads use) directly onto disk
+    /// under `dest`, entry by entry, until the `TRAILER!!!` end marker.
+    fn unpack_newc_cpio(mut reader: impl Read, package: &Path, dest: &TempDir) -> PackageResult<()> {
+        const S_IFMT: u32 = 0o170_000;
+        const S_IFDIR: u32 = 0o040_000;
+        const S_IFLNK: u32 = 0o120_000;
+
+        let mut bytes_read: u64 = 0;
+        loop {
+            let entry = Self::read_cpio_header(&mut reader, package)?;
+            if entry.name == CPIO_TRAILER_NAME {
+                return Ok(());
+            }
+
+            // Anchor absolute entry names under `dest`; `Path::join` would otherwise replace it.
+            let relative_path = Path::new(entry.name.trim_start_matches('/'));
+            if PackageFile::path_escapes_root(relative_path) {
+                return Err(PackageError::ExtractionFailed {
+                    path: package.to_path_buf(),
+                    reason: format!("CPIO entry escapes package root: {}", entry.name),
+                });
+            }
+            let out_path = dest.path().join(relative_path);
+
+            match entry.mode & S_IFMT {
+                S_IFDIR => {
+                    std::fs::create_dir_all(&out_path).map_err(|e| PackageError::ExtractionFailed {
+                        path: package.to_path_buf(),
+                        reason: format!("Failed to create directory {}: {e}", out_path.display()),
+                    })?;
+                }
+                S_IFLNK => {
+                    let target = Self::read_exact_padded(&mut reader, entry.file_size, package)?;
+                    Self::create_parent_dirs(&out_path, package)?;
+                    let target = PathBuf::from(String::from_utf8_lossy(&target).into_owned());
+                    std::os::unix::fs::symlink(&target, &out_path).map_err(|e| {
+                        PackageError::ExtractionFailed {
+                            path: package.to_path_buf(),
+                            reason: format!("Failed to create symlink {}: {e}", out_path.display()),
+                        }
+                    })?;
+                }
+                _ => {
+                    bytes_read += entry.file_size;
+                    if bytes_read > MAX_EXTRACTED_BYTES {
+                        return Err(PackageError::ExtractedSizeLimitExceeded {
+                            path: package.to_path_buf(),
+                            limit: MAX_EXTRACTED_BYTES,
+                        });
+                    }
+                    let contents = Self::read_exact_padded(&mut reader, entry.file_size, package)?;
+                    Self::create_parent_dirs(&out_path, package)?;
+                    std::fs::write(&out_path, &contents).map_err(|e| PackageError::ExtractionFailed {
+                        path: package.to_path_buf(),
+                        reason: format!("Failed to write {}: {e}", out_path.display()),
+                    })?;
+                }
+            }
+        }
+    }
+
+    /// Read one `newc` CPIO entry header (6-byte magic, 13 8-hex-digit fields, then the
+    /// NUL-terminated name, with the whole header padded to a 4-byte boundary).
+    fn read_cpio_header(reader: &mut impl Read, package: &Path) -> PackageResult<CpioEntry> {
+        let mut magic = [0u8; 6];
+        reader
+            .read_exact(&mut magic)
+            .map_err(|e| PackageError::ExtractionFailed {
+                path: package.to_path_buf(),
+                reason: format!("Failed to read CPIO entry magic: {e}"),
+            })?;
+        if &magic != CPIO_NEWC_MAGIC && &magic != CPIO_NEWC_CRC_MAGIC {
+            return Err(PackageError::ExtractionFailed {
+                path: package.to_path_buf(),
+                reason: "Unsupported or corrupt CPIO payload: expected a newc magic".to_string(),
+            });
+        }
+
+        // 13 fields (ino, mode, uid, gid, nlink, mtime, filesize, devmajor, devminor, rdevmajor,
+        // rdevminor, namesize, check), each an 8-character hex ASCII field.
+        let mut fields = [0u8; 13 * 8];
+        reader
+            .read_exact(&mut fields)
+            .map_err(|e| PackageError::ExtractionFailed {
+                path: package.to_path_buf(),
+                reason: format!("Failed to read CPIO entry header: {e}"),
+            })?;
+        let field = |index: usize| -> PackageResult<u32> {
+            let text = std::str::from_utf8(&fields[index * 8..index * 8 + 8]).map_err(|_| {
+                PackageError::ExtractionFailed {
+                    path: package.to_path_buf(),
+                    reason: "CPIO header field is not valid UTF-8".to_string(),
+                }
+            })?;
+            u32::from_str_radix(text, 16).map_err(|_| PackageError::ExtractionFailed {
+                path: package.to_path_buf(),
+                reason: format!("Invalid hex CPIO header field: {text:?}"),
+            })
+        };
+        let mode = field(1)?;
+        let file_size = u64::from(field(6)?);
+        let name_size = field(11)? as usize;
+
+        let mut name_bytes = vec![0u8; name_size];
+        reader
+            .read_exact(&mut name_bytes)
+            .map_err(|e| PackageError::ExtractionFailed {
+                path: package.to_path_buf(),
+                reason: format!("Failed to read CPIO entry name: {e}"),
+            })?;
+        let name = String::from_utf8_lossy(name_bytes.strip_suffix(b"\0").unwrap_or(&name_bytes)).into_owned();
+
+        let header_len = magic.len() + fields.len() + name_size;
+        Self::skip_padding(reader, header_len, package)?;
+
+        Ok(CpioEntry {
+            mode,
+            file_size,
+            name,
+        })
+    }
+
+    /// Read `len` bytes of entry data, then skip the trailing padding to the next 4-byte
+    /// boundary, matching `newc`'s alignment for both header and data sections.
+    fn read_exact_padded(reader: &mut impl Read, len: u64, package: &Path) -> PackageResult<Vec<u8>> {
+        let mut buf = vec![0u8; usize::try_from(len).unwrap_or(usize::MAX)];
+        reader
+            .read_exact(&mut buf)
+            .map_err(|e| PackageError::ExtractionFailed {
+                path: package.to_path_buf(),
+                reason: format!("Failed to read CPIO entry data: {e}"),
+            })?;
+        Self::skip_padding(reader, buf.len(), package)?;
+        Ok(buf)
+    }
+
+    /// Consume the zero-padding bytes after a `newc` header or data section, bringing the
+    /// stream back onto a 4-byte boundary.
+    fn skip_padding(reader: &mut impl Read, len: usize, package: &Path) -> PackageResult<()> {
+        let pad = (4 - len % 4) % 4;
+        if pad == 0 {
+            return Ok(());
+        }
+        let mut buf = [0u8; 4];
+        reader
+            .read_exact(&mut buf[..pad])
+            .map_err(|e| PackageError::ExtractionFailed {
+                path: package.to_path_buf(),
+                reason: format!("Failed to skip CPIO padding: {e}"),
+            })
+    }
+
+    fn create_parent_dirs(path: &Path, package: &Path) -> PackageResult<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| PackageError::ExtractionFailed {
+                path: package.to_path_buf(),
+                reason: format!("Failed to create directory {}: {e}", parent.display()),
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Extract an RPM package into a temporary directory via the `rpm2cpio | cpio` subprocess
+    /// pipeline, used as a fallback when `extract_native` can't handle a package.
+    ///
     /// # Errors
     /// Returns an error if the package cannot be extracted.
     ///
@@ -28,12 +363,13 @@ impl PackageExtractor for RpmExtractor {
     /// This function enforces a total timeout of 30 seconds for the `rpm2cpio` and
     /// `cpio` pipeline. If extraction takes longer, the processes will be killed and
     /// a `CommandTimeout` error will be returned.
-    fn extract(package: &Path, dest: &TempDir) -> PackageResult<PackageFiles> {
+    fn extract_via_subprocess(
+        package: &Path,
+        dest: &TempDir,
+        filter: &ExtractionFilter,
+    ) -> PackageResult<PackageFiles> {
         let start = Instant::now();
 
-        // Use system commands for maximum performance: rpm2cpio | cpio -id
-        // This is much faster than parsing CPIO in Rust, especially for large RPMs
-        // The system cpio command handles padding, alignment, and all edge cases efficiently
         let mut rpm2cpio_child = match std::process::Command::new("rpm2cpio")
             .arg(package)
             .stdout(Stdio::piped())
@@ -98,7 +434,7 @@ impl PackageExtractor for RpmExtractor {
         let _ = rpm2cpio_child.wait();
 
         if cpio_status.success() {
-            Self::process(dest, package)
+            Self::process(dest, package, filter)
         } else {
             Err(PackageError::ExtractionFailed {
                 path: package.to_path_buf(),
@@ -111,6 +447,27 @@ impl PackageExtractor for RpmExtractor {
     }
 }
 
+/// A parsed RPM header section (signature or main): its index entries, the data store they
+/// point into, and the offset immediately after the data store ends.
+struct RpmHeader<'a> {
+    entries: Vec<RpmHeaderEntry>,
+    data: &'a [u8],
+    end: usize,
+}
+
+struct RpmHeaderEntry {
+    tag: u32,
+    offset: usize,
+}
+
+/// One parsed `newc` CPIO entry header, with its name already decoded (the associated file
+/// data, if any, is read separately by the caller).
+struct CpioEntry {
+    mode: u32,
+    file_size: u64,
+    name: String,
+}
+
 #[cfg(test)]
 mod tests {
     use crate::package::{Package, PackageFile};
@@ -5,10 +5,13 @@
 //! Formats and prints report summaries to the console.
 
 use comfy_table::{Cell, Table};
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
+use super::dependency_resolver::DependencyResolverResult;
 use super::utils::find_common_prefix;
-use super::{DependencyStatus, Report};
+use super::{DependencyKind, DependencyStatus, Report};
+use crate::package::ElfType;
 
 /// Summarize the report to the console.
 ///
@@ -31,12 +34,86 @@ pub fn summarize_report(report: &Report<'_>) {
         let table = missing_dependencies_table(&missing_deps);
         println!("{table}");
         println!(
-            "\nTotal: {} ELF file(s) with missing dependencies",
+            "\nTotal: {} ELF file(s) with missing or version-unsatisfied dependencies",
             missing_deps.len()
         );
     }
 }
 
+/// Print a `cargo tree`-like rendering of the runtime dependency closure for every top-level
+/// binary in the package (an `ElfType::Executable`, as opposed to a shared library only pulled
+/// in by others): each `DT_NEEDED` library as an indented child, annotated with its resolution
+/// status and kind, recursing into package-provided libraries' own dependencies in turn.
+///
+/// A subtree is only ever expanded once: on a repeat visit (whether a sibling reusing the same
+/// library, or a true dependency cycle looping back to an ancestor) it's printed with a `(*)`
+/// marker instead of being walked again, which keeps the output finite either way.
+pub fn print_dependency_tree(report: &Report<'_>) {
+    let mut roots: Vec<&Path> = report
+        .files
+        .iter()
+        .filter(|(_, elf)| *elf.kind() == ElfType::Executable)
+        .map(|(path, _)| *path)
+        .collect();
+    roots.sort_unstable();
+
+    println!("Dependency tree:\n");
+    let mut expanded = HashSet::new();
+    for root in roots {
+        println!("{}", root.display());
+        print_dependency_subtree(report, root, "", &mut expanded);
+    }
+}
+
+/// Print `path`'s direct dependencies as a tree under `prefix`, recursing into any that resolved
+/// to another package-provided ELF.
+fn print_dependency_subtree(report: &Report<'_>, path: &Path, prefix: &str, expanded: &mut HashSet<PathBuf>) {
+    let Some(deps) = report.dependencies.get(path) else {
+        return;
+    };
+    let mut entries: Vec<(&str, &DependencyResolverResult)> =
+        deps.iter().map(|(name, result)| (*name, result)).collect();
+    entries.sort_unstable_by_key(|(name, _)| *name);
+
+    let last_index = entries.len().saturating_sub(1);
+    for (i, (name, result)) in entries.into_iter().enumerate() {
+        let is_last = i == last_index;
+        let branch = if is_last { "└── " } else { "├── " };
+        let child_prefix = format!("{prefix}{}", if is_last { "    " } else { "│   " });
+        let annotation = annotate(result);
+
+        match (&result.kind, &result.path) {
+            (DependencyKind::Package, Some(resolved)) if report.dependencies.contains_key(resolved.as_path()) => {
+                if expanded.insert(resolved.clone()) {
+                    println!("{prefix}{branch}{name} ({annotation})");
+                    print_dependency_subtree(report, resolved, &child_prefix, expanded);
+                } else {
+                    println!("{prefix}{branch}{name} ({annotation}) (*)");
+                }
+            }
+            _ => println!("{prefix}{branch}{name} ({annotation})"),
+        }
+    }
+}
+
+/// Render a dependency's status and kind as a short annotation, e.g. `"Found, Package"` or
+/// `"Missing"`.
+fn annotate(result: &DependencyResolverResult) -> String {
+    let status = match &result.status {
+        DependencyStatus::Found => "Found".to_string(),
+        DependencyStatus::Missing => "Missing".to_string(),
+        DependencyStatus::Error(reason) => format!("Error: {reason}"),
+        DependencyStatus::VersionUnsatisfied { missing_versions } => {
+            format!("Version unsatisfied: {}", missing_versions.join(", "))
+        }
+    };
+    match result.kind {
+        DependencyKind::Unknown => status,
+        DependencyKind::System => format!("{status}, System"),
+        DependencyKind::Package => format!("{status}, Package"),
+    }
+}
+
 /// Create a table with the default preset styling.
 fn default_table_preset() -> Table {
     let mut table = Table::new();
@@ -123,6 +200,10 @@ fn dependency_status_table(report: &Report) -> Table {
             Cell::new("Error"),
             Cell::new(report.totals.dependencies.error),
         ])
+        .add_row(vec![
+            Cell::new("Version unsatisfied"),
+            Cell::new(report.totals.dependencies.version_unsatisfied),
+        ])
         .add_row(vec![
             Cell::new("Total").add_attribute(comfy_table::Attribute::Bold),
             Cell::new(report.totals.dependencies.total).add_attribute(comfy_table::Attribute::Bold),
@@ -130,27 +211,122 @@ fn dependency_status_table(report: &Report) -> Table {
     table
 }
 
-/// Collect ELF files with missing dependencies from the report.
-fn missing_dependencies<'a>(report: &Report<'a>) -> Vec<(&'a Path, Vec<&'a str>)> {
-    let mut result: Vec<(&'a Path, Vec<&'a str>)> = report
+/// Render the directories a missing dependency's resolution actually searched (mirroring the
+/// dynamic linker's own order of precedence), so packagers can see exactly what would fail at
+/// runtime instead of just the bare soname.
+fn format_searched_paths(searched_paths: &[PathBuf]) -> String {
+    if searched_paths.is_empty() {
+        return "none".to_string();
+    }
+    searched_paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Collect ELF files with dependencies that are missing outright, or present but not satisfying
+/// every symbol version required of them, from the report.
+fn missing_dependencies<'a>(report: &Report<'a>) -> Vec<(&'a Path, Vec<String>)> {
+    let mut result: Vec<(&'a Path, Vec<String>)> = report
         .dependencies
         .iter()
         .filter_map(|(elf_path, deps_map)| {
-            let missing: Vec<&str> = deps_map
+            let mut problems: Vec<(&str, String)> = deps_map
                 .iter()
-                .filter(|(_, result)| matches!(result.status, DependencyStatus::Missing))
-                .map(|(dep_name, _)| *dep_name)
+                .filter_map(|(dep_name, result)| match &result.status {
+                    DependencyStatus::Missing => Some((
+                        *dep_name,
+                        format!("{dep_name} (searched: {})", format_searched_paths(&result.searched_paths)),
+                    )),
+                    DependencyStatus::VersionUnsatisfied { missing_versions } => Some((
+                        *dep_name,
+                        format!("{dep_name} (missing version(s): {})", missing_versions.join(", ")),
+                    )),
+                    DependencyStatus::Found | DependencyStatus::Error(_) => None,
+                })
                 .collect();
+            problems.sort_unstable_by_key(|(name, _)| *name);
+            let problems: Vec<String> = problems.into_iter().map(|(_, display)| display).collect();
 
-            (!missing.is_empty()).then_some((*elf_path, missing))
+            (!problems.is_empty()).then_some((*elf_path, problems))
         })
         .collect();
     result.sort_by_key(|(path, _)| *path);
     result
 }
 
-/// Create a table showing missing dependencies for each ELF file.
-fn missing_dependencies_table(missing_dependencies: &[(&Path, Vec<&str>)]) -> Table {
+/// Print the inverse of the missing-dependencies table: one row per missing soname, listing
+/// every ELF file that requires it, sorted by impact (most dependents first) so the single
+/// library breaking the most binaries surfaces first instead of being buried in a per-file
+/// scroll.
+pub fn print_dependency_requesters(report: &Report<'_>) {
+    let requesters = dependency_requesters(report);
+    if requesters.is_empty() {
+        return;
+    }
+    println!("{}", dependency_requesters_table(&requesters));
+}
+
+/// Group missing dependencies by soname, collecting every ELF file that requires each one, most
+/// requested first.
+fn dependency_requesters<'a>(report: &Report<'a>) -> Vec<(&'a str, Vec<&'a Path>)> {
+    let mut by_dependency: HashMap<&'a str, Vec<&'a Path>> = HashMap::new();
+    for (elf_path, deps_map) in &report.dependencies {
+        for (dependency, result) in deps_map {
+            if matches!(result.status, DependencyStatus::Missing) {
+                by_dependency.entry(*dependency).or_default().push(*elf_path);
+            }
+        }
+    }
+
+    let mut result: Vec<(&'a str, Vec<&'a Path>)> = by_dependency.into_iter().collect();
+    for (_, elfs) in &mut result {
+        elfs.sort_unstable();
+    }
+    result.sort_by(|(a_name, a_elfs), (b_name, b_elfs)| {
+        b_elfs.len().cmp(&a_elfs.len()).then_with(|| a_name.cmp(b_name))
+    });
+    result
+}
+
+/// Create a table showing, for each missing dependency, every ELF file that requires it.
+fn dependency_requesters_table(dependency_requesters: &[(&str, Vec<&Path>)]) -> Table {
+    // Find common prefix to strip for cleaner display
+    let paths: Vec<&Path> = dependency_requesters
+        .iter()
+        .flat_map(|(_, elfs)| elfs.iter().copied())
+        .collect();
+    let common_prefix = find_common_prefix(&paths);
+
+    let mut table = default_table_preset();
+    table.set_header(vec![
+        Cell::new("Missing Dependency").add_attribute(comfy_table::Attribute::Bold),
+        Cell::new("Required By").add_attribute(comfy_table::Attribute::Bold),
+    ]);
+
+    for (dependency, elfs) in dependency_requesters {
+        let display_paths: Vec<String> = elfs
+            .iter()
+            .map(|path| {
+                let display_path = if let Some(prefix) = &common_prefix {
+                    path.strip_prefix(prefix).unwrap_or(path)
+                } else {
+                    path
+                };
+                display_path.to_string_lossy().to_string()
+            })
+            .collect();
+        table.add_row(vec![
+            Cell::new(*dependency),
+            Cell::new(format!("({}) {}", elfs.len(), display_paths.join(", "))),
+        ]);
+    }
+    table
+}
+
+/// Create a table showing missing or version-unsatisfied dependencies for each ELF file.
+fn missing_dependencies_table(missing_dependencies: &[(&Path, Vec<String>)]) -> Table {
     // Find common prefix to strip for cleaner display
     let paths: Vec<&Path> = missing_dependencies.iter().map(|(p, _)| *p).collect();
     let common_prefix = find_common_prefix(&paths);
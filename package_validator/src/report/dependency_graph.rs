@@ -0,0 +1,463 @@
+// Copyright (C) 2026 Checkmk GmbH - License: GNU General Public License v2
+// This file is part of Checkmk (https://checkmk.com). It is subject to the terms and
+// conditions defined in the file COPYING, which is part of this source code package.
+
+//! Computes the transitive closure of shared-object dependencies reachable from a single root
+//! ELF: every library it pulls in at runtime, and everything those pull in in turn. Unlike
+//! `DependencyResolver` (which independently resolves every ELF's own *direct* `DT_NEEDED`
+//! entries, for flagging errors), this recurses from one root, which is what answers "what does
+//! this binary actually pull in at runtime".
+//!
+//! The recursion has to honor how `RPATH` and `RUNPATH` behave differently across a dependency
+//! chain: `DT_RUNPATH` applies only to the object that declares it, while `DT_RPATH` is inherited
+//! by every descendant, so the ancestor RPATH list is threaded down the walk separately from
+//! each object's own RUNPATH.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::package::{Elf, Package};
+use crate::report::search_config::SearchConfig;
+use crate::report::symlink_resolver::{SymlinkResolutionResult, SymlinkResolver};
+
+/// A `DT_NEEDED` name that couldn't be located anywhere in the search path used to resolve it.
+/// Recorded rather than treated as an error, since a binary can legitimately depend on a library
+/// the host provides rather than the package itself.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct UnresolvedDependency {
+    pub(crate) needed: String,
+    pub(crate) searched_paths: Vec<PathBuf>,
+}
+
+/// The transitive closure of shared-object dependencies reachable from a root ELF binary.
+///
+/// Nodes are every ELF reached during the walk, keyed by its cleaned absolute path in the
+/// package; edges are `(needed_by, needed)` pairs between them.
+#[derive(Debug, Default)]
+pub(crate) struct DependencyGraph<'a> {
+    pub(crate) nodes: HashMap<PathBuf, &'a Elf>,
+    pub(crate) edges: Vec<(PathBuf, PathBuf)>,
+    pub(crate) unresolved: HashMap<PathBuf, Vec<UnresolvedDependency>>,
+    /// For every reached node (other than the root itself), the chain of requesters that pulled
+    /// it in, root first, so a deep missing dependency can be traced back to the top-level
+    /// binary it ultimately breaks.
+    pub(crate) requesters: HashMap<PathBuf, Vec<PathBuf>>,
+}
+
+/// A `DependencyGraph`, stripped of its borrowed `Elf` references so it can be serialized
+/// alongside the rest of `Report`.
+#[derive(Debug, Serialize)]
+pub(crate) struct DependencyGraphSummary {
+    /// Each resolved-in-package ELF reached from the root, mapped to the dependencies it pulls
+    /// in directly.
+    adjacency: BTreeMap<PathBuf, Vec<PathBuf>>,
+    /// For each reached ELF, the chain of requesters (root first) that pulled it in.
+    requesters: BTreeMap<PathBuf, Vec<PathBuf>>,
+    /// `DT_NEEDED` entries that couldn't be resolved, keyed by the ELF that required them.
+    unresolved: BTreeMap<PathBuf, Vec<UnresolvedDependency>>,
+}
+
+impl<'a> DependencyGraph<'a> {
+    /// Walk the full transitive shared-object closure reachable from `root`. Returns an empty
+    /// graph if `root` isn't an ELF file in `package`. `search_config` supplies the same
+    /// `LD_LIBRARY_PATH`/`ld.so.conf`/trusted-defaults fallback `DependencyResolver` uses, under
+    /// the same injectable sysroot, so this transitive view agrees with the per-ELF validation
+    /// path on what a `--sysroot`-staged install tree actually resolves.
+    #[must_use]
+    pub(crate) fn build(root: &Path, package: &'a Package, search_config: &SearchConfig) -> Self {
+        let mut graph = Self::default();
+        let elfs = package.elfs();
+        let Some(&root_elf) = elfs.get(root) else {
+            return graph;
+        };
+
+        let symlink_resolver = SymlinkResolver::new(package);
+        let mut visited = HashSet::new();
+        Self::walk(
+            &mut graph,
+            root,
+            root_elf,
+            &[],
+            &[],
+            package,
+            search_config,
+            &symlink_resolver,
+            &mut visited,
+        );
+        graph
+    }
+
+    /// Collapse this graph into the owned, serializable form `Report` exposes.
+    pub(crate) fn into_summary(self) -> DependencyGraphSummary {
+        let mut adjacency: BTreeMap<PathBuf, Vec<PathBuf>> = BTreeMap::new();
+        for (needed_by, needed) in self.edges {
+            adjacency.entry(needed_by).or_default().push(needed);
+        }
+        DependencyGraphSummary {
+            adjacency,
+            requesters: self.requesters.into_iter().collect(),
+            unresolved: self.unresolved.into_iter().collect(),
+        }
+    }
+
+    /// Recurse into `elf`'s `DT_NEEDED` entries. `ancestor_rpath` is the RPATH list inherited
+    /// from every ancestor in the chain so far (RPATH entries accumulate down the chain; RUNPATH
+    /// never does). `chain` is the chain of requesters that led here, root first. `visited`
+    /// breaks cycles, keyed by cleaned absolute path.
+    fn walk(
+        graph: &mut Self,
+        path: &Path,
+        elf: &'a Elf,
+        ancestor_rpath: &[PathBuf],
+        chain: &[PathBuf],
+        package: &'a Package,
+        search_config: &SearchConfig,
+        symlink_resolver: &SymlinkResolver<'a>,
+        visited: &mut HashSet<PathBuf>,
+    ) {
+        if !visited.insert(path.to_path_buf()) {
+            return;
+        }
+        graph.nodes.insert(path.to_path_buf(), elf);
+        if !chain.is_empty() {
+            graph.requesters.insert(path.to_path_buf(), chain.to_vec());
+        }
+
+        let origin = path.parent().unwrap_or_else(|| Path::new("/"));
+        let own_runpath = elf.normalized_runpath(origin);
+
+        // Directories consulted once RPATH/RUNPATH are exhausted, mirroring
+        // `DependencyResolver::determine_search_paths`: the emulated `LD_LIBRARY_PATH`,
+        // `ld.so.conf`, then the trusted defaults, all resolved under `search_config`'s sysroot
+        // rather than the real host root.
+        let fallback_dirs = || {
+            search_config
+                .ld_library_path()
+                .chain(search_config.ld_so_conf_dirs())
+                .chain(search_config.trusted_defaults())
+                .map(|search_path| search_path.path)
+        };
+
+        // Per ld.so: a declared RUNPATH resolves this object's own direct dependencies on its
+        // own (inherited RPATH is *not* consulted alongside it), and the RUNPATH itself is not
+        // passed on; the RPATH inherited from ancestors is still carried down past it. With no
+        // RUNPATH, this object's own RPATH is searched in addition to whatever was inherited,
+        // and the combined list is carried down to its dependencies in turn.
+        let (search_paths, next_ancestor_rpath) = if own_runpath.is_empty() {
+            let mut inherited = elf.normalized_rpath(origin);
+            inherited.extend(ancestor_rpath.iter().cloned());
+            let mut search_paths = inherited.clone();
+            search_paths.extend(fallback_dirs());
+            (search_paths, inherited)
+        } else {
+            let mut search_paths = own_runpath;
+            search_paths.extend(fallback_dirs());
+            (search_paths, ancestor_rpath.to_vec())
+        };
+
+        let next_chain: Vec<PathBuf> = chain.iter().cloned().chain(std::iter::once(path.to_path_buf())).collect();
+        for needed in elf.dependencies() {
+            match Self::resolve(needed, &search_paths, package, symlink_resolver) {
+                Some((resolved_path, resolved_elf)) => {
+                    graph
+                        .edges
+                        .push((path.to_path_buf(), resolved_path.clone()));
+                    Self::walk(
+                        graph,
+                        &resolved_path,
+                        resolved_elf,
+                        &next_ancestor_rpath,
+                        &next_chain,
+                        package,
+                        search_config,
+                        symlink_resolver,
+                        visited,
+                    );
+                }
+                None => {
+                    graph
+                        .unresolved
+                        .entry(path.to_path_buf())
+                        .or_default()
+                        .push(UnresolvedDependency {
+                            needed: needed.clone(),
+                            searched_paths: search_paths.clone(),
+                        });
+                }
+            }
+        }
+    }
+
+    /// Resolve a single `DT_NEEDED` name against `search_paths`, in order. A `DT_NEEDED` value
+    /// that is itself an absolute path is checked directly rather than joined onto a search path.
+    fn resolve(
+        needed: &str,
+        search_paths: &[PathBuf],
+        package: &'a Package,
+        symlink_resolver: &SymlinkResolver<'a>,
+    ) -> Option<(PathBuf, &'a Elf)> {
+        let needed_path = Path::new(needed);
+        if needed_path.is_absolute() {
+            return Self::find_elf(needed_path, package, symlink_resolver);
+        }
+        search_paths
+            .iter()
+            .find_map(|search_path| Self::find_elf(&search_path.join(needed), package, symlink_resolver))
+    }
+
+    /// Look up `path` in the package and return it only if it resolves to an ELF file, following
+    /// a symlink via `SymlinkResolver` (which already resolves chains and detects cycles).
+    fn find_elf(
+        path: &Path,
+        package: &'a Package,
+        symlink_resolver: &SymlinkResolver<'a>,
+    ) -> Option<(PathBuf, &'a Elf)> {
+        let resolved = match symlink_resolver.resolve(path) {
+            Some(SymlinkResolutionResult::Found { target, .. }) => target.to_path_buf(),
+            Some(_) => return None, // System dependency, dangling, cycle, or escapes root.
+            None => path.to_path_buf(),
+        };
+        package
+            .elfs()
+            .get(resolved.as_path())
+            .map(|&elf| (resolved, elf))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::package::{PackageFile, PackageFiles};
+    use std::path::PathBuf;
+
+    fn elf_needing(dependencies: &[&str], rpath: Vec<String>, runpath: Vec<String>) -> Elf {
+        Elf::new_for_testing_with_dependencies(
+            rpath,
+            runpath,
+            Vec::new(),
+            dependencies.iter().map(|s| s.to_string()).collect(),
+        )
+    }
+
+    /// A `SearchConfig` rooted at a sysroot that doesn't exist, so `ld.so.conf` and the trusted
+    /// default directories never pick up anything from the host running the test suite -- these
+    /// tests only care about RPATH/RUNPATH-driven resolution within the fake package itself.
+    fn test_search_config() -> SearchConfig {
+        SearchConfig::new("/nonexistent-test-sysroot", Vec::new())
+    }
+
+    #[test]
+    fn test_build_walks_transitive_chain() {
+        let mut files = PackageFiles::new();
+        files.insert(
+            PathBuf::from("/usr/bin/app"),
+            PackageFile::Elf(elf_needing(&["libfoo.so"], Vec::new(), vec!["/usr/lib".to_string()])),
+        );
+        files.insert(
+            PathBuf::from("/usr/lib/libfoo.so"),
+            PackageFile::Elf(elf_needing(&["libbar.so"], Vec::new(), vec!["/usr/lib".to_string()])),
+        );
+        files.insert(
+            PathBuf::from("/usr/lib/libbar.so"),
+            PackageFile::Elf(elf_needing(&[], Vec::new(), Vec::new())),
+        );
+        let package = Package::new_for_testing(PathBuf::from("/test/package.deb"), files);
+
+        let graph = DependencyGraph::build(Path::new("/usr/bin/app"), &package, &test_search_config());
+
+        assert_eq!(graph.nodes.len(), 3);
+        assert!(graph.unresolved.is_empty());
+        assert!(graph
+            .edges
+            .contains(&(PathBuf::from("/usr/bin/app"), PathBuf::from("/usr/lib/libfoo.so"))));
+        assert!(graph.edges.contains(&(
+            PathBuf::from("/usr/lib/libfoo.so"),
+            PathBuf::from("/usr/lib/libbar.so")
+        )));
+    }
+
+    #[test]
+    fn test_build_records_requester_chain_root_first() {
+        let mut files = PackageFiles::new();
+        files.insert(
+            PathBuf::from("/usr/bin/app"),
+            PackageFile::Elf(elf_needing(&["libfoo.so"], Vec::new(), vec!["/usr/lib".to_string()])),
+        );
+        files.insert(
+            PathBuf::from("/usr/lib/libfoo.so"),
+            PackageFile::Elf(elf_needing(&["libbar.so"], Vec::new(), vec!["/usr/lib".to_string()])),
+        );
+        files.insert(
+            PathBuf::from("/usr/lib/libbar.so"),
+            PackageFile::Elf(elf_needing(&[], Vec::new(), Vec::new())),
+        );
+        let package = Package::new_for_testing(PathBuf::from("/test/package.deb"), files);
+
+        let graph = DependencyGraph::build(Path::new("/usr/bin/app"), &package, &test_search_config());
+
+        // The root itself has no requester chain.
+        assert!(!graph.requesters.contains_key(Path::new("/usr/bin/app")));
+        assert_eq!(
+            graph.requesters.get(Path::new("/usr/lib/libfoo.so")).unwrap(),
+            &vec![PathBuf::from("/usr/bin/app")]
+        );
+        assert_eq!(
+            graph.requesters.get(Path::new("/usr/lib/libbar.so")).unwrap(),
+            &vec![PathBuf::from("/usr/bin/app"), PathBuf::from("/usr/lib/libfoo.so")]
+        );
+    }
+
+    #[test]
+    fn test_into_summary_collapses_edges_into_adjacency() {
+        let mut files = PackageFiles::new();
+        files.insert(
+            PathBuf::from("/usr/bin/app"),
+            PackageFile::Elf(elf_needing(&["libfoo.so", "libmissing.so"], Vec::new(), vec!["/usr/lib".to_string()])),
+        );
+        files.insert(
+            PathBuf::from("/usr/lib/libfoo.so"),
+            PackageFile::Elf(elf_needing(&[], Vec::new(), Vec::new())),
+        );
+        let package = Package::new_for_testing(PathBuf::from("/test/package.deb"), files);
+
+        let summary = DependencyGraph::build(Path::new("/usr/bin/app"), &package, &test_search_config()).into_summary();
+
+        assert_eq!(
+            summary.adjacency.get(Path::new("/usr/bin/app")).unwrap(),
+            &vec![PathBuf::from("/usr/lib/libfoo.so")]
+        );
+        assert_eq!(
+            summary.requesters.get(Path::new("/usr/lib/libfoo.so")).unwrap(),
+            &vec![PathBuf::from("/usr/bin/app")]
+        );
+        let unresolved = summary.unresolved.get(Path::new("/usr/bin/app")).unwrap();
+        assert_eq!(unresolved[0].needed, "libmissing.so");
+    }
+
+    #[test]
+    fn test_build_records_unresolved_dependency_instead_of_erroring() {
+        let mut files = PackageFiles::new();
+        files.insert(
+            PathBuf::from("/usr/bin/app"),
+            PackageFile::Elf(elf_needing(&["libmissing.so"], Vec::new(), Vec::new())),
+        );
+        let package = Package::new_for_testing(PathBuf::from("/test/package.deb"), files);
+
+        let graph = DependencyGraph::build(Path::new("/usr/bin/app"), &package, &test_search_config());
+
+        assert_eq!(graph.nodes.len(), 1);
+        assert!(graph.edges.is_empty());
+        let unresolved = graph
+            .unresolved
+            .get(Path::new("/usr/bin/app"))
+            .expect("should have recorded the unresolved dependency");
+        assert_eq!(unresolved.len(), 1);
+        assert_eq!(unresolved[0].needed, "libmissing.so");
+    }
+
+    #[test]
+    fn test_build_breaks_cycles_via_visited_set() {
+        let mut files = PackageFiles::new();
+        files.insert(
+            PathBuf::from("/usr/lib/liba.so"),
+            PackageFile::Elf(elf_needing(&["libb.so"], Vec::new(), vec!["/usr/lib".to_string()])),
+        );
+        files.insert(
+            PathBuf::from("/usr/lib/libb.so"),
+            PackageFile::Elf(elf_needing(&["liba.so"], Vec::new(), vec!["/usr/lib".to_string()])),
+        );
+        let package = Package::new_for_testing(PathBuf::from("/test/package.deb"), files);
+
+        let graph = DependencyGraph::build(Path::new("/usr/lib/liba.so"), &package, &test_search_config());
+
+        // Both nodes are reached exactly once, despite the cycle.
+        assert_eq!(graph.nodes.len(), 2);
+        assert_eq!(graph.edges.len(), 2);
+    }
+
+    #[test]
+    fn test_runpath_is_not_inherited_but_rpath_is() {
+        // app has RPATH only (inherited by its whole chain); libfoo has a RUNPATH of its own,
+        // which must NOT be combined with the inherited RPATH, and must NOT itself propagate
+        // further down to libbar.
+        let mut files = PackageFiles::new();
+        files.insert(
+            PathBuf::from("/usr/bin/app"),
+            PackageFile::Elf(elf_needing(
+                &["libfoo.so"],
+                vec!["/opt/inherited".to_string()],
+                Vec::new(),
+            )),
+        );
+        files.insert(
+            PathBuf::from("/opt/inherited/libfoo.so"),
+            PackageFile::Elf(elf_needing(
+                &["libbar.so"],
+                Vec::new(),
+                vec!["/opt/runpath-only".to_string()],
+            )),
+        );
+        files.insert(
+            PathBuf::from("/opt/runpath-only/libbar.so"),
+            PackageFile::Elf(elf_needing(&["libbaz.so", "libqux.so"], Vec::new(), Vec::new())),
+        );
+        // Only reachable through libfoo's RUNPATH, which libbar must not inherit.
+        files.insert(
+            PathBuf::from("/opt/runpath-only/libbaz.so"),
+            PackageFile::Elf(elf_needing(&[], Vec::new(), Vec::new())),
+        );
+        // Only reachable through app's RPATH, which libbar does inherit.
+        files.insert(
+            PathBuf::from("/opt/inherited/libqux.so"),
+            PackageFile::Elf(elf_needing(&[], Vec::new(), Vec::new())),
+        );
+        let package = Package::new_for_testing(PathBuf::from("/test/package.deb"), files);
+
+        let graph = DependencyGraph::build(Path::new("/usr/bin/app"), &package, &test_search_config());
+
+        // libfoo and libbar both resolved via the chain so far.
+        assert!(graph.nodes.contains_key(Path::new("/opt/inherited/libfoo.so")));
+        assert!(graph
+            .nodes
+            .contains_key(Path::new("/opt/runpath-only/libbar.so")));
+        // libqux resolves from libbar through app's RPATH, inherited past libfoo's RUNPATH.
+        assert!(graph.nodes.contains_key(Path::new("/opt/inherited/libqux.so")));
+        // libbaz can't be found from libbar: libfoo's RUNPATH doesn't propagate to it.
+        let unresolved = graph
+            .unresolved
+            .get(Path::new("/opt/runpath-only/libbar.so"))
+            .expect("libbaz.so should be unresolved");
+        assert_eq!(unresolved[0].needed, "libbaz.so");
+    }
+
+    #[test]
+    fn test_resolve_handles_absolute_dt_needed_value() {
+        let mut files = PackageFiles::new();
+        files.insert(
+            PathBuf::from("/usr/bin/app"),
+            PackageFile::Elf(elf_needing(&["/opt/lib/libfoo.so"], Vec::new(), Vec::new())),
+        );
+        files.insert(
+            PathBuf::from("/opt/lib/libfoo.so"),
+            PackageFile::Elf(elf_needing(&[], Vec::new(), Vec::new())),
+        );
+        let package = Package::new_for_testing(PathBuf::from("/test/package.deb"), files);
+
+        let graph = DependencyGraph::build(Path::new("/usr/bin/app"), &package, &test_search_config());
+
+        assert_eq!(graph.nodes.len(), 2);
+        assert!(graph.unresolved.is_empty());
+    }
+
+    #[test]
+    fn test_build_returns_empty_graph_for_non_elf_root() {
+        let mut files = PackageFiles::new();
+        files.insert(PathBuf::from("/usr/share/doc/readme"), PackageFile::File);
+        let package = Package::new_for_testing(PathBuf::from("/test/package.deb"), files);
+
+        let graph = DependencyGraph::build(Path::new("/usr/share/doc/readme"), &package, &test_search_config());
+
+        assert!(graph.nodes.is_empty());
+    }
+}
@@ -11,8 +11,10 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use crate::package::{Elf, Package};
+use crate::report::search_config::{SearchConfig, SearchPath, SearchPathSource};
 use crate::report::symlink_resolver::{SymlinkResolutionResult, SymlinkResolver};
 use crate::report::system_dependencies::SystemDependencies;
+use crate::report::utils::find_common_prefix;
 use crate::report::ReportDependencies;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -21,6 +23,11 @@ pub(crate) enum DependencyStatus {
     #[default]
     Missing, // The dependency was not found in the package or the system.
     Error(String), // An error occurred while resolving the dependency.
+    /// The dependency was found in the package, but doesn't define every symbol version
+    /// (`.gnu.version_d`) this ELF requires from it (`.gnu.version_r`) -- the classic "built
+    /// against a newer glibc/libstdc++ than the package actually ships" breakage, which a plain
+    /// presence check can't catch.
+    VersionUnsatisfied { missing_versions: Vec<String> },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -37,6 +44,12 @@ pub(crate) struct DependencyResolverResult {
     pub(crate) kind: DependencyKind,
     pub(crate) path: Option<PathBuf>,
     pub(crate) searched_paths: Vec<PathBuf>,
+    /// Which search-path rule (`DT_RPATH`, an emulated `LD_LIBRARY_PATH` entry, `ld.so.conf`,
+    /// etc.) actually resolved this dependency, so users can tell why this `.so` was picked
+    /// over another copy shipped elsewhere. `None` when the dependency matched the
+    /// system-dependency list by name, or wasn't found in any search path; an `Error` result
+    /// carries the source of the search path at which resolution stopped.
+    pub(crate) source: Option<SearchPathSource>,
 }
 
 impl Serialize for DependencyResolverResult {
@@ -59,6 +72,10 @@ impl Serialize for DependencyResolverResult {
             state.serialize_field("searched_paths", &self.searched_paths)?;
         }
 
+        if let Some(ref source) = self.source {
+            state.serialize_field("source", source)?;
+        }
+
         state.end()
     }
 }
@@ -75,26 +92,37 @@ impl DependencyResolverResult {
             kind: r#type,
             path: path.into(),
             searched_paths,
+            source: None,
         }
     }
+
+    /// Record which search-path rule resolved this dependency.
+    #[must_use]
+    pub(crate) fn with_source(mut self, source: SearchPathSource) -> Self {
+        self.source = Some(source);
+        self
+    }
 }
 
-pub(crate) struct DependencyResolver<'a, 'b, 'c> {
+pub(crate) struct DependencyResolver<'a, 'b, 'c, 'd> {
     package: &'a Package,
     symlink_resolver: &'b SymlinkResolver<'a>,
     system_dependencies: &'c SystemDependencies,
+    search_config: &'d SearchConfig,
 }
 
-impl<'a, 'b, 'c> DependencyResolver<'a, 'b, 'c> {
+impl<'a, 'b, 'c, 'd> DependencyResolver<'a, 'b, 'c, 'd> {
     pub(crate) fn new(
         package: &'a Package,
         symlink_resolver: &'b SymlinkResolver<'a>,
         system_dependencies: &'c SystemDependencies,
+        search_config: &'d SearchConfig,
     ) -> Self {
         Self {
             package,
             symlink_resolver,
             system_dependencies,
+            search_config,
         }
     }
 
@@ -135,21 +163,32 @@ impl<'a, 'b, 'c> DependencyResolver<'a, 'b, 'c> {
                 None,
             );
         }
-        let search_paths = Self::determine_search_paths(path, elf);
+        let search_paths =
+            Self::determine_search_paths(path, elf, self.package, self.search_config);
+        let searched_paths: Vec<PathBuf> =
+            search_paths.iter().map(|search_path| search_path.path.clone()).collect();
 
         // Cannot be parallelized, as the order is important when searching for the dependency.
         // We want to search the paths in order of definition.
         for search_path in &search_paths {
-            let dependency_path = search_path.join(dependency);
+            let dependency_path = search_path.path.join(dependency);
             let (status, kind, path) = self.find_dependency(&dependency_path);
             match status {
                 DependencyStatus::Missing => {} // Continue to the next search path.
                 DependencyStatus::Found => {
-                    return DependencyResolverResult::new(status, kind, search_paths, path);
+                    let status = if kind == DependencyKind::Package {
+                        self.check_version_unsatisfied(elf, dependency, path.as_deref())
+                            .unwrap_or(status)
+                    } else {
+                        status
+                    };
+                    return DependencyResolverResult::new(status, kind, searched_paths, path)
+                        .with_source(search_path.source);
                 }
-                error @ DependencyStatus::Error(_) => {
+                error @ (DependencyStatus::Error(_) | DependencyStatus::VersionUnsatisfied { .. }) => {
                     // Stop searching and return the error.
-                    return DependencyResolverResult::new(error, kind, search_paths, path);
+                    return DependencyResolverResult::new(error, kind, searched_paths, path)
+                        .with_source(search_path.source);
                 }
             }
         }
@@ -157,24 +196,94 @@ impl<'a, 'b, 'c> DependencyResolver<'a, 'b, 'c> {
         DependencyResolverResult::new(
             DependencyStatus::Missing,
             DependencyKind::Unknown,
-            search_paths,
+            searched_paths,
             None,
         )
     }
 
-    /// Determine the search paths for resolving dependencies.
+    /// Determine the search paths for resolving dependencies, mirroring the dynamic linker's
+    /// own order of precedence:
     ///
-    /// The search order is:
-    /// 1. RPATH/RUNPATH entries from the ELF file (with `$ORIGIN` substitution)
-    /// 2. Common library paths from `/etc/ld.so.conf` conventions
-    /// 3. Default system library paths
+    /// 1. `DT_RPATH`, but only when the ELF has no `DT_RUNPATH` at all (`RUNPATH`, where
+    ///    present, supersedes `RPATH` outright rather than merely taking priority over it).
+    /// 2. The emulated `LD_LIBRARY_PATH` list from `search_config`.
+    /// 3. `DT_RUNPATH`.
+    /// 4. Directories from `/etc/ld.so.conf` under `search_config`'s sysroot (recursively
+    ///    following its `include` directives).
+    /// 5. The trusted default directories (`/lib`, `/usr/lib`, etc.), also under the sysroot.
+    /// 6. `lib`/`bin` directories discovered under the package's own common install prefix, for
+    ///    packages that install outside the trusted defaults (e.g. under `/opt/<product>`).
     ///
-    /// We do not check common paths, or default system paths, but instead use
-    /// `SystemDependencyResolver` to check if the dependency is a system dependency instead.
-    /// It only checks the dependency name, not the path, so it's distro-agnostic.
-    fn determine_search_paths(path: &Path, elf: &'a Elf) -> Vec<PathBuf> {
+    /// Every one of these is resolved against the package's own file map (via
+    /// `find_dependency`), not the host filesystem, so this only catches dependencies the
+    /// package ships itself in one of these locations. Dependencies satisfied by the host's own
+    /// copy of a library are instead recognized by `SystemDependencies`, which matches by
+    /// filename and is distro-agnostic.
+    fn determine_search_paths(
+        path: &Path,
+        elf: &'a Elf,
+        package: &Package,
+        search_config: &SearchConfig,
+    ) -> Vec<SearchPath> {
         let origin = path.parent().unwrap_or_else(|| Path::new("/"));
-        elf.normalize_paths(origin)
+        let mut search_paths = Vec::new();
+
+        if elf.runpath().is_empty() {
+            search_paths.extend(elf.normalized_rpath(origin).into_iter().map(|path| SearchPath {
+                path,
+                source: SearchPathSource::Rpath,
+            }));
+        }
+        search_paths.extend(search_config.ld_library_path());
+        search_paths.extend(elf.normalized_runpath(origin).into_iter().map(|path| SearchPath {
+            path,
+            source: SearchPathSource::Runpath,
+        }));
+        search_paths.extend(search_config.ld_so_conf_dirs());
+        search_paths.extend(search_config.trusted_defaults());
+        search_paths.extend(Self::discover_package_library_directories(package).into_iter().map(
+            |path| SearchPath {
+                path,
+                source: SearchPathSource::PackageInstallPrefix,
+            },
+        ));
+        search_paths
+    }
+
+    /// Discover `lib`/`lib64`/`bin` directories under the common prefix shared by every
+    /// extracted file, so packages installed outside the well-known default directories (e.g.
+    /// under `/opt/<product>`) can still resolve their own shared libraries.
+    fn discover_package_library_directories(package: &Package) -> Vec<PathBuf> {
+        let paths: Vec<&Path> = package.files().keys().map(PathBuf::as_path).collect();
+        let Some(common_prefix) = find_common_prefix(&paths) else {
+            return Vec::new();
+        };
+        ["lib", "lib64", "usr/lib", "usr/lib64", "bin", "usr/bin"]
+            .iter()
+            .map(|suffix| common_prefix.join(suffix))
+            .collect()
+    }
+
+    /// If `elf` requires specific symbol versions from `dependency` (per its `.gnu.version_r`
+    /// section) and `dependency` resolved to a package-provided ELF at `resolved_path`, check
+    /// that ELF's own `.gnu.version_d` definitions for each required version. Returns
+    /// `DependencyStatus::VersionUnsatisfied` if any are missing, or `None` if there's nothing
+    /// to check or everything required is defined.
+    fn check_version_unsatisfied(
+        &self,
+        elf: &Elf,
+        dependency: &str,
+        resolved_path: Option<&Path>,
+    ) -> Option<DependencyStatus> {
+        let required = elf.version_requirements_by_dependency().get(dependency)?;
+        let resolved_elf = self.package.elfs().get(resolved_path?)?;
+        let defined = resolved_elf.defined_versions();
+        let missing_versions: Vec<String> = required
+            .iter()
+            .filter(|version| !defined.contains(version.as_str()))
+            .cloned()
+            .collect();
+        (!missing_versions.is_empty()).then_some(DependencyStatus::VersionUnsatisfied { missing_versions })
     }
 
     // Assumes the calling function has checked that the path exists in the package.
@@ -182,17 +291,27 @@ impl<'a, 'b, 'c> DependencyResolver<'a, 'b, 'c> {
         if let Some(symlink_result) = self.symlink_resolver.resolve(path) {
             // If the dependency is a symlink, we need to resolve it to the target path.
             match symlink_result {
-                SymlinkResolutionResult::NotFound(target_path) => {
-                    self.resolve_system_dependency(target_path)
+                SymlinkResolutionResult::SystemDependency { target, .. }
+                | SymlinkResolutionResult::DanglingInPackage { target, .. } => {
+                    self.resolve_system_dependency(target)
                 }
-                SymlinkResolutionResult::Found(target_path) => {
-                    self.resolve_package_dependency(target_path)
+                SymlinkResolutionResult::Found { target, .. } => {
+                    self.resolve_package_dependency(target)
                 }
-                SymlinkResolutionResult::CycleDetected() => (
+                SymlinkResolutionResult::CycleDetected { .. } => (
                     DependencyStatus::Error(format!("Symlink cycle detected: {}", path.display())),
                     DependencyKind::Unknown,
                     None,
                 ),
+                SymlinkResolutionResult::EscapesRoot { raw_target, .. } => (
+                    DependencyStatus::Error(format!(
+                        "Symlink target escapes package root: {} -> {}",
+                        path.display(),
+                        raw_target.display()
+                    )),
+                    DependencyKind::Unknown,
+                    None,
+                ),
             }
         } else {
             // Not a symlink, check if the dependency can be found in the package.
@@ -5,12 +5,15 @@
 //! Defines error types for report validation (e.g., system dependencies found in package).
 
 use serde::Serialize;
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
+use super::dependency_resolver::{DependencyKind, DependencyStatus};
+use super::symbol_versions::{self, VersionBaseline};
 use super::system_dependencies::SystemDependencies;
-use crate::package::Package;
+use super::ReportDependencies;
+use crate::package::{Elf, Package};
 use crate::report::symlink_resolver::{SymlinkResolutionResult, SymlinkResolver};
 
 pub(crate) type SystemDependencyResolutionErrors<'a> = Vec<ReportError<'a>>;
@@ -20,63 +23,274 @@ pub(crate) enum ReportError<'a> {
     // Dependencies should *not* be defined as system dependencies and exist in the package.
     // Either the dependency was wrongly defined as a system dependency, or the package
     // contains the dependency it shouldn't.
+    //
+    // `dependency` is the system-dependencies rule that matched: either the exact filename, or
+    // the glob pattern text if the match came from a pattern like `libm.so.*`.
     #[error("System dependency {dependency}: Found in package at path(s): {paths:?}")]
     SystemDependencyFoundInPackage {
         dependency: &'a str,
         paths: Vec<&'a Path>,
     },
+    // The dynamic loader's search (RPATH/RUNPATH, then the default library directories) could
+    // not satisfy this `DT_NEEDED` entry from inside the package, and it isn't a recognized
+    // system dependency either.
+    #[error("ELF {elf:?}: Could not resolve needed library {needed}, searched: {searched:?}")]
+    UnresolvedNeededLibrary {
+        elf: &'a Path,
+        needed: &'a str,
+        searched: Vec<PathBuf>,
+    },
+    // The highest version of a provider (e.g. `GLIBC`, `GLIBCXX`) required by this ELF's
+    // `.gnu.version_r` section is newer than the applicable baseline: either a `PROVIDER <=
+    // VERSION` line for that provider in the system-dependencies file, or the general
+    // `--max-glibc` baseline otherwise. Not raised if the requirement is satisfied by the
+    // `.gnu.version_d` section of a resolved in-package dependency, since the package then ships
+    // the version itself and portability to an older system libc doesn't matter for it.
+    #[error("ELF {elf:?}: Requires {symbol}, newer than baseline {baseline}")]
+    SymbolVersionTooNew {
+        elf: &'a Path,
+        symbol: &'a str,
+        baseline: String,
+    },
+    // A name (or glob pattern) in the system-dependencies file never matched any `DT_NEEDED`
+    // soname actually referenced by an ELF in the package, i.e. it's a stale allowlist entry.
+    #[error("System dependency {dependency}: Declared but not needed by any ELF in the package")]
+    UnusedSystemDependency { dependency: &'a str },
+    // None of this ELF's `DT_NEEDED` libraries (all resolved within the package, in search
+    // order) export this undefined symbol, so the binary will fail to load (or crash on first
+    // call) at runtime even though every dependency itself was found.
+    #[error("ELF {elf:?}: No resolved dependency exports required symbol {symbol}")]
+    MissingSymbol { elf: &'a Path, symbol: &'a str },
 }
 
 /// Scans a package for errors.
 ///
-/// Returns a list of errors for any system dependencies found in the package.
-/// Symlinks pointing outside the package are excluded from error detection.
+/// Returns a list of errors for any system dependencies found in the package, one
+/// `UnresolvedNeededLibrary` error for each `DT_NEEDED` entry the dependency resolver could not
+/// satisfy, one `MissingSymbol` error for each undefined symbol no resolved dependency exports,
+/// and one `SymbolVersionTooNew` error for each ELF that requires a symbol version newer than
+/// the baseline applicable to its provider (a `system_dependencies`-declared per-provider
+/// baseline, falling back to `max_glibc` if set). Symlinks pointing outside the package are
+/// excluded from error detection.
 pub(crate) fn scan_for_errors<'a>(
     package: &'a Package,
     symlink_resolver: &SymlinkResolver<'a>,
     system_dependencies: &'a SystemDependencies,
+    dependencies: &ReportDependencies<'a>,
+    max_glibc: Option<&VersionBaseline>,
 ) -> SystemDependencyResolutionErrors<'a> {
-    let system_dependencies = system_dependencies.dependencies();
-    // Map file name to paths, and including only system dependencies.
-    package
+    // Map the matched rule (exact name or glob pattern) to paths, including only files whose
+    // name matches a system dependency.
+    let mut errors: SystemDependencyResolutionErrors<'a> = package
         .files()
         .iter()
         .filter_map(|(path, _)| {
             path.file_name()
                 .and_then(|f| f.to_str())
-                // Any system dependency included in the package is an error.
-                .filter(|name| system_dependencies.contains(*name))
+                // Any system dependency included in the package is an error. Carry the matched
+                // rule (not just the literal filename) so the report shows which rule fired.
+                .and_then(|name| system_dependencies.matching_rule(name))
                 // Except if it is a symlink to a file outside of the package.
                 .filter(|_| {
                     symlink_resolver
                         .resolve(path.as_path())
                         .is_none_or(|result| {
-                            !matches!(result, SymlinkResolutionResult::NotFound(_))
-                        }) // Regular files (not symlinks) should be included
+                            // Regular files (not symlinks) should be included. Symlinks that
+                            // point outside the package (to a system path, or a target the
+                            // package just doesn't ship) are excluded; only symlinks that
+                            // resolve to real content bundled in the package are an error.
+                            !matches!(
+                                result,
+                                SymlinkResolutionResult::SystemDependency { .. }
+                                    | SymlinkResolutionResult::DanglingInPackage { .. }
+                            )
+                        })
                 })
-                .map(|name| (name, path))
+                .map(|rule| (rule, path))
         })
         .fold(
             HashMap::<&str, Vec<&Path>>::new(),
-            |mut acc, (name, path)| {
-                acc.entry(name).or_default().push(path);
+            |mut acc, (rule, path)| {
+                acc.entry(rule).or_default().push(path);
                 acc
             },
         )
         .into_iter()
         .map(
-            |(name, paths)| ReportError::SystemDependencyFoundInPackage {
-                dependency: name,
+            |(rule, paths)| ReportError::SystemDependencyFoundInPackage {
+                dependency: rule,
                 paths,
             },
         )
+        .collect();
+
+    errors.extend(scan_for_unresolved_needed_libraries(dependencies));
+    errors.extend(scan_for_excessive_symbol_versions(
+        package,
+        dependencies,
+        system_dependencies,
+        max_glibc,
+    ));
+    errors.extend(scan_for_unused_system_dependencies(package, system_dependencies));
+    errors.extend(scan_for_missing_symbols(package, dependencies));
+    errors
+}
+
+/// Scans resolved dependencies for `DT_NEEDED` entries the loader search could not satisfy.
+fn scan_for_unresolved_needed_libraries<'a>(
+    dependencies: &ReportDependencies<'a>,
+) -> SystemDependencyResolutionErrors<'a> {
+    dependencies
+        .iter()
+        .flat_map(|(elf, needed)| needed.iter().map(move |(needed, result)| (*elf, *needed, result)))
+        .filter(|(_, _, result)| matches!(result.status, DependencyStatus::Missing))
+        .map(|(elf, needed, result)| ReportError::UnresolvedNeededLibrary {
+            elf,
+            needed,
+            searched: result.searched_paths.clone(),
+        })
+        .collect()
+}
+
+/// Scans every ELF in `package` for symbol version requirements newer than the baseline
+/// applicable to their provider (`system_dependencies`'s per-provider baselines, falling back to
+/// `default_baseline`), excluding any requirement already satisfied by the `.gnu.version_d`
+/// section of a dependency resolved inside the package.
+fn scan_for_excessive_symbol_versions<'a>(
+    package: &'a Package,
+    dependencies: &ReportDependencies<'a>,
+    system_dependencies: &SystemDependencies,
+    default_baseline: Option<&VersionBaseline>,
+) -> SystemDependencyResolutionErrors<'a> {
+    let elfs = package.elfs();
+    elfs.iter()
+        .flat_map(|(path, elf)| {
+            let (path, elf): (&'a Path, &'a Elf) = (*path, *elf);
+            let closure = transitive_package_dependencies(path, dependencies, &elfs);
+            symbol_versions::exceeding_requirements_per_provider(
+                elf.version_requirements(),
+                system_dependencies.version_baselines(),
+                default_baseline,
+            )
+            .into_iter()
+            .filter(|(symbol, _)| {
+                !closure.as_ref().is_some_and(|closure| {
+                    closure
+                        .iter()
+                        .any(|dep| dep.exported_symbols().iter().any(|s| s.ends_with(&format!("@{symbol}"))))
+                })
+            })
+            .map(|(symbol, baseline)| ReportError::SymbolVersionTooNew {
+                elf: path,
+                symbol,
+                baseline,
+            })
+            .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Scans the declared `system_dependencies` for rules (exact names or glob patterns) that
+/// never matched a `DT_NEEDED` soname actually referenced by any ELF in the package.
+fn scan_for_unused_system_dependencies<'a>(
+    package: &'a Package,
+    system_dependencies: &'a SystemDependencies,
+) -> SystemDependencyResolutionErrors<'a> {
+    let used_rules: HashSet<&str> = package
+        .elfs()
+        .into_values()
+        .flat_map(Elf::dependencies)
+        .filter_map(|needed| system_dependencies.matching_rule(needed))
+        .collect();
+
+    system_dependencies
+        .declared_rules()
+        .filter(|rule| !used_rules.contains(rule))
+        .map(|dependency| ReportError::UnusedSystemDependency { dependency })
         .collect()
 }
 
+/// Scans every ELF's undefined dynamic symbols against the exported symbols of its full
+/// transitive `DT_NEEDED` closure, reporting any that no dependency (direct or indirect)
+/// provides. Weak undefined symbols are never reported: the dynamic linker resolves an
+/// unsatisfied weak symbol to a null address rather than failing to load.
+///
+/// This is intentionally conservative: an ELF is only checked if *every* dependency reached
+/// while walking its closure resolved to an ELF shipped inside the package. The moment any
+/// dependency is a system library (or simply unresolved), the whole ELF is skipped, since this
+/// tool has no access to a system library's actual exported symbols to compare against, and
+/// guessing would produce false positives for the overwhelmingly common case of binaries linking
+/// against libc.
+fn scan_for_missing_symbols<'a>(
+    package: &'a Package,
+    dependencies: &ReportDependencies<'a>,
+) -> SystemDependencyResolutionErrors<'a> {
+    let elfs = package.elfs();
+
+    elfs.iter()
+        .filter_map(|(path, elf)| {
+            let (path, elf): (&'a Path, &'a Elf) = (*path, *elf);
+            let closure = transitive_package_dependencies(path, dependencies, &elfs)?;
+
+            Some(
+                elf.undefined_symbols()
+                    .iter()
+                    .filter(|symbol| !closure.iter().any(|dep| dep.exported_symbols().contains(*symbol)))
+                    .map(|symbol| ReportError::MissingSymbol {
+                        elf: path,
+                        symbol: symbol.as_str(),
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .flatten()
+        .collect()
+}
+
+/// Resolve the full transitive closure of `path`'s package-shipped `DT_NEEDED` dependencies
+/// (not just the direct ones), since a symbol a direct dependency doesn't itself export might
+/// still be re-exported further down its own dependency chain. Returns `None` if any dependency
+/// anywhere in the closure didn't resolve to a package-shipped ELF (a system dependency, or
+/// simply missing), since that makes the full closure unverifiable.
+///
+/// `pub(crate)` so `totals::symbols` can compute the same closure when tallying how many
+/// required symbols are satisfied, without duplicating the traversal.
+pub(crate) fn transitive_package_dependencies<'a>(
+    path: &'a Path,
+    dependencies: &ReportDependencies<'a>,
+    elfs: &HashMap<&'a Path, &'a Elf>,
+) -> Option<Vec<&'a Elf>> {
+    let mut closure = Vec::new();
+    let mut seen = HashSet::new();
+    let mut queue = vec![path];
+
+    while let Some(current_path) = queue.pop() {
+        let elf = elfs.get(current_path)?;
+        if elf.dependencies().is_empty() {
+            continue;
+        }
+        let needed = dependencies.get(current_path)?;
+        for dependency in elf.dependencies() {
+            let result = needed.get(dependency.as_str())?;
+            if result.kind != DependencyKind::Package || result.status != DependencyStatus::Found {
+                return None;
+            }
+            let dependency_path = result.path.as_deref()?;
+            if seen.insert(dependency_path) {
+                closure.push(*elfs.get(dependency_path)?);
+                queue.push(dependency_path);
+            }
+        }
+    }
+
+    Some(closure)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::package::{Package, PackageFile, PackageFiles};
+    use crate::package::{Elf, Package, PackageFile, PackageFiles, SymlinkTarget};
+    use crate::report::dependency_resolver::{DependencyKind, DependencyResolverResult};
     use crate::report::symlink_resolver::SymlinkResolver;
     use std::io::Write;
     use std::path::{Path, PathBuf};
@@ -99,6 +313,50 @@ mod tests {
         Package::new_for_testing(PathBuf::from("/test/package.deb"), package_files)
     }
 
+    fn empty_dependencies<'a>() -> ReportDependencies<'a> {
+        ReportDependencies::new()
+    }
+
+    /// An ELF file whose `DT_NEEDED` entries are `dependencies`, so tests that only care about
+    /// `SystemDependencyFoundInPackage`/symlink behavior don't also trip the unused-system-
+    /// dependency scan for names they declared but didn't wire up as actually needed.
+    fn elf_needing(dependencies: &[&str]) -> PackageFile {
+        PackageFile::Elf(Elf::new_for_testing_with_dependencies(
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            dependencies.iter().map(|d| (*d).to_string()).collect(),
+        ))
+    }
+
+    /// An ELF with `DT_NEEDED` entries, exported symbols, and undefined symbols, for exercising
+    /// `scan_for_missing_symbols`.
+    fn elf_with_symbols(
+        dependencies: &[&str],
+        exported_symbols: &[&str],
+        undefined_symbols: &[&str],
+    ) -> PackageFile {
+        PackageFile::Elf(Elf::new_for_testing_with_symbols(
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            dependencies.iter().map(|d| (*d).to_string()).collect(),
+            exported_symbols.iter().map(|s| (*s).to_string()).collect(),
+            undefined_symbols.iter().map(|s| (*s).to_string()).collect(),
+        ))
+    }
+
+    /// A `DependencyResolverResult` resolving to a package-shipped ELF at `path`, for building
+    /// `ReportDependencies` maps in tests that don't go through the real `DependencyResolver`.
+    fn found_in_package(path: &str) -> DependencyResolverResult {
+        DependencyResolverResult::new(
+            DependencyStatus::Found,
+            DependencyKind::Package,
+            Vec::new(),
+            PathBuf::from(path),
+        )
+    }
+
     fn assert_error_matches(
         error: &ReportError,
         expected_dependency: &str,
@@ -117,6 +375,7 @@ mod tests {
                     );
                 }
             }
+            other => panic!("Expected SystemDependencyFoundInPackage, got {other:?}"),
         }
     }
 
@@ -127,6 +386,7 @@ mod tests {
             .iter()
             .map(|e| match e {
                 ReportError::SystemDependencyFoundInPackage { dependency, .. } => *dependency,
+                other => panic!("Expected SystemDependencyFoundInPackage, got {other:?}"),
             })
             .collect()
     }
@@ -134,26 +394,26 @@ mod tests {
     #[test]
     fn test_no_errors_when_no_system_dependencies() {
         let package = create_test_package(vec![
-            ("/usr/bin/myapp", PackageFile::File),
+            ("/usr/bin/myapp", elf_needing(&["libm.so.6", "libc.so.6"])),
             ("/usr/lib/myapp.so", PackageFile::File),
         ]);
         let system_deps = create_system_dependencies(&["libm.so.6", "libc.so.6"]);
         let symlink_resolver = SymlinkResolver::new(&package);
 
-        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps);
+        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps, &empty_dependencies(), None);
         assert!(errors.is_empty());
     }
 
     #[test]
     fn test_error_detected_for_single_system_dependency() {
         let package = create_test_package(vec![
-            ("/usr/bin/myapp", PackageFile::File),
+            ("/usr/bin/myapp", elf_needing(&["libm.so.6"])),
             ("/usr/lib/libm.so.6", PackageFile::File),
         ]);
         let system_deps = create_system_dependencies(&["libm.so.6"]);
         let symlink_resolver = SymlinkResolver::new(&package);
 
-        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps);
+        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps, &empty_dependencies(), None);
         assert_eq!(errors.len(), 1);
         assert_error_matches(&errors[0], "libm.so.6", &["/usr/lib/libm.so.6"]);
     }
@@ -161,6 +421,7 @@ mod tests {
     #[test]
     fn test_multiple_paths_for_same_dependency() {
         let package = create_test_package(vec![
+            ("/usr/bin/myapp", elf_needing(&["libm.so.6"])),
             ("/usr/lib/libm.so.6", PackageFile::File),
             ("/usr/lib/x86_64-linux-gnu/libm.so.6", PackageFile::File),
             ("/opt/lib/libm.so.6", PackageFile::File),
@@ -168,7 +429,7 @@ mod tests {
         let system_deps = create_system_dependencies(&["libm.so.6"]);
         let symlink_resolver = SymlinkResolver::new(&package);
 
-        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps);
+        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps, &empty_dependencies(), None);
         assert_eq!(errors.len(), 1);
         assert_error_matches(
             &errors[0],
@@ -187,13 +448,16 @@ mod tests {
             ("/usr/lib/libm.so.6", PackageFile::File),
             ("/usr/lib/libc.so.6", PackageFile::File),
             ("/usr/lib/libpthread.so.0", PackageFile::File),
-            ("/usr/bin/myapp", PackageFile::File),
+            (
+                "/usr/bin/myapp",
+                elf_needing(&["libm.so.6", "libc.so.6", "libpthread.so.0"]),
+            ),
         ]);
         let system_deps =
             create_system_dependencies(&["libm.so.6", "libc.so.6", "libpthread.so.0"]);
         let symlink_resolver = SymlinkResolver::new(&package);
 
-        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps);
+        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps, &empty_dependencies(), None);
         assert_eq!(errors.len(), 3);
 
         let error_deps = get_error_dependencies(&errors);
@@ -205,6 +469,7 @@ mod tests {
     #[test]
     fn test_filename_matching_exact_only() {
         let package = create_test_package(vec![
+            ("/usr/bin/myapp", elf_needing(&["libm.so.6"])),
             ("/usr/lib/libm.so", PackageFile::File),
             ("/usr/lib/libm.so.6", PackageFile::File),
             ("/usr/lib/libm.so.6.0", PackageFile::File),
@@ -213,7 +478,7 @@ mod tests {
         let system_deps = create_system_dependencies(&["libm.so.6"]);
         let symlink_resolver = SymlinkResolver::new(&package);
 
-        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps);
+        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps, &empty_dependencies(), None);
         assert_eq!(errors.len(), 1);
         assert_error_matches(&errors[0], "libm.so.6", &["/usr/lib/libm.so.6"]);
     }
@@ -221,6 +486,7 @@ mod tests {
     #[test]
     fn test_files_in_different_directories_same_filename() {
         let package = create_test_package(vec![
+            ("/usr/bin/myapp", elf_needing(&["libm.so.6"])),
             ("/usr/lib/libm.so.6", PackageFile::File),
             ("/usr/lib/x86_64-linux-gnu/libm.so.6", PackageFile::File),
             ("/opt/custom/libm.so.6", PackageFile::File),
@@ -228,7 +494,7 @@ mod tests {
         let system_deps = create_system_dependencies(&["libm.so.6"]);
         let symlink_resolver = SymlinkResolver::new(&package);
 
-        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps);
+        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps, &empty_dependencies(), None);
         assert_eq!(errors.len(), 1);
         assert_error_matches(
             &errors[0],
@@ -250,20 +516,21 @@ mod tests {
         let system_deps = SystemDependencies::empty();
         let symlink_resolver = SymlinkResolver::new(&package);
 
-        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps);
+        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps, &empty_dependencies(), None);
         assert!(errors.is_empty());
     }
 
     #[test]
     fn test_error_message_formatting() {
         let package = create_test_package(vec![
+            ("/usr/bin/myapp", elf_needing(&["libm.so.6"])),
             ("/usr/lib/libm.so.6", PackageFile::File),
             ("/opt/lib/libm.so.6", PackageFile::File),
         ]);
         let system_deps = create_system_dependencies(&["libm.so.6"]);
         let symlink_resolver = SymlinkResolver::new(&package);
 
-        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps);
+        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps, &empty_dependencies(), None);
         assert_eq!(errors.len(), 1);
 
         let error_msg = format!("{}", errors[0]);
@@ -276,6 +543,7 @@ mod tests {
     #[test]
     fn test_only_filename_matters_not_path() {
         let package = create_test_package(vec![
+            ("/usr/bin/myapp", elf_needing(&["libm.so.6"])),
             ("/usr/bin/libm.so.6", PackageFile::File),
             ("/etc/libm.so.6", PackageFile::File),
             ("/tmp/libm.so.6", PackageFile::File),
@@ -283,7 +551,7 @@ mod tests {
         let system_deps = create_system_dependencies(&["libm.so.6"]);
         let symlink_resolver = SymlinkResolver::new(&package);
 
-        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps);
+        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps, &empty_dependencies(), None);
         assert_eq!(errors.len(), 1);
         assert_error_matches(
             &errors[0],
@@ -295,14 +563,17 @@ mod tests {
     #[test]
     fn test_symlink_pointing_outside_package_no_error() {
         // Symlink pointing to a path not in the package should NOT generate an error
-        let package = create_test_package(vec![(
-            "/usr/lib/libm.so.6",
-            PackageFile::Symlink(PathBuf::from("/lib/x86_64-linux-gnu/libm.so.6")),
-        )]);
+        let package = create_test_package(vec![
+            ("/usr/bin/myapp", elf_needing(&["libm.so.6"])),
+            (
+                "/usr/lib/libm.so.6",
+                PackageFile::Symlink(SymlinkTarget::new_for_testing(PathBuf::from("/lib/x86_64-linux-gnu/libm.so.6"))),
+            ),
+        ]);
         let system_deps = create_system_dependencies(&["libm.so.6"]);
         let symlink_resolver = SymlinkResolver::new(&package);
 
-        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps);
+        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps, &empty_dependencies(), None);
         // Should be empty because symlink points outside the package
         assert!(errors.is_empty());
     }
@@ -311,16 +582,17 @@ mod tests {
     fn test_symlink_pointing_inside_package_generates_error() {
         // Symlink pointing to a file inside the package SHOULD generate an error
         let package = create_test_package(vec![
+            ("/usr/bin/myapp", elf_needing(&["libm.so.6"])),
             ("/usr/lib/libm.so.6.actual", PackageFile::File),
             (
                 "/usr/lib/libm.so.6",
-                PackageFile::Symlink(PathBuf::from("/usr/lib/libm.so.6.actual")),
+                PackageFile::Symlink(SymlinkTarget::new_for_testing(PathBuf::from("/usr/lib/libm.so.6.actual"))),
             ),
         ]);
         let system_deps = create_system_dependencies(&["libm.so.6"]);
         let symlink_resolver = SymlinkResolver::new(&package);
 
-        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps);
+        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps, &empty_dependencies(), None);
         assert_eq!(errors.len(), 1);
         assert_error_matches(&errors[0], "libm.so.6", &["/usr/lib/libm.so.6"]);
     }
@@ -329,16 +601,17 @@ mod tests {
     fn test_mixed_regular_file_and_symlink_outside() {
         // Regular file should generate error, symlink pointing outside should not
         let package = create_test_package(vec![
+            ("/usr/bin/myapp", elf_needing(&["libm.so.6", "libc.so.6"])),
             ("/usr/lib/libm.so.6", PackageFile::File),
             (
                 "/usr/lib/libc.so.6",
-                PackageFile::Symlink(PathBuf::from("/lib/x86_64-linux-gnu/libc.so.6")),
+                PackageFile::Symlink(SymlinkTarget::new_for_testing(PathBuf::from("/lib/x86_64-linux-gnu/libc.so.6"))),
             ),
         ]);
         let system_deps = create_system_dependencies(&["libm.so.6", "libc.so.6"]);
         let symlink_resolver = SymlinkResolver::new(&package);
 
-        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps);
+        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps, &empty_dependencies(), None);
         // Only libm.so.6 (regular file) should generate error
         assert_eq!(errors.len(), 1);
         assert_error_matches(&errors[0], "libm.so.6", &["/usr/lib/libm.so.6"]);
@@ -348,17 +621,18 @@ mod tests {
     fn test_mixed_regular_file_and_symlink_inside() {
         // Both regular file and symlink pointing inside should generate errors
         let package = create_test_package(vec![
+            ("/usr/bin/myapp", elf_needing(&["libm.so.6", "libc.so.6"])),
             ("/usr/lib/libm.so.6", PackageFile::File),
             ("/usr/lib/libc.so.6.actual", PackageFile::File),
             (
                 "/usr/lib/libc.so.6",
-                PackageFile::Symlink(PathBuf::from("/usr/lib/libc.so.6.actual")),
+                PackageFile::Symlink(SymlinkTarget::new_for_testing(PathBuf::from("/usr/lib/libc.so.6.actual"))),
             ),
         ]);
         let system_deps = create_system_dependencies(&["libm.so.6", "libc.so.6"]);
         let symlink_resolver = SymlinkResolver::new(&package);
 
-        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps);
+        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps, &empty_dependencies(), None);
         assert_eq!(errors.len(), 2);
 
         let error_deps = get_error_dependencies(&errors);
@@ -370,20 +644,21 @@ mod tests {
     fn test_symlink_chain_pointing_inside() {
         // Symlink chain A -> B -> file, where file is in package, should generate error
         let package = create_test_package(vec![
+            ("/usr/bin/myapp", elf_needing(&["libm.so.6"])),
             ("/usr/lib/libm.so.6.actual", PackageFile::File),
             (
                 "/usr/lib/libm.so.6.intermediate",
-                PackageFile::Symlink(PathBuf::from("/usr/lib/libm.so.6.actual")),
+                PackageFile::Symlink(SymlinkTarget::new_for_testing(PathBuf::from("/usr/lib/libm.so.6.actual"))),
             ),
             (
                 "/usr/lib/libm.so.6",
-                PackageFile::Symlink(PathBuf::from("/usr/lib/libm.so.6.intermediate")),
+                PackageFile::Symlink(SymlinkTarget::new_for_testing(PathBuf::from("/usr/lib/libm.so.6.intermediate"))),
             ),
         ]);
         let system_deps = create_system_dependencies(&["libm.so.6"]);
         let symlink_resolver = SymlinkResolver::new(&package);
 
-        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps);
+        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps, &empty_dependencies(), None);
         // The symlink chain resolves to a file inside the package, so it should generate an error
         assert_eq!(errors.len(), 1);
         assert_error_matches(&errors[0], "libm.so.6", &["/usr/lib/libm.so.6"]);
@@ -393,20 +668,490 @@ mod tests {
     fn test_symlink_chain_pointing_outside() {
         // Symlink chain A -> B -> file, where file is NOT in package, should NOT generate error
         let package = create_test_package(vec![
+            ("/usr/bin/myapp", elf_needing(&["libm.so.6"])),
             (
                 "/usr/lib/libm.so.6.intermediate",
-                PackageFile::Symlink(PathBuf::from("/lib/x86_64-linux-gnu/libm.so.6")),
+                PackageFile::Symlink(SymlinkTarget::new_for_testing(PathBuf::from("/lib/x86_64-linux-gnu/libm.so.6"))),
             ),
             (
                 "/usr/lib/libm.so.6",
-                PackageFile::Symlink(PathBuf::from("/usr/lib/libm.so.6.intermediate")),
+                PackageFile::Symlink(SymlinkTarget::new_for_testing(PathBuf::from("/usr/lib/libm.so.6.intermediate"))),
             ),
         ]);
         let system_deps = create_system_dependencies(&["libm.so.6"]);
         let symlink_resolver = SymlinkResolver::new(&package);
 
-        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps);
+        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps, &empty_dependencies(), None);
         // The symlink chain resolves to a file outside the package, so it should NOT generate an error
         assert!(errors.is_empty());
     }
+
+    #[test]
+    fn test_unresolved_needed_library_generates_error() {
+        let package = create_test_package(vec![("/usr/bin/myapp", PackageFile::File)]);
+        let system_deps = SystemDependencies::empty();
+        let symlink_resolver = SymlinkResolver::new(&package);
+
+        let mut dependencies = empty_dependencies();
+        dependencies.insert(
+            Path::new("/usr/bin/myapp"),
+            HashMap::from([(
+                "libmissing.so.1",
+                DependencyResolverResult::new(
+                    DependencyStatus::Missing,
+                    DependencyKind::Unknown,
+                    vec![PathBuf::from("/usr/lib")],
+                    None,
+                ),
+            )]),
+        );
+
+        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps, &dependencies, None);
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ReportError::UnresolvedNeededLibrary {
+                elf,
+                needed,
+                searched,
+            } => {
+                assert_eq!(*elf, Path::new("/usr/bin/myapp"));
+                assert_eq!(*needed, "libmissing.so.1");
+                assert_eq!(searched, &vec![PathBuf::from("/usr/lib")]);
+            }
+            other => panic!("Expected UnresolvedNeededLibrary, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_found_needed_library_generates_no_error() {
+        let package = create_test_package(vec![("/usr/bin/myapp", PackageFile::File)]);
+        let system_deps = SystemDependencies::empty();
+        let symlink_resolver = SymlinkResolver::new(&package);
+
+        let mut dependencies = empty_dependencies();
+        dependencies.insert(
+            Path::new("/usr/bin/myapp"),
+            HashMap::from([(
+                "libfound.so.1",
+                DependencyResolverResult::new(
+                    DependencyStatus::Found,
+                    DependencyKind::Package,
+                    Vec::new(),
+                    PathBuf::from("/usr/lib/libfound.so.1"),
+                ),
+            )]),
+        );
+
+        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps, &dependencies, None);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_symbol_version_exceeding_baseline_generates_error() {
+        let elf = Elf::new_for_testing_with_version_requirements(
+            Vec::new(),
+            Vec::new(),
+            vec!["GLIBC_2.27".to_string()],
+        );
+        let package = create_test_package(vec![("/usr/bin/myapp", PackageFile::Elf(elf))]);
+        let system_deps = SystemDependencies::empty();
+        let symlink_resolver = SymlinkResolver::new(&package);
+        let baseline = VersionBaseline::parse("2.17").unwrap();
+
+        let errors = scan_for_errors(
+            &package,
+            &symlink_resolver,
+            &system_deps,
+            &empty_dependencies(),
+            Some(&baseline),
+        );
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ReportError::SymbolVersionTooNew {
+                elf,
+                symbol,
+                baseline,
+            } => {
+                assert_eq!(*elf, Path::new("/usr/bin/myapp"));
+                assert_eq!(*symbol, "GLIBC_2.27");
+                assert_eq!(baseline, "2.17");
+            }
+            other => panic!("Expected SymbolVersionTooNew, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_symbol_version_within_baseline_generates_no_error() {
+        let elf = Elf::new_for_testing_with_version_requirements(
+            Vec::new(),
+            Vec::new(),
+            vec!["GLIBC_2.17".to_string()],
+        );
+        let package = create_test_package(vec![("/usr/bin/myapp", PackageFile::Elf(elf))]);
+        let system_deps = SystemDependencies::empty();
+        let symlink_resolver = SymlinkResolver::new(&package);
+        let baseline = VersionBaseline::parse("2.17").unwrap();
+
+        let errors = scan_for_errors(
+            &package,
+            &symlink_resolver,
+            &system_deps,
+            &empty_dependencies(),
+            Some(&baseline),
+        );
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_symbol_version_per_provider_baseline_overrides_default() {
+        let elf = Elf::new_for_testing_with_version_requirements(
+            Vec::new(),
+            Vec::new(),
+            vec!["GLIBCXX_3.4.25".to_string()],
+        );
+        let package = create_test_package(vec![("/usr/bin/myapp", PackageFile::Elf(elf))]);
+        // The default (`--max-glibc`-style) baseline would allow this, but the system
+        // dependencies file declares a stricter override specifically for GLIBCXX.
+        let system_deps = create_system_dependencies(&["GLIBCXX <= 3.4.19"]);
+        let symlink_resolver = SymlinkResolver::new(&package);
+        let default_baseline = VersionBaseline::parse("2.99").unwrap();
+
+        let errors = scan_for_errors(
+            &package,
+            &symlink_resolver,
+            &system_deps,
+            &empty_dependencies(),
+            Some(&default_baseline),
+        );
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ReportError::SymbolVersionTooNew { symbol, baseline, .. } => {
+                assert_eq!(*symbol, "GLIBCXX_3.4.25");
+                assert_eq!(baseline, "3.4.19");
+            }
+            other => panic!("Expected SymbolVersionTooNew, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_symbol_version_satisfied_by_resolved_package_dependency_is_not_an_error() {
+        let myapp = Elf::new_for_testing_with_symbols(
+            Vec::new(),
+            Vec::new(),
+            vec!["GLIBC_2.27".to_string()],
+            vec!["libfoo.so".to_string()],
+            Vec::new(),
+            Vec::new(),
+        );
+        // libfoo bundles its own copy of glibc 2.27's version definitions, so myapp's
+        // requirement doesn't depend on the system libc actually being that new.
+        let libfoo = Elf::new_for_testing_with_symbols(
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            vec!["some_symbol@GLIBC_2.27".to_string()],
+            Vec::new(),
+        );
+        let package = create_test_package(vec![
+            ("/usr/bin/myapp", PackageFile::Elf(myapp)),
+            ("/usr/lib/libfoo.so", PackageFile::Elf(libfoo)),
+        ]);
+        let system_deps = SystemDependencies::empty();
+        let symlink_resolver = SymlinkResolver::new(&package);
+        let baseline = VersionBaseline::parse("2.17").unwrap();
+
+        let mut dependencies = empty_dependencies();
+        dependencies.insert(
+            Path::new("/usr/bin/myapp"),
+            HashMap::from([(
+                "libfoo.so",
+                DependencyResolverResult::new(
+                    DependencyStatus::Found,
+                    DependencyKind::Package,
+                    Vec::new(),
+                    PathBuf::from("/usr/lib/libfoo.so"),
+                ),
+            )]),
+        );
+
+        let errors = scan_for_errors(
+            &package,
+            &symlink_resolver,
+            &system_deps,
+            &dependencies,
+            Some(&baseline),
+        );
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_no_max_glibc_baseline_skips_symbol_version_check() {
+        let elf = Elf::new_for_testing_with_version_requirements(
+            Vec::new(),
+            Vec::new(),
+            vec!["GLIBC_99.99".to_string()],
+        );
+        let package = create_test_package(vec![("/usr/bin/myapp", PackageFile::Elf(elf))]);
+        let system_deps = SystemDependencies::empty();
+        let symlink_resolver = SymlinkResolver::new(&package);
+
+        let errors = scan_for_errors(
+            &package,
+            &symlink_resolver,
+            &system_deps,
+            &empty_dependencies(),
+            None,
+        );
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_unused_system_dependency_generates_error() {
+        let package = create_test_package(vec![("/usr/bin/myapp", elf_needing(&["libm.so.6"]))]);
+        let system_deps = create_system_dependencies(&["libm.so.6", "libold.so.1"]);
+        let symlink_resolver = SymlinkResolver::new(&package);
+
+        let errors = scan_for_errors(
+            &package,
+            &symlink_resolver,
+            &system_deps,
+            &empty_dependencies(),
+            None,
+        );
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ReportError::UnusedSystemDependency { dependency } => {
+                assert_eq!(*dependency, "libold.so.1");
+            }
+            other => panic!("Expected UnusedSystemDependency, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_used_system_dependency_generates_no_unused_error() {
+        let package = create_test_package(vec![("/usr/bin/myapp", elf_needing(&["libm.so.6"]))]);
+        let system_deps = create_system_dependencies(&["libm.so.6"]);
+        let symlink_resolver = SymlinkResolver::new(&package);
+
+        let errors = scan_for_errors(
+            &package,
+            &symlink_resolver,
+            &system_deps,
+            &empty_dependencies(),
+            None,
+        );
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_unused_system_dependency_matched_via_glob_pattern() {
+        let package = create_test_package(vec![("/usr/bin/myapp", elf_needing(&["libm.so.6"]))]);
+        let system_deps = create_system_dependencies(&["libm.so.*"]);
+        let symlink_resolver = SymlinkResolver::new(&package);
+
+        let errors = scan_for_errors(
+            &package,
+            &symlink_resolver,
+            &system_deps,
+            &empty_dependencies(),
+            None,
+        );
+        // The pattern matched an actual DT_NEEDED soname, so it's not reported as unused.
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_missing_symbol_generates_error_when_no_package_dependency_exports_it() {
+        let package = create_test_package(vec![
+            (
+                "/usr/bin/myapp",
+                elf_with_symbols(&["libfoo.so"], &[], &["needed_symbol"]),
+            ),
+            (
+                "/usr/lib/libfoo.so",
+                elf_with_symbols(&[], &["other_symbol"], &[]),
+            ),
+        ]);
+        let system_deps = SystemDependencies::empty();
+        let symlink_resolver = SymlinkResolver::new(&package);
+
+        let mut dependencies = empty_dependencies();
+        dependencies.insert(
+            Path::new("/usr/bin/myapp"),
+            HashMap::from([("libfoo.so", found_in_package("/usr/lib/libfoo.so"))]),
+        );
+
+        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps, &dependencies, None);
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ReportError::MissingSymbol { elf, symbol } => {
+                assert_eq!(*elf, Path::new("/usr/bin/myapp"));
+                assert_eq!(*symbol, "needed_symbol");
+            }
+            other => panic!("Expected MissingSymbol, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_no_missing_symbol_error_when_dependency_exports_it() {
+        let package = create_test_package(vec![
+            (
+                "/usr/bin/myapp",
+                elf_with_symbols(&["libfoo.so"], &[], &["needed_symbol"]),
+            ),
+            (
+                "/usr/lib/libfoo.so",
+                elf_with_symbols(&[], &["needed_symbol"], &[]),
+            ),
+        ]);
+        let system_deps = SystemDependencies::empty();
+        let symlink_resolver = SymlinkResolver::new(&package);
+
+        let mut dependencies = empty_dependencies();
+        dependencies.insert(
+            Path::new("/usr/bin/myapp"),
+            HashMap::from([("libfoo.so", found_in_package("/usr/lib/libfoo.so"))]),
+        );
+
+        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps, &dependencies, None);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_missing_symbol_check_skipped_when_a_dependency_is_a_system_library() {
+        // `libfoo.so` doesn't export `needed_symbol`, but `libc.so.6` is a system dependency
+        // whose real exports aren't known here, so the whole ELF must be skipped rather than
+        // reported, to avoid a false positive.
+        let package = create_test_package(vec![
+            (
+                "/usr/bin/myapp",
+                elf_with_symbols(&["libfoo.so", "libc.so.6"], &[], &["needed_symbol"]),
+            ),
+            (
+                "/usr/lib/libfoo.so",
+                elf_with_symbols(&[], &["other_symbol"], &[]),
+            ),
+        ]);
+        let system_deps = create_system_dependencies(&["libc.so.6"]);
+        let symlink_resolver = SymlinkResolver::new(&package);
+
+        let mut dependencies = empty_dependencies();
+        dependencies.insert(
+            Path::new("/usr/bin/myapp"),
+            HashMap::from([
+                ("libfoo.so", found_in_package("/usr/lib/libfoo.so")),
+                (
+                    "libc.so.6",
+                    DependencyResolverResult::new(
+                        DependencyStatus::Found,
+                        DependencyKind::System,
+                        Vec::new(),
+                        None,
+                    ),
+                ),
+            ]),
+        );
+
+        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps, &dependencies, None);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_missing_symbol_satisfied_by_transitive_dependency() {
+        // `libbar.so` (not a direct `DT_NEEDED` of `myapp`) exports `needed_symbol`; it's only
+        // reachable by following `libfoo.so`'s own `DT_NEEDED` entry.
+        let package = create_test_package(vec![
+            (
+                "/usr/bin/myapp",
+                elf_with_symbols(&["libfoo.so"], &[], &["needed_symbol"]),
+            ),
+            (
+                "/usr/lib/libfoo.so",
+                elf_with_symbols(&["libbar.so"], &[], &[]),
+            ),
+            (
+                "/usr/lib/libbar.so",
+                elf_with_symbols(&[], &["needed_symbol"], &[]),
+            ),
+        ]);
+        let system_deps = SystemDependencies::empty();
+        let symlink_resolver = SymlinkResolver::new(&package);
+
+        let mut dependencies = empty_dependencies();
+        dependencies.insert(
+            Path::new("/usr/bin/myapp"),
+            HashMap::from([("libfoo.so", found_in_package("/usr/lib/libfoo.so"))]),
+        );
+        dependencies.insert(
+            Path::new("/usr/lib/libfoo.so"),
+            HashMap::from([("libbar.so", found_in_package("/usr/lib/libbar.so"))]),
+        );
+
+        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps, &dependencies, None);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_weak_undefined_symbol_unresolved_by_any_dependency_is_not_an_error() {
+        let package = create_test_package(vec![
+            (
+                "/usr/bin/myapp",
+                PackageFile::Elf(Elf::new_for_testing_with_weak_symbols(
+                    Vec::new(),
+                    Vec::new(),
+                    Vec::new(),
+                    vec!["libfoo.so".to_string()],
+                    Vec::new(),
+                    Vec::new(),
+                    vec!["optional_symbol".to_string()],
+                )),
+            ),
+            (
+                "/usr/lib/libfoo.so",
+                elf_with_symbols(&[], &["other_symbol"], &[]),
+            ),
+        ]);
+        let system_deps = SystemDependencies::empty();
+        let symlink_resolver = SymlinkResolver::new(&package);
+
+        let mut dependencies = empty_dependencies();
+        dependencies.insert(
+            Path::new("/usr/bin/myapp"),
+            HashMap::from([("libfoo.so", found_in_package("/usr/lib/libfoo.so"))]),
+        );
+
+        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps, &dependencies, None);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_missing_symbol_check_skipped_when_a_dependency_is_unresolved() {
+        let package = create_test_package(vec![(
+            "/usr/bin/myapp",
+            elf_with_symbols(&["libmissing.so"], &[], &["needed_symbol"]),
+        )]);
+        let system_deps = SystemDependencies::empty();
+        let symlink_resolver = SymlinkResolver::new(&package);
+
+        let mut dependencies = empty_dependencies();
+        dependencies.insert(
+            Path::new("/usr/bin/myapp"),
+            HashMap::from([(
+                "libmissing.so",
+                DependencyResolverResult::new(
+                    DependencyStatus::Missing,
+                    DependencyKind::Unknown,
+                    Vec::new(),
+                    None,
+                ),
+            )]),
+        );
+
+        let errors = scan_for_errors(&package, &symlink_resolver, &system_deps, &dependencies, None);
+        // `libmissing.so` already generates its own UnresolvedNeededLibrary error; the missing-
+        // symbol check must not also fire for a dependency it can't verify.
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], ReportError::UnresolvedNeededLibrary { .. }));
+    }
 }
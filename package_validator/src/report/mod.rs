@@ -5,25 +5,35 @@
 //! Report struct and public API for generating validation results.
 
 mod console;
+mod dependency_graph;
 mod dependency_resolver;
 mod errors;
+mod policy;
+mod sarif;
+mod search_config;
+mod symbol_versions;
 mod symlink_resolver;
 mod system_dependencies;
 mod totals;
 mod utils;
 mod validate;
 
-pub use console::summarize_report;
-pub(crate) use dependency_resolver::DependencyStatus;
+pub use console::{print_dependency_requesters, print_dependency_tree, summarize_report};
+pub(crate) use dependency_resolver::{DependencyKind, DependencyStatus};
+pub use policy::ValidationPolicy;
+pub use sarif::{to_sarif, SarifLog};
+pub use search_config::SearchConfig;
+pub use symbol_versions::VersionBaseline;
 pub use system_dependencies::SystemDependencies;
 pub use validate::validate_report;
 
 use anyhow::Result;
 use serde::Serialize;
 use std::collections::{BTreeMap, HashMap};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use crate::package::{Elf, Package};
+use crate::package::{Elf, ElfType, Package};
+use dependency_graph::{DependencyGraph, DependencyGraphSummary};
 use dependency_resolver::{DependencyResolver, DependencyResolverResult};
 use errors::{scan_for_errors, SystemDependencyResolutionErrors};
 use symlink_resolver::SymlinkResolver;
@@ -40,22 +50,49 @@ pub struct Report<'a> {
     errors: SystemDependencyResolutionErrors<'a>,
     dependencies: ReportDependencies<'a>,
     files: ReportFiles<'a>,
+    /// The full transitive runtime closure reachable from each top-level executable (an
+    /// `ElfType::Executable`, as opposed to a shared library pulled in only by others), so a
+    /// deep missing dependency can be traced back to the binary it ultimately breaks. Keyed by
+    /// the executable's own path.
+    dependency_graphs: BTreeMap<PathBuf, DependencyGraphSummary>,
 }
 
 impl<'a> Report<'a> {
     /// Create a new report.
     ///
+    /// `max_glibc` is an optional baseline (see `VersionBaseline`); when set, binaries that
+    /// require a symbol version newer than it are reported as errors.
+    ///
+    /// `search_config` supplies the library search-path configuration (`LD_LIBRARY_PATH`,
+    /// `ld.so.conf`, trusted defaults) the dynamic linker would use, under an injectable
+    /// sysroot; pass `SearchConfig::host()` to analyze against the host's own configuration.
+    ///
     /// # Errors
     /// Returns an error if the system dependencies file cannot be read.
-    pub fn new(package: &'a Package, system_dependencies: &'a SystemDependencies) -> Result<Self> {
+    pub fn new(
+        package: &'a Package,
+        system_dependencies: &'a SystemDependencies,
+        max_glibc: Option<&VersionBaseline>,
+        search_config: &SearchConfig,
+    ) -> Result<Self> {
         // Compute dependencies using resolvers that only need to live during computation
         let symlink_resolver = SymlinkResolver::new(package);
         let dependencies = {
-            let resolver = DependencyResolver::new(package, &symlink_resolver, system_dependencies);
+            let resolver = DependencyResolver::new(
+                package,
+                &symlink_resolver,
+                system_dependencies,
+                search_config,
+            );
             // dependencies() returns references tied to package, not the resolvers
             resolver.dependencies()
         };
-        let totals = ReportTotals::new(package.files(), &package.elfs(), &dependencies);
+        let totals = ReportTotals::new(
+            package.files(),
+            &package.elfs(),
+            &dependencies,
+            package.control_metadata(),
+        );
 
         Ok(Self {
             package: package
@@ -65,12 +102,29 @@ impl<'a> Report<'a> {
                 .to_string_lossy()
                 .to_string(),
             totals,
-            errors: scan_for_errors(package, &symlink_resolver, system_dependencies),
+            errors: scan_for_errors(
+                package,
+                &symlink_resolver,
+                system_dependencies,
+                &dependencies,
+                max_glibc,
+            ),
             dependencies,
             // Only interested in the ELF files for the report.
             // Using sequential iteration here since parallel collection into BTreeMap
             // provides minimal benefit and adds overhead.
             files: package.elfs().into_iter().collect(),
+            dependency_graphs: package
+                .elfs()
+                .into_iter()
+                .filter(|(_, elf)| *elf.kind() == ElfType::Executable)
+                .map(|(path, _)| {
+                    (
+                        path.to_path_buf(),
+                        DependencyGraph::build(path, package, search_config).into_summary(),
+                    )
+                })
+                .collect(),
         })
     }
 }
@@ -0,0 +1,162 @@
+// Copyright (C) 2026 Checkmk GmbH - License: GNU General Public License v2
+// This file is part of Checkmk (https://checkmk.com). It is subject to the terms and
+// conditions defined in the file COPYING, which is part of this source code package.
+
+//! Configurable validation policy: which findings actually fail the process. `validate_report`
+//! applies a flat "any missing/error dependency fails" rule by default, which is too strict
+//! across heterogeneous base images -- a policy lets callers allow-list sonames known to be
+//! provided at runtime, cap how many distinct missing dependencies are tolerated, or downgrade
+//! `DependencyKind::Unknown` dependencies (couldn't be classified as system- or
+//! package-provided) from failures to warnings.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+use super::system_dependencies::SystemDependencies;
+
+/// Resolved validation policy, built from CLI arguments and optionally widened by a policy
+/// file. `DependencyStatus::Error` dependencies are never covered by a policy -- those are
+/// resolution failures (e.g. symlink cycles), not absences a policy can legitimately relax.
+pub struct ValidationPolicy {
+    ignored: SystemDependencies,
+    max_missing_unique: Option<usize>,
+    downgrade_unknown_kind: bool,
+}
+
+impl ValidationPolicy {
+    /// Build a policy directly from resolved CLI values.
+    ///
+    /// # Errors
+    /// Returns an error if any `ignored` entry is not a valid glob pattern.
+    pub fn new(
+        ignored: impl IntoIterator<Item = String>,
+        max_missing_unique: Option<usize>,
+        downgrade_unknown_kind: bool,
+    ) -> Result<Self> {
+        Ok(Self {
+            ignored: SystemDependencies::from_patterns(ignored)
+                .context("Failed to parse --ignore-dependency patterns")?,
+            max_missing_unique,
+            downgrade_unknown_kind,
+        })
+    }
+
+    /// Widen this policy with directives read from a policy file, one per line: `ignore
+    /// <NAME_OR_GLOB>` adds to the ignore-list, `max-missing-unique <N>` tightens the threshold
+    /// (the lower of any value already set and `N`), and the bare directive
+    /// `downgrade-unknown-kind` turns that flag on. Empty lines and lines starting with `#` are
+    /// ignored.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read, or contains an unrecognized directive or an
+    /// invalid `max-missing-unique` count.
+    pub fn merge_from_file<P: AsRef<Path>>(mut self, path: P) -> Result<Self> {
+        let content = fs::read_to_string(path.as_ref())
+            .with_context(|| format!("Failed to read policy file: {}", path.as_ref().display()))?;
+
+        let mut ignored_patterns: Vec<String> = self
+            .ignored
+            .declared_rules()
+            .map(str::to_string)
+            .collect();
+
+        for line in content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        {
+            if let Some(pattern) = line.strip_prefix("ignore ") {
+                ignored_patterns.push(pattern.trim().to_string());
+            } else if let Some(count) = line.strip_prefix("max-missing-unique ") {
+                let count: usize = count.trim().parse().with_context(|| {
+                    format!("Invalid max-missing-unique count in policy file: {line}")
+                })?;
+                self.max_missing_unique =
+                    Some(self.max_missing_unique.map_or(count, |existing| existing.min(count)));
+            } else if line == "downgrade-unknown-kind" {
+                self.downgrade_unknown_kind = true;
+            } else {
+                return Err(anyhow::anyhow!("Unrecognized policy file directive: {line}"));
+            }
+        }
+
+        self.ignored = SystemDependencies::from_patterns(ignored_patterns)
+            .context("Failed to parse policy file ignore patterns")?;
+        Ok(self)
+    }
+
+    /// The ignore-list rule (exact name or glob pattern text) that matches `dependency`, if any.
+    #[must_use]
+    pub(crate) fn ignored_rule(&self, dependency: &str) -> Option<&str> {
+        self.ignored.matching_rule(dependency)
+    }
+
+    /// Whether `DependencyKind::Unknown` dependencies should be downgraded to warnings.
+    #[must_use]
+    pub(crate) fn downgrades_unknown_kind(&self) -> bool {
+        self.downgrade_unknown_kind
+    }
+
+    /// How many distinct missing dependencies (after ignore-list and unknown-kind filtering) are
+    /// tolerated before validation fails. Defaults to 0, i.e. any missing dependency fails.
+    #[must_use]
+    pub(crate) fn max_missing_unique(&self) -> usize {
+        self.max_missing_unique.unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_default_policy_ignores_nothing_and_has_zero_threshold() {
+        let policy = ValidationPolicy::new(Vec::new(), None, false).unwrap();
+        assert_eq!(policy.ignored_rule("libfoo.so"), None);
+        assert_eq!(policy.max_missing_unique(), 0);
+        assert!(!policy.downgrades_unknown_kind());
+    }
+
+    #[test]
+    fn test_cli_ignore_patterns_are_honored() {
+        let policy = ValidationPolicy::new(vec!["libfoo.*".to_string()], Some(3), true).unwrap();
+        assert_eq!(policy.ignored_rule("libfoo.so.1"), Some("libfoo.*"));
+        assert_eq!(policy.max_missing_unique(), 3);
+        assert!(policy.downgrades_unknown_kind());
+    }
+
+    #[test]
+    fn test_merge_from_file_adds_ignore_rules_and_tightens_threshold() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "# comment").unwrap();
+        writeln!(file, "ignore libbar.so.2").unwrap();
+        writeln!(file, "max-missing-unique 2").unwrap();
+        writeln!(file, "downgrade-unknown-kind").unwrap();
+        file.flush().unwrap();
+
+        let policy = ValidationPolicy::new(Vec::new(), Some(5), false)
+            .unwrap()
+            .merge_from_file(file.path())
+            .unwrap();
+
+        assert_eq!(policy.ignored_rule("libbar.so.2"), Some("libbar.so.2"));
+        assert_eq!(policy.max_missing_unique(), 2);
+        assert!(policy.downgrades_unknown_kind());
+    }
+
+    #[test]
+    fn test_merge_from_file_rejects_unrecognized_directive() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "bogus-directive").unwrap();
+        file.flush().unwrap();
+
+        let error = ValidationPolicy::new(Vec::new(), None, false)
+            .unwrap()
+            .merge_from_file(file.path())
+            .unwrap_err();
+        assert!(error.to_string().contains("Unrecognized policy file directive"));
+    }
+}
@@ -0,0 +1,381 @@
+// Copyright (C) 2026 Checkmk GmbH - License: GNU General Public License v2
+// This file is part of Checkmk (https://checkmk.com). It is subject to the terms and
+// conditions defined in the file COPYING, which is part of this source code package.
+
+//! Converts a `Report`'s findings into a SARIF 2.1.0 log, so they can be uploaded directly to
+//! code-scanning dashboards (e.g. GitHub code scanning).
+
+use serde::Serialize;
+use std::path::Path;
+
+use super::errors::ReportError;
+use super::{DependencyStatus, Report};
+
+const SARIF_SCHEMA: &str =
+    "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const TOOL_NAME: &str = "package_validator";
+
+#[derive(Debug, Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifRun {
+    tool: SarifTool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifDriver {
+    name: &'static str,
+    rules: Vec<SarifRule>,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifRule {
+    id: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifResult {
+    #[serde(rename = "ruleId")]
+    rule_id: String,
+    // Every finding this validator reports is a genuine packaging defect (the same class
+    // `validate_report` already fails the run on), so this is always "error" -- there's no
+    // "warning"/"note" tier of finding yet.
+    level: &'static str,
+    message: SarifMessage,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    locations: Vec<SarifLocation>,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifMessage {
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifLocation {
+    #[serde(rename = "physicalLocation")]
+    physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifPhysicalLocation {
+    #[serde(rename = "artifactLocation")]
+    artifact_location: SarifArtifactLocation,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+impl SarifResult {
+    fn new(rule_id: &'static str, message: String, path: &Path) -> Self {
+        Self {
+            rule_id: rule_id.to_string(),
+            level: "error",
+            message: SarifMessage { text: message },
+            locations: vec![SarifLocation {
+                physical_location: SarifPhysicalLocation {
+                    artifact_location: SarifArtifactLocation {
+                        uri: path.to_string_lossy().trim_start_matches('/').to_string(),
+                    },
+                },
+            }],
+        }
+    }
+
+    fn new_without_location(rule_id: &'static str, message: String) -> Self {
+        Self {
+            rule_id: rule_id.to_string(),
+            level: "error",
+            message: SarifMessage { text: message },
+            locations: Vec::new(),
+        }
+    }
+}
+
+/// Build a SARIF 2.1.0 log from a report's findings: every `ReportError`, plus every dependency
+/// resolution that ended in `DependencyStatus::Error` (e.g. a symlink cycle) or
+/// `DependencyStatus::VersionUnsatisfied`, neither of which is otherwise captured as a
+/// `ReportError`. Each `ReportError` result's `ruleId` is the variant's name and its message
+/// reuses that variant's `Display` text; the two dependency statuses use the rule ids
+/// `DependencyResolutionError` and `DependencyVersionUnsatisfied` (the latter listing the unmet
+/// versions). Each result carries a single physical-location artifact for the offending
+/// in-package path, except `UnusedSystemDependency`, which has no location.
+#[must_use]
+pub fn to_sarif(report: &Report<'_>) -> SarifLog {
+    let mut results: Vec<SarifResult> = report.errors.iter().flat_map(results_for_error).collect();
+
+    results.extend(report.dependencies.iter().flat_map(|(elf, dependencies)| {
+        dependencies
+            .iter()
+            .filter_map(move |(dependency, resolved)| match &resolved.status {
+                DependencyStatus::Error(message) => Some(SarifResult::new(
+                    "DependencyResolutionError",
+                    format!("ELF {elf:?}: {dependency}: {message}"),
+                    elf,
+                )),
+                DependencyStatus::VersionUnsatisfied { missing_versions } => Some(SarifResult::new(
+                    "DependencyVersionUnsatisfied",
+                    format!(
+                        "ELF {elf:?}: {dependency} doesn't define the required symbol version(s): {}",
+                        missing_versions.join(", ")
+                    ),
+                    elf,
+                )),
+                DependencyStatus::Found | DependencyStatus::Missing => None,
+            })
+    }));
+
+    let mut rule_ids: Vec<String> = results.iter().map(|result| result.rule_id.clone()).collect();
+    rule_ids.sort_unstable();
+    rule_ids.dedup();
+
+    SarifLog {
+        schema: SARIF_SCHEMA,
+        version: SARIF_VERSION,
+        runs: vec![SarifRun {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: TOOL_NAME,
+                    rules: rule_ids.into_iter().map(|id| SarifRule { id }).collect(),
+                },
+            },
+            results,
+        }],
+    }
+}
+
+fn results_for_error<'a>(error: &'a ReportError<'a>) -> Vec<SarifResult> {
+    let message = error.to_string();
+    match error {
+        ReportError::SystemDependencyFoundInPackage { paths, .. } => paths
+            .iter()
+            .map(|path| SarifResult::new("SystemDependencyFoundInPackage", message.clone(), path))
+            .collect(),
+        ReportError::UnresolvedNeededLibrary { elf, .. } => {
+            vec![SarifResult::new("UnresolvedNeededLibrary", message, elf)]
+        }
+        ReportError::SymbolVersionTooNew { elf, .. } => {
+            vec![SarifResult::new("SymbolVersionTooNew", message, elf)]
+        }
+        // Not tied to any in-package artifact, so it carries no physical location.
+        ReportError::UnusedSystemDependency { .. } => {
+            vec![SarifResult::new_without_location("UnusedSystemDependency", message)]
+        }
+        ReportError::MissingSymbol { elf, .. } => {
+            vec![SarifResult::new("MissingSymbol", message, elf)]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::dependency_resolver::{DependencyKind, DependencyResolverResult};
+    use crate::report::totals::ReportTotals;
+    use std::collections::{BTreeMap, HashMap};
+    use std::path::PathBuf;
+
+    fn test_report<'a>(
+        errors: Vec<ReportError<'a>>,
+        dependencies: BTreeMap<&'a Path, HashMap<&'a str, DependencyResolverResult>>,
+    ) -> Report<'a> {
+        Report {
+            package: "test.deb".to_string(),
+            totals: ReportTotals {
+                files: 0,
+                elfs: Default::default(),
+                dependencies: Default::default(),
+                license: Default::default(),
+                declared_dependencies: Default::default(),
+            },
+            errors,
+            dependencies,
+            files: BTreeMap::new(),
+            dependency_graphs: BTreeMap::new(),
+        }
+    }
+
+    fn dependency_result(status: DependencyStatus) -> DependencyResolverResult {
+        DependencyResolverResult {
+            status,
+            kind: DependencyKind::Unknown,
+            path: None,
+            searched_paths: Vec::new(),
+            source: None,
+        }
+    }
+
+    #[test]
+    fn test_results_are_reported_at_error_level() {
+        let error = ReportError::UnusedSystemDependency {
+            dependency: "libold.so.1",
+        };
+        let sarif = to_sarif(&test_report(vec![error], BTreeMap::new()));
+
+        assert_eq!(sarif.runs[0].results[0].level, "error");
+    }
+
+    #[test]
+    fn test_to_sarif_has_schema_and_version() {
+        let sarif = to_sarif(&test_report(Vec::new(), BTreeMap::new()));
+        assert_eq!(sarif.schema, SARIF_SCHEMA);
+        assert_eq!(sarif.version, SARIF_VERSION);
+        assert_eq!(sarif.runs.len(), 1);
+        assert!(sarif.runs[0].results.is_empty());
+    }
+
+    #[test]
+    fn test_system_dependency_found_in_package_produces_one_result_per_path() {
+        let error = ReportError::SystemDependencyFoundInPackage {
+            dependency: "libm.so.6",
+            paths: vec![Path::new("/usr/lib/libm.so.6"), Path::new("/lib/libm.so.6")],
+        };
+        let sarif = to_sarif(&test_report(vec![error], BTreeMap::new()));
+
+        let results = &sarif.runs[0].results;
+        assert_eq!(results.len(), 2);
+        assert!(results
+            .iter()
+            .all(|result| result.rule_id == "SystemDependencyFoundInPackage"));
+        assert_eq!(
+            results[0].locations[0].physical_location.artifact_location.uri,
+            "usr/lib/libm.so.6"
+        );
+    }
+
+    #[test]
+    fn test_unresolved_needed_library_produces_result_with_elf_location() {
+        let error = ReportError::UnresolvedNeededLibrary {
+            elf: Path::new("/usr/bin/app"),
+            needed: "libfoo.so",
+            searched: vec![PathBuf::from("/usr/lib")],
+        };
+        let sarif = to_sarif(&test_report(vec![error], BTreeMap::new()));
+
+        let result = &sarif.runs[0].results[0];
+        assert_eq!(result.rule_id, "UnresolvedNeededLibrary");
+        assert!(result.message.text.contains("libfoo.so"));
+        assert_eq!(
+            result.locations[0].physical_location.artifact_location.uri,
+            "usr/bin/app"
+        );
+    }
+
+    #[test]
+    fn test_dependency_resolution_error_produces_result() {
+        let mut dependencies = HashMap::new();
+        dependencies.insert(
+            "libbar.so",
+            dependency_result(DependencyStatus::Error("symlink cycle".to_string())),
+        );
+        let mut by_elf = BTreeMap::new();
+        by_elf.insert(Path::new("/usr/bin/app"), dependencies);
+
+        let sarif = to_sarif(&test_report(Vec::new(), by_elf));
+
+        let result = &sarif.runs[0].results[0];
+        assert_eq!(result.rule_id, "DependencyResolutionError");
+        assert!(result.message.text.contains("symlink cycle"));
+    }
+
+    #[test]
+    fn test_version_unsatisfied_dependency_produces_result() {
+        let mut dependencies = HashMap::new();
+        dependencies.insert(
+            "libc.so.6",
+            dependency_result(DependencyStatus::VersionUnsatisfied {
+                missing_versions: vec!["GLIBC_2.34".to_string()],
+            }),
+        );
+        let mut by_elf = BTreeMap::new();
+        by_elf.insert(Path::new("/usr/bin/app"), dependencies);
+
+        let sarif = to_sarif(&test_report(Vec::new(), by_elf));
+
+        let result = &sarif.runs[0].results[0];
+        assert_eq!(result.rule_id, "DependencyVersionUnsatisfied");
+        assert!(result.message.text.contains("GLIBC_2.34"));
+        assert_eq!(
+            result.locations[0].physical_location.artifact_location.uri,
+            "usr/bin/app"
+        );
+    }
+
+    #[test]
+    fn test_found_and_missing_dependencies_produce_no_results() {
+        let mut dependencies = HashMap::new();
+        dependencies.insert("libbar.so", dependency_result(DependencyStatus::Found));
+        dependencies.insert("libbaz.so", dependency_result(DependencyStatus::Missing));
+        let mut by_elf = BTreeMap::new();
+        by_elf.insert(Path::new("/usr/bin/app"), dependencies);
+
+        let sarif = to_sarif(&test_report(Vec::new(), by_elf));
+
+        assert!(sarif.runs[0].results.is_empty());
+    }
+
+    #[test]
+    fn test_unused_system_dependency_has_no_location() {
+        let error = ReportError::UnusedSystemDependency {
+            dependency: "libold.so.1",
+        };
+        let sarif = to_sarif(&test_report(vec![error], BTreeMap::new()));
+
+        let result = &sarif.runs[0].results[0];
+        assert_eq!(result.rule_id, "UnusedSystemDependency");
+        assert!(result.locations.is_empty());
+    }
+
+    #[test]
+    fn test_missing_symbol_produces_result_with_elf_location() {
+        let error = ReportError::MissingSymbol {
+            elf: Path::new("/usr/bin/app"),
+            symbol: "needed_symbol",
+        };
+        let sarif = to_sarif(&test_report(vec![error], BTreeMap::new()));
+
+        let result = &sarif.runs[0].results[0];
+        assert_eq!(result.rule_id, "MissingSymbol");
+        assert!(result.message.text.contains("needed_symbol"));
+        assert_eq!(
+            result.locations[0].physical_location.artifact_location.uri,
+            "usr/bin/app"
+        );
+    }
+
+    #[test]
+    fn test_rules_are_deduplicated_across_results() {
+        let errors = vec![
+            ReportError::UnresolvedNeededLibrary {
+                elf: Path::new("/usr/bin/a"),
+                needed: "libfoo.so",
+                searched: Vec::new(),
+            },
+            ReportError::UnresolvedNeededLibrary {
+                elf: Path::new("/usr/bin/b"),
+                needed: "libbar.so",
+                searched: Vec::new(),
+            },
+        ];
+        let sarif = to_sarif(&test_report(errors, BTreeMap::new()));
+
+        let rules = &sarif.runs[0].tool.driver.rules;
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].id, "UnresolvedNeededLibrary");
+    }
+}
@@ -0,0 +1,275 @@
+// Copyright (C) 2026 Checkmk GmbH - License: GNU General Public License v2
+// This file is part of Checkmk (https://checkmk.com). It is subject to the terms and
+// conditions defined in the file COPYING, which is part of this source code package.
+
+//! Emulates the dynamic linker's library search-path configuration outside of `DT_RPATH`/
+//! `DT_RUNPATH` themselves: an `LD_LIBRARY_PATH` list, `/etc/ld.so.conf` (recursively following
+//! its `include` directives), and the trusted default directories. The `ld.so.conf` directories
+//! and the trusted defaults are resolved under an injectable `sysroot`, so a staged install tree
+//! or container rootfs can be analyzed instead of the host running `package_validator`, which is
+//! essential for reproducible QA. `LD_LIBRARY_PATH` entries are used exactly as given.
+
+use globset::Glob;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Library directories the dynamic linker trusts by default once `ld.so.conf` is exhausted.
+const TRUSTED_DEFAULT_DIRS: &[&str] = &["/lib", "/lib64", "/usr/lib", "/usr/lib64"];
+
+/// Which rule contributed a search directory, in the dynamic linker's own order of precedence,
+/// so a resolved dependency can be explained: why this `.so` and not another copy shipped
+/// elsewhere in the package or sysroot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum SearchPathSource {
+    /// `DT_RPATH`, only consulted when the object has no `DT_RUNPATH`.
+    Rpath,
+    /// An emulated `LD_LIBRARY_PATH` entry.
+    LdLibraryPath,
+    /// `DT_RUNPATH`.
+    Runpath,
+    /// A directory listed in (or included by) `/etc/ld.so.conf`.
+    LdSoConf,
+    /// One of the dynamic linker's built-in trusted directories (`/lib`, `/usr/lib`, etc.).
+    TrustedDefault,
+    /// A `lib`/`bin` directory `package_validator` itself discovered under the package's common
+    /// install prefix; not part of the real loader's search order, but needed for packages
+    /// installed outside the trusted defaults (e.g. under `/opt/<product>`).
+    PackageInstallPrefix,
+}
+
+/// A single search directory, tagged with the rule that contributed it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SearchPath {
+    pub(crate) path: PathBuf,
+    pub(crate) source: SearchPathSource,
+}
+
+/// Library search-path configuration, mirroring the dynamic linker's own precedence.
+/// `dependency_resolver` combines this with each ELF's own `DT_RPATH`/`DT_RUNPATH` to produce
+/// the full search order: RPATH (no RUNPATH present), `LD_LIBRARY_PATH`, RUNPATH, `ld.so.conf`,
+/// trusted defaults.
+#[derive(Debug, Clone)]
+pub struct SearchConfig {
+    sysroot: PathBuf,
+    ld_library_path: Vec<PathBuf>,
+}
+
+impl SearchConfig {
+    /// Build a config rooted at `sysroot`: every directory this config produces (`ld.so.conf`
+    /// contents, trusted defaults) is resolved under it rather than the real root, so analysis
+    /// can target a staged install tree or container rootfs. `ld_library_path` emulates the
+    /// environment variable of the same name.
+    #[must_use]
+    pub fn new(sysroot: impl Into<PathBuf>, ld_library_path: Vec<PathBuf>) -> Self {
+        Self {
+            sysroot: sysroot.into(),
+            ld_library_path,
+        }
+    }
+
+    /// A config analyzing the host's own root filesystem, with no emulated `LD_LIBRARY_PATH`.
+    #[must_use]
+    pub fn host() -> Self {
+        Self::new("/", Vec::new())
+    }
+
+    /// Resolve a path that's meaningful on the target root (e.g. `/etc/ld.so.conf`) against
+    /// this config's sysroot.
+    fn resolve(&self, path: &Path) -> PathBuf {
+        match path.strip_prefix("/") {
+            Ok(relative) => self.sysroot.join(relative),
+            Err(_) => self.sysroot.join(path),
+        }
+    }
+
+    /// The emulated `LD_LIBRARY_PATH` list, each tagged `SearchPathSource::LdLibraryPath`.
+    pub(crate) fn ld_library_path(&self) -> impl Iterator<Item = SearchPath> + '_ {
+        self.ld_library_path.iter().cloned().map(|path| SearchPath {
+            path,
+            source: SearchPathSource::LdLibraryPath,
+        })
+    }
+
+    /// Parse `/etc/ld.so.conf` under this config's sysroot, recursively following `include`
+    /// directives, and return every directory it lists, tagged `SearchPathSource::LdSoConf`.
+    ///
+    /// `ldconfig` normally precompiles these into a binary `/etc/ld.so.cache`, but that cache is
+    /// entirely derived from `ld.so.conf` and may be stale (or never generated at all) in a
+    /// staged install tree or container rootfs that hasn't run `ldconfig`, so this always
+    /// regenerates the directory list from the source config rather than parsing the cache's
+    /// binary format.
+    #[must_use]
+    pub(crate) fn ld_so_conf_dirs(&self) -> Vec<SearchPath> {
+        let mut dirs = Vec::new();
+        let mut seen_files = HashSet::new();
+        self.parse_ld_so_conf_file(&self.resolve(Path::new("/etc/ld.so.conf")), &mut dirs, &mut seen_files);
+        dirs
+    }
+
+    /// Parse one `ld.so.conf`-format file, appending directories to `dirs` and recursing into
+    /// any `include` directive. `seen_files` guards against include cycles. Missing or
+    /// unreadable files contribute nothing, since not every rootfs ships `ld.so.conf`.
+    fn parse_ld_so_conf_file(
+        &self,
+        path: &Path,
+        dirs: &mut Vec<SearchPath>,
+        seen_files: &mut HashSet<PathBuf>,
+    ) {
+        let Ok(canonical) = path.canonicalize() else {
+            return;
+        };
+        if !seen_files.insert(canonical) {
+            return;
+        }
+        let Ok(content) = fs::read_to_string(path) else {
+            return;
+        };
+
+        for line in content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        {
+            if let Some(pattern) = line.strip_prefix("include") {
+                for included in self.expand_include(pattern.trim()) {
+                    self.parse_ld_so_conf_file(&included, dirs, seen_files);
+                }
+            } else {
+                dirs.push(SearchPath {
+                    path: self.resolve(Path::new(line)),
+                    source: SearchPathSource::LdSoConf,
+                });
+            }
+        }
+    }
+
+    /// Expand an `include <pattern>` directive's pattern into the (sysroot-resolved) files it
+    /// matches, sorted for deterministic output. A relative pattern (the common case, e.g.
+    /// `ld.so.conf.d/*.conf`) is resolved against `/etc`, matching glibc's own behavior.
+    fn expand_include(&self, pattern: &str) -> Vec<PathBuf> {
+        let pattern_path = if Path::new(pattern).is_absolute() {
+            self.resolve(Path::new(pattern))
+        } else {
+            self.resolve(Path::new("/etc")).join(pattern)
+        };
+        let Some(parent) = pattern_path.parent() else {
+            return Vec::new();
+        };
+        let Some(file_pattern) = pattern_path.file_name().and_then(|name| name.to_str()) else {
+            return Vec::new();
+        };
+        let Ok(glob) = Glob::new(file_pattern) else {
+            return Vec::new();
+        };
+        let matcher = glob.compile_matcher();
+        let Ok(entries) = fs::read_dir(parent) else {
+            return Vec::new();
+        };
+
+        let mut matches: Vec<PathBuf> = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| matcher.is_match(name))
+            })
+            .collect();
+        matches.sort_unstable();
+        matches
+    }
+
+    /// The trusted default library directories, tagged `SearchPathSource::TrustedDefault`.
+    #[must_use]
+    pub(crate) fn trusted_defaults(&self) -> Vec<SearchPath> {
+        TRUSTED_DEFAULT_DIRS
+            .iter()
+            .map(|dir| SearchPath {
+                path: self.resolve(Path::new(dir)),
+                source: SearchPathSource::TrustedDefault,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_host_config_has_no_ld_library_path() {
+        let config = SearchConfig::host();
+        assert_eq!(config.ld_library_path().count(), 0);
+    }
+
+    #[test]
+    fn test_ld_library_path_tagged_correctly() {
+        let config = SearchConfig::new("/", vec![PathBuf::from("/opt/app/lib")]);
+        let paths: Vec<SearchPath> = config.ld_library_path().collect();
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].path, PathBuf::from("/opt/app/lib"));
+        assert_eq!(paths[0].source, SearchPathSource::LdLibraryPath);
+    }
+
+    #[test]
+    fn test_trusted_defaults_resolved_under_sysroot() {
+        let config = SearchConfig::new("/sysroot", Vec::new());
+        let dirs = config.trusted_defaults();
+        assert!(dirs.iter().any(|d| d.path == PathBuf::from("/sysroot/lib")));
+        assert!(dirs.iter().all(|d| d.source == SearchPathSource::TrustedDefault));
+    }
+
+    #[test]
+    fn test_ld_so_conf_missing_file_contributes_nothing() {
+        let sysroot = TempDir::new().unwrap();
+        let config = SearchConfig::new(sysroot.path(), Vec::new());
+        assert!(config.ld_so_conf_dirs().is_empty());
+    }
+
+    #[test]
+    fn test_ld_so_conf_parses_plain_directories() {
+        let sysroot = TempDir::new().unwrap();
+        fs::create_dir_all(sysroot.path().join("etc")).unwrap();
+        fs::write(
+            sysroot.path().join("etc/ld.so.conf"),
+            "# a comment\n/opt/app/lib\n\n/opt/app/lib64\n",
+        )
+        .unwrap();
+
+        let config = SearchConfig::new(sysroot.path(), Vec::new());
+        let dirs = config.ld_so_conf_dirs();
+        assert_eq!(dirs.len(), 2);
+        assert!(dirs.iter().all(|d| d.source == SearchPathSource::LdSoConf));
+        assert!(dirs.iter().any(|d| d.path == sysroot.path().join("opt/app/lib")));
+        assert!(dirs.iter().any(|d| d.path == sysroot.path().join("opt/app/lib64")));
+    }
+
+    #[test]
+    fn test_ld_so_conf_follows_include_globs() {
+        let sysroot = TempDir::new().unwrap();
+        let etc = sysroot.path().join("etc");
+        fs::create_dir_all(etc.join("ld.so.conf.d")).unwrap();
+        fs::write(etc.join("ld.so.conf"), "include ld.so.conf.d/*.conf\n").unwrap();
+        fs::write(etc.join("ld.so.conf.d/app.conf"), "/opt/app/lib\n").unwrap();
+        fs::write(etc.join("ld.so.conf.d/readme"), "/should/not/be/included\n").unwrap();
+
+        let config = SearchConfig::new(sysroot.path(), Vec::new());
+        let dirs = config.ld_so_conf_dirs();
+        assert_eq!(dirs.len(), 1);
+        assert_eq!(dirs[0].path, sysroot.path().join("opt/app/lib"));
+    }
+
+    #[test]
+    fn test_ld_so_conf_include_cycle_does_not_loop_forever() {
+        let sysroot = TempDir::new().unwrap();
+        fs::create_dir_all(sysroot.path().join("etc")).unwrap();
+        fs::write(sysroot.path().join("etc/ld.so.conf"), "include ld.so.conf\n").unwrap();
+
+        let config = SearchConfig::new(sysroot.path(), Vec::new());
+        assert!(config.ld_so_conf_dirs().is_empty());
+    }
+}
@@ -0,0 +1,229 @@
+// Copyright (C) 2026 Checkmk GmbH - License: GNU General Public License v2
+// This file is part of Checkmk (https://checkmk.com). It is subject to the terms and
+// conditions defined in the file COPYING, which is part of this source code package.
+
+//! Parses and compares dotted-decimal symbol versions (e.g. `GLIBC_2.27`, as required by an
+//! ELF's `.gnu.version_r` section) against a configurable baseline, so packages that need a
+//! newer libc/libstdc++ than a target distribution ships can be caught before deployment.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::num::ParseIntError;
+
+/// A symbol version baseline such as `2.17` (glibc 2.17, as shipped by RHEL/CentOS 7),
+/// parsed once from CLI input and compared against the versions ELF files require.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionBaseline {
+    raw: String,
+    components: Vec<u32>,
+}
+
+impl VersionBaseline {
+    /// Parse a dotted-decimal version string like `"2.17"` into a baseline.
+    ///
+    /// # Errors
+    /// Returns an error if any component is not a valid non-negative integer.
+    pub fn parse(raw: &str) -> Result<Self, ParseIntError> {
+        Ok(Self {
+            raw: raw.to_string(),
+            components: parse_components(raw)?,
+        })
+    }
+
+    /// Whether `components` is strictly newer than this baseline.
+    fn is_exceeded_by(&self, components: &[u32]) -> bool {
+        compare_components(components, &self.components) == Ordering::Greater
+    }
+}
+
+impl fmt::Display for VersionBaseline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+/// Parse a dotted-decimal version string like `"2.27"` into its numeric components.
+fn parse_components(version: &str) -> Result<Vec<u32>, ParseIntError> {
+    version.split('.').map(str::parse).collect()
+}
+
+/// Compare two dotted-decimal version component lists, treating a missing trailing component
+/// as zero, so `[2, 27]` > `[2, 9]` and `[2, 17]` == `[2, 17, 0]`.
+fn compare_components(a: &[u32], b: &[u32]) -> Ordering {
+    (0..a.len().max(b.len()))
+        .map(|i| a.get(i).copied().unwrap_or(0).cmp(&b.get(i).copied().unwrap_or(0)))
+        .find(|ordering| *ordering != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Split a verneed symbol version like `"GLIBC_2.27"` into its provider (`"GLIBC"`) and
+/// dotted-decimal version tail (`"2.27"`).
+fn split_provider_version(symbol: &str) -> Option<(&str, &str)> {
+    symbol.split_once('_')
+}
+
+/// For each provider referenced in `requirements` (e.g. `GLIBC`, `GLIBCXX`), find the
+/// requirement with the highest version and return its provider, raw symbol (e.g.
+/// `"GLIBC_2.27"`), and parsed version components. Requirements that aren't of the
+/// `PROVIDER_x.y.z` form are ignored.
+fn highest_required_versions(requirements: &[String]) -> Vec<(&str, &str, Vec<u32>)> {
+    let mut highest: HashMap<&str, (&str, Vec<u32>)> = HashMap::new();
+    for requirement in requirements {
+        let Some((provider, version)) = split_provider_version(requirement) else {
+            continue;
+        };
+        let Ok(components) = parse_components(version) else {
+            continue;
+        };
+        let replace = highest
+            .get(provider)
+            .is_none_or(|(_, current)| compare_components(&components, current) == Ordering::Greater);
+        if replace {
+            highest.insert(provider, (requirement.as_str(), components));
+        }
+    }
+    highest
+        .into_iter()
+        .map(|(provider, (symbol, components))| (provider, symbol, components))
+        .collect()
+}
+
+/// Find every requirement in `requirements` whose provider's highest required version exceeds
+/// `baseline`, returning the offending raw symbol (e.g. `"GLIBC_2.27"`) for each.
+pub(crate) fn exceeding_requirements<'a>(
+    requirements: &'a [String],
+    baseline: &VersionBaseline,
+) -> Vec<&'a str> {
+    highest_required_versions(requirements)
+        .into_iter()
+        .filter(|(_, _, components)| baseline.is_exceeded_by(components))
+        .map(|(_, symbol, _)| symbol)
+        .collect()
+}
+
+/// Like `exceeding_requirements`, but looks up the baseline to compare against per provider: a
+/// provider with an entry in `baselines` (e.g. a `GLIBCXX <= 3.4.19` line in the system
+/// dependencies file) uses that baseline, otherwise `default_baseline` applies. A provider with
+/// neither is never flagged. Returns, for each offending requirement, its raw symbol and the
+/// baseline that was exceeded (for error reporting).
+pub(crate) fn exceeding_requirements_per_provider<'a>(
+    requirements: &'a [String],
+    baselines: &HashMap<String, VersionBaseline>,
+    default_baseline: Option<&VersionBaseline>,
+) -> Vec<(&'a str, String)> {
+    highest_required_versions(requirements)
+        .into_iter()
+        .filter_map(|(provider, symbol, components)| {
+            let baseline = baselines.get(provider).or(default_baseline)?;
+            baseline.is_exceeded_by(&components).then(|| (symbol, baseline.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_baseline() {
+        let baseline = VersionBaseline::parse("2.17").unwrap();
+        assert_eq!(baseline.to_string(), "2.17");
+    }
+
+    #[test]
+    fn test_parse_baseline_invalid_component_errors() {
+        assert!(VersionBaseline::parse("2.x").is_err());
+    }
+
+    #[test]
+    fn test_compare_components_missing_trailing_treated_as_zero() {
+        assert_eq!(compare_components(&[2, 17], &[2, 17, 0]), Ordering::Equal);
+        assert_eq!(compare_components(&[2, 17, 0], &[2, 17]), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_compare_components_orders_numerically_not_lexically() {
+        // 9 < 27 numerically, even though "9" > "27" lexically.
+        assert_eq!(compare_components(&[2, 9], &[2, 27]), Ordering::Less);
+    }
+
+    #[test]
+    fn test_split_provider_version() {
+        assert_eq!(split_provider_version("GLIBC_2.27"), Some(("GLIBC", "2.27")));
+        assert_eq!(
+            split_provider_version("GLIBCXX_3.4.25"),
+            Some(("GLIBCXX", "3.4.25"))
+        );
+        assert_eq!(split_provider_version("not_versioned_at_all"), Some(("not", "versioned_at_all")));
+        assert_eq!(split_provider_version("NOUNDERSCORE"), None);
+    }
+
+    #[test]
+    fn test_exceeding_requirements_none_within_baseline() {
+        let requirements = vec!["GLIBC_2.5".to_string(), "GLIBC_2.17".to_string()];
+        let baseline = VersionBaseline::parse("2.17").unwrap();
+        assert!(exceeding_requirements(&requirements, &baseline).is_empty());
+    }
+
+    #[test]
+    fn test_exceeding_requirements_reports_highest_per_provider() {
+        let requirements = vec![
+            "GLIBC_2.2.5".to_string(),
+            "GLIBC_2.27".to_string(),
+            "GLIBCXX_3.4.25".to_string(),
+        ];
+        let baseline = VersionBaseline::parse("2.17").unwrap();
+        let mut exceeding = exceeding_requirements(&requirements, &baseline);
+        exceeding.sort_unstable();
+        // Only the highest requirement of each provider is reported (GLIBC_2.27, not
+        // GLIBC_2.2.5); GLIBCXX_3.4.25 is compared against the same 2.17 baseline and exceeds it.
+        assert_eq!(exceeding, vec!["GLIBCXX_3.4.25", "GLIBC_2.27"]);
+    }
+
+    #[test]
+    fn test_exceeding_requirements_empty_when_no_verneed() {
+        let baseline = VersionBaseline::parse("2.17").unwrap();
+        assert!(exceeding_requirements(&[], &baseline).is_empty());
+    }
+
+    #[test]
+    fn test_exceeding_requirements_ignores_unversioned_symbols() {
+        let requirements = vec!["NOUNDERSCORE".to_string()];
+        let baseline = VersionBaseline::parse("2.17").unwrap();
+        assert!(exceeding_requirements(&requirements, &baseline).is_empty());
+    }
+
+    #[test]
+    fn test_exceeding_requirements_per_provider_uses_matching_override() {
+        let requirements = vec!["GLIBC_2.27".to_string(), "GLIBCXX_3.4.20".to_string()];
+        let baselines = HashMap::from([
+            ("GLIBC".to_string(), VersionBaseline::parse("2.17").unwrap()),
+            ("GLIBCXX".to_string(), VersionBaseline::parse("3.4.19").unwrap()),
+        ]);
+        let exceeding = exceeding_requirements_per_provider(&requirements, &baselines, None);
+        let mut symbols: Vec<&str> = exceeding.iter().map(|(symbol, _)| *symbol).collect();
+        symbols.sort_unstable();
+        assert_eq!(symbols, vec!["GLIBCXX_3.4.20", "GLIBC_2.27"]);
+    }
+
+    #[test]
+    fn test_exceeding_requirements_per_provider_falls_back_to_default() {
+        let requirements = vec!["GLIBC_2.27".to_string()];
+        let baselines = HashMap::from([(
+            "GLIBCXX".to_string(),
+            VersionBaseline::parse("3.4.19").unwrap(),
+        )]);
+        let default_baseline = VersionBaseline::parse("2.17").unwrap();
+        let exceeding =
+            exceeding_requirements_per_provider(&requirements, &baselines, Some(&default_baseline));
+        assert_eq!(exceeding, vec![("GLIBC_2.27", "2.17".to_string())]);
+    }
+
+    #[test]
+    fn test_exceeding_requirements_per_provider_skips_provider_without_any_baseline() {
+        let requirements = vec!["GLIBC_99.99".to_string()];
+        let baselines = HashMap::new();
+        assert!(exceeding_requirements_per_provider(&requirements, &baselines, None).is_empty());
+    }
+}
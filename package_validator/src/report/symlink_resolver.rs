@@ -7,14 +7,34 @@
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
-use crate::package::{Package, PackageFiles, PackageSymlinks};
+use crate::package::{Package, PackageFiles, PackageSymlinks, SymlinkTarget};
 
 pub(crate) type SymlinkResolutionResults<'a> = HashMap<&'a Path, SymlinkResolutionResult<'a>>;
 
 pub(crate) enum SymlinkResolutionResult<'a> {
-    NotFound(PathBuf), // The symlink was not found in the package, likely a system dependency.
-    Found(&'a Path),   // The symlink was found in the package.
-    CycleDetected(),   // The symlink points to itself or a cycle was detected.
+    /// Absolute target not present in the package, and no sibling file exists under
+    /// the same directory either -- looks like a genuine system library path.
+    SystemDependency {
+        target: PathBuf,
+        hops: Vec<&'a Path>,
+    },
+    /// Target not present in the package, but other files exist alongside where it
+    /// would have been -- likely a broken/incomplete package rather than a real
+    /// external path.
+    DanglingInPackage {
+        target: PathBuf,
+        hops: Vec<&'a Path>,
+    },
+    /// The symlink was found in the package.
+    Found { target: &'a Path, hops: Vec<&'a Path> },
+    /// The symlink (or a link further down its chain) points to itself, forming a cycle.
+    CycleDetected { hops: Vec<&'a Path> },
+    /// A relative target's `..` components climbed above the package root before
+    /// `PathClean` silently clamped them back down to `/` -- a path-traversal artifact.
+    EscapesRoot {
+        raw_target: PathBuf,
+        hops: Vec<&'a Path>,
+    },
 }
 
 pub(crate) struct SymlinkResolver<'a> {
@@ -40,14 +60,16 @@ impl<'a> SymlinkResolver<'a> {
     ) -> SymlinkResolutionResults<'a> {
         symlinks
             .iter()
-            .map(|(symlink_path, target_path)| {
+            .map(|(symlink_path, target)| {
                 let mut visited = HashSet::<&'a Path>::new();
+                let mut hops = Vec::<&'a Path>::new();
                 let result = Self::resolve_single_symlink(
                     symlink_path,
-                    target_path,
+                    target,
                     files,
                     symlinks,
                     &mut visited,
+                    &mut hops,
                 );
                 (*symlink_path, result)
             })
@@ -56,25 +78,56 @@ impl<'a> SymlinkResolver<'a> {
 
     fn resolve_single_symlink(
         current_path: &'a Path,
-        target_path: &'a Path,
+        target: &'a SymlinkTarget,
         files: &'a PackageFiles,
         symlinks: &PackageSymlinks<'a>,
         visited: &mut HashSet<&'a Path>,
+        hops: &mut Vec<&'a Path>,
     ) -> SymlinkResolutionResult<'a> {
         if visited.contains(current_path) {
-            return SymlinkResolutionResult::CycleDetected();
+            return SymlinkResolutionResult::CycleDetected { hops: hops.clone() };
         }
         visited.insert(current_path);
+        hops.push(current_path);
 
+        if target.escapes_root() {
+            return SymlinkResolutionResult::EscapesRoot {
+                raw_target: target.raw().to_path_buf(),
+                hops: hops.clone(),
+            };
+        }
+
+        let target_path = target.as_path();
         if !files.contains_key(target_path) {
-            // Target not found in package, likely a system dependency
-            SymlinkResolutionResult::NotFound(target_path.to_path_buf())
+            Self::classify_not_found(target_path, files, hops.clone())
         } else if let Some(next_target) = symlinks.get(target_path) {
             // Target is a symlink, recursively resolve
-            Self::resolve_single_symlink(target_path, next_target, files, symlinks, visited)
+            Self::resolve_single_symlink(target_path, next_target, files, symlinks, visited, hops)
         } else {
             // Target is not a symlink, we've found the final target
-            SymlinkResolutionResult::Found(target_path)
+            SymlinkResolutionResult::Found {
+                target: target_path,
+                hops: hops.clone(),
+            }
+        }
+    }
+
+    /// Classify a target that isn't present in the package: if a sibling file exists
+    /// under the same directory, the package looks incomplete rather than the target
+    /// being a genuine system path.
+    fn classify_not_found(
+        target_path: &Path,
+        files: &'a PackageFiles,
+        hops: Vec<&'a Path>,
+    ) -> SymlinkResolutionResult<'a> {
+        let has_sibling_in_package = target_path
+            .parent()
+            .is_some_and(|parent| files.keys().any(|path| path.starts_with(parent)));
+        let target = target_path.to_path_buf();
+        if has_sibling_in_package {
+            SymlinkResolutionResult::DanglingInPackage { target, hops }
+        } else {
+            SymlinkResolutionResult::SystemDependency { target, hops }
         }
     }
 
@@ -95,6 +148,10 @@ mod tests {
         Package::new_for_testing(PathBuf::from("/test/package.deb"), files)
     }
 
+    fn symlink(target: PathBuf) -> PackageFile {
+        PackageFile::Symlink(SymlinkTarget::new_for_testing(target))
+    }
+
     #[test]
     fn test_simple_symlink_resolution() {
         // A -> /usr/bin/file
@@ -102,10 +159,7 @@ mod tests {
         let file_path = PathBuf::from("/usr/bin/file");
         let symlink_path = PathBuf::from("/usr/bin/A");
         files.insert(file_path.clone(), PackageFile::File);
-        files.insert(
-            symlink_path.clone(),
-            PackageFile::Symlink(file_path.clone()),
-        );
+        files.insert(symlink_path.clone(), symlink(file_path.clone()));
 
         let package = create_test_package(files);
         let resolver = SymlinkResolver::new(&package);
@@ -113,7 +167,7 @@ mod tests {
 
         assert_eq!(results.len(), 1);
         match results.get(symlink_path.as_path()) {
-            Some(SymlinkResolutionResult::Found(target)) => {
+            Some(SymlinkResolutionResult::Found { target, .. }) => {
                 assert_eq!(*target, file_path.as_path());
             }
             _ => panic!("Expected Found result"),
@@ -129,30 +183,28 @@ mod tests {
         let symlink_a_path = PathBuf::from("/usr/bin/A");
 
         files.insert(file_path.clone(), PackageFile::File);
-        files.insert(
-            symlink_b_path.clone(),
-            PackageFile::Symlink(file_path.clone()),
-        );
-        files.insert(
-            symlink_a_path.clone(),
-            PackageFile::Symlink(symlink_b_path.clone()),
-        );
+        files.insert(symlink_b_path.clone(), symlink(file_path.clone()));
+        files.insert(symlink_a_path.clone(), symlink(symlink_b_path.clone()));
 
         let package = create_test_package(files);
         let resolver = SymlinkResolver::new(&package);
         let results = resolver.symlinks();
 
         assert_eq!(results.len(), 2);
-        // Check that A resolves to file
+        // Check that A resolves to file, with the full hop chain A -> B -> file
         match results.get(symlink_a_path.as_path()) {
-            Some(SymlinkResolutionResult::Found(target)) => {
+            Some(SymlinkResolutionResult::Found { target, hops }) => {
                 assert_eq!(*target, file_path.as_path());
+                assert_eq!(
+                    hops,
+                    &vec![symlink_a_path.as_path(), symlink_b_path.as_path()]
+                );
             }
             _ => panic!("Expected Found result for A"),
         }
         // Check that B resolves to file
         match results.get(symlink_b_path.as_path()) {
-            Some(SymlinkResolutionResult::Found(target)) => {
+            Some(SymlinkResolutionResult::Found { target, .. }) => {
                 assert_eq!(*target, file_path.as_path());
             }
             _ => panic!("Expected Found result for B"),
@@ -164,10 +216,7 @@ mod tests {
         // A -> A (self-reference)
         let mut files = HashMap::new();
         let symlink_path = PathBuf::from("/usr/bin/A");
-        files.insert(
-            symlink_path.clone(),
-            PackageFile::Symlink(symlink_path.clone()),
-        );
+        files.insert(symlink_path.clone(), symlink(symlink_path.clone()));
 
         let package = create_test_package(files);
         let resolver = SymlinkResolver::new(&package);
@@ -175,7 +224,7 @@ mod tests {
 
         assert_eq!(results.len(), 1);
         match results.get(symlink_path.as_path()) {
-            Some(SymlinkResolutionResult::CycleDetected()) => {}
+            Some(SymlinkResolutionResult::CycleDetected { .. }) => {}
             _ => panic!("Expected CycleDetected result"),
         }
     }
@@ -187,14 +236,8 @@ mod tests {
         let symlink_a_path = PathBuf::from("/usr/bin/A");
         let symlink_b_path = PathBuf::from("/usr/bin/B");
 
-        files.insert(
-            symlink_a_path.clone(),
-            PackageFile::Symlink(symlink_b_path.clone()),
-        );
-        files.insert(
-            symlink_b_path.clone(),
-            PackageFile::Symlink(symlink_a_path.clone()),
-        );
+        files.insert(symlink_a_path.clone(), symlink(symlink_b_path.clone()));
+        files.insert(symlink_b_path.clone(), symlink(symlink_a_path.clone()));
 
         let package = create_test_package(files);
         let resolver = SymlinkResolver::new(&package);
@@ -203,37 +246,87 @@ mod tests {
         assert_eq!(results.len(), 2);
         // Both should detect cycles
         match results.get(symlink_a_path.as_path()) {
-            Some(SymlinkResolutionResult::CycleDetected()) => {}
+            Some(SymlinkResolutionResult::CycleDetected { .. }) => {}
             _ => panic!("Expected CycleDetected result for A"),
         }
         match results.get(symlink_b_path.as_path()) {
-            Some(SymlinkResolutionResult::CycleDetected()) => {}
+            Some(SymlinkResolutionResult::CycleDetected { .. }) => {}
             _ => panic!("Expected CycleDetected result for B"),
         }
     }
 
     #[test]
-    fn test_not_found() {
-        // Symlink pointing to /usr/lib/missing.so (not in package)
+    fn test_not_found_classified_as_system_dependency() {
+        // Symlink pointing to /usr/lib/missing.so, with no sibling in the package.
         let mut files = HashMap::new();
         let symlink_path = PathBuf::from("/usr/bin/A");
         let missing_target = PathBuf::from("/usr/lib/missing.so");
 
+        files.insert(symlink_path.clone(), symlink(missing_target.clone()));
+
+        let package = create_test_package(files);
+        let resolver = SymlinkResolver::new(&package);
+        let results = resolver.symlinks();
+
+        assert_eq!(results.len(), 1);
+        match results.get(symlink_path.as_path()) {
+            Some(SymlinkResolutionResult::SystemDependency { target, .. }) => {
+                assert_eq!(target, &missing_target);
+            }
+            _ => panic!("Expected SystemDependency result"),
+        }
+    }
+
+    #[test]
+    fn test_not_found_classified_as_dangling_in_package() {
+        // Symlink pointing to /usr/lib/missing.so, with a sibling file present under
+        // the same directory -- looks like an incomplete package rather than a
+        // genuine external dependency.
+        let mut files = HashMap::new();
+        let symlink_path = PathBuf::from("/usr/lib/A");
+        let missing_target = PathBuf::from("/usr/lib/missing.so");
+        let sibling_path = PathBuf::from("/usr/lib/present.so");
+
+        files.insert(sibling_path, PackageFile::File);
+        files.insert(symlink_path.clone(), symlink(missing_target.clone()));
+
+        let package = create_test_package(files);
+        let resolver = SymlinkResolver::new(&package);
+        let results = resolver.symlinks();
+
+        match results.get(symlink_path.as_path()) {
+            Some(SymlinkResolutionResult::DanglingInPackage { target, .. }) => {
+                assert_eq!(target, &missing_target);
+            }
+            _ => panic!("Expected DanglingInPackage result"),
+        }
+    }
+
+    #[test]
+    fn test_escapes_root_relative_symlink() {
+        // Symlink at /usr/bin/A -> ../../../../etc/passwd climbs four levels, but
+        // the parent (/usr/bin) only has depth two, so it escapes the root by two.
+        let mut files = HashMap::new();
+        let symlink_path = PathBuf::from("/usr/bin/A");
+        let raw_target = PathBuf::from("../../../../etc/passwd");
         files.insert(
             symlink_path.clone(),
-            PackageFile::Symlink(missing_target.clone()),
+            PackageFile::Symlink(SymlinkTarget::new(
+                PathBuf::from("/etc/passwd"),
+                raw_target.clone(),
+                2,
+            )),
         );
 
         let package = create_test_package(files);
         let resolver = SymlinkResolver::new(&package);
         let results = resolver.symlinks();
 
-        assert_eq!(results.len(), 1);
         match results.get(symlink_path.as_path()) {
-            Some(SymlinkResolutionResult::NotFound(target)) => {
-                assert_eq!(target, &missing_target);
+            Some(SymlinkResolutionResult::EscapesRoot { raw_target: rt, .. }) => {
+                assert_eq!(rt, &raw_target);
             }
-            _ => panic!("Expected NotFound result"),
+            _ => panic!("Expected EscapesRoot result"),
         }
     }
 
@@ -249,14 +342,8 @@ mod tests {
 
         files.insert(file1_path.clone(), PackageFile::File);
         files.insert(file2_path.clone(), PackageFile::File);
-        files.insert(
-            symlink1_path.clone(),
-            PackageFile::Symlink(file1_path.clone()),
-        );
-        files.insert(
-            symlink2_path.clone(),
-            PackageFile::Symlink(missing_target.clone()),
-        );
+        files.insert(symlink1_path.clone(), symlink(file1_path.clone()));
+        files.insert(symlink2_path.clone(), symlink(missing_target.clone()));
 
         let package = create_test_package(files);
         let resolver = SymlinkResolver::new(&package);
@@ -265,17 +352,17 @@ mod tests {
         assert_eq!(results.len(), 2);
         // Check symlink1 resolves to file1
         match results.get(symlink1_path.as_path()) {
-            Some(SymlinkResolutionResult::Found(target)) => {
+            Some(SymlinkResolutionResult::Found { target, .. }) => {
                 assert_eq!(*target, file1_path.as_path());
             }
             _ => panic!("Expected Found result for symlink1"),
         }
         // Check symlink2 is not found
         match results.get(symlink2_path.as_path()) {
-            Some(SymlinkResolutionResult::NotFound(target)) => {
+            Some(SymlinkResolutionResult::SystemDependency { target, .. }) => {
                 assert_eq!(target, &missing_target);
             }
-            _ => panic!("Expected NotFound result for symlink2"),
+            _ => panic!("Expected SystemDependency result for symlink2"),
         }
     }
 
@@ -312,32 +399,26 @@ mod tests {
         let symlink_b_path = PathBuf::from("/usr/bin/B");
         let missing_target = PathBuf::from("/usr/lib/missing.so");
 
-        files.insert(
-            symlink_a_path.clone(),
-            PackageFile::Symlink(symlink_b_path.clone()),
-        );
-        files.insert(
-            symlink_b_path.clone(),
-            PackageFile::Symlink(missing_target.clone()),
-        );
+        files.insert(symlink_a_path.clone(), symlink(symlink_b_path.clone()));
+        files.insert(symlink_b_path.clone(), symlink(missing_target.clone()));
 
         let package = create_test_package(files);
         let resolver = SymlinkResolver::new(&package);
         let results = resolver.symlinks();
 
         assert_eq!(results.len(), 2);
-        // Both should result in NotFound since the chain ends in a missing file
+        // Both should result in SystemDependency since the chain ends in a missing file
         match results.get(symlink_a_path.as_path()) {
-            Some(SymlinkResolutionResult::NotFound(target)) => {
+            Some(SymlinkResolutionResult::SystemDependency { target, .. }) => {
                 assert_eq!(target, &missing_target);
             }
-            _ => panic!("Expected NotFound result for A"),
+            _ => panic!("Expected SystemDependency result for A"),
         }
         match results.get(symlink_b_path.as_path()) {
-            Some(SymlinkResolutionResult::NotFound(target)) => {
+            Some(SymlinkResolutionResult::SystemDependency { target, .. }) => {
                 assert_eq!(target, &missing_target);
             }
-            _ => panic!("Expected NotFound result for B"),
+            _ => panic!("Expected SystemDependency result for B"),
         }
     }
 }
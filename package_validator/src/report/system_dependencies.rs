@@ -2,17 +2,41 @@
 // This file is part of Checkmk (https://checkmk.com). It is subject to the terms and
 // conditions defined in the file COPYING, which is part of this source code package.
 
-//! Resolves system dependencies using exact name matching from a configuration file.
+//! Resolves system dependencies by matching dependency names against a configuration file of
+//! exact names and/or glob patterns. The same file may also declare per-provider symbol version
+//! baselines (`PROVIDER <= VERSION`), overriding the general `--max-glibc` baseline.
 
 use anyhow::{Context, Result};
-use std::collections::HashSet;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::Path;
 
-/// Resolves system dependencies by matching exact dependency names.
+use super::symbol_versions::VersionBaseline;
+
+/// Resolves system dependencies by matching dependency names, either exactly, against a glob
+/// pattern (e.g. `libm.so.*`), or -- when soname normalization is enabled -- by treating a bare
+/// `libfoo.so` entry as matching any versioned `libfoo.so.N[.M...]` the dynamic linker would
+/// also accept, so maintainers don't have to enumerate every versioned soname by hand.
+///
+/// `matching_rule`/`contains` check exact names first, then glob patterns, then (if enabled)
+/// normalized bare sonames, in that order -- so a more specific rule always wins over a looser
+/// one that happens to also match.
 #[derive(Default)]
 pub struct SystemDependencies {
-    dependencies: HashSet<String>,
+    /// Lines with no glob metacharacters, matched by plain `HashSet` lookup.
+    exact: HashSet<String>,
+    /// Lines containing `*`, `?`, `[`, or `]`, compiled once into a `GlobSet`.
+    globs: GlobSet,
+    /// The original pattern text for each entry in `globs`, indexed the same way `GlobSet`
+    /// reports its matches, so a match can be reported back as the rule that fired.
+    glob_patterns: Vec<String>,
+    /// Per-provider symbol version baselines declared as `PROVIDER <= VERSION` lines (e.g.
+    /// `GLIBC <= 2.17`), overriding the general `--max-glibc` baseline for that provider.
+    version_baselines: HashMap<String, VersionBaseline>,
+    /// Whether a bare `exact` entry like `libfoo.so` should also match versioned sonames like
+    /// `libfoo.so.6` or `libfoo.so.6.0`. Off by default: see `with_soname_normalization`.
+    soname_normalization: bool,
 }
 
 impl SystemDependencies {
@@ -24,13 +48,51 @@ impl SystemDependencies {
         Self::default()
     }
 
-    /// Create a new `SystemDependencyResolver` from a file containing exact dependency names.
+    /// Build from an explicit list of dependency names and/or glob patterns, with no version
+    /// baselines -- e.g. a `--ignore-dependency` allow-list collected from CLI arguments rather
+    /// than read from a system-deps file.
     ///
-    /// Each line in the file is treated as an exact dependency name. Empty lines and lines
-    /// starting with `#` are ignored.
+    /// # Errors
+    /// Returns an error if any pattern is not a valid glob pattern.
+    pub(crate) fn from_patterns(patterns: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut exact = HashSet::new();
+        let mut glob_patterns = Vec::new();
+        let mut builder = GlobSetBuilder::new();
+
+        for pattern in patterns {
+            if Self::is_glob_pattern(&pattern) {
+                let glob = Glob::new(&pattern)
+                    .with_context(|| format!("Invalid glob pattern: {pattern}"))?;
+                builder.add(glob);
+                glob_patterns.push(pattern);
+            } else {
+                exact.insert(pattern);
+            }
+        }
+
+        let globs = builder
+            .build()
+            .with_context(|| "Failed to build glob set for dependency patterns")?;
+
+        Ok(Self {
+            exact,
+            globs,
+            glob_patterns,
+            version_baselines: HashMap::new(),
+            soname_normalization: false,
+        })
+    }
+
+    /// Create a new `SystemDependencyResolver` from a file containing dependency names and/or
+    /// glob patterns, one per line.
+    ///
+    /// A line is treated as a symbol version baseline if it's of the form `PROVIDER <= VERSION`
+    /// (e.g. `GLIBC <= 2.17`); as a glob pattern if it contains `*`, `?`, `[`, or `]`; otherwise
+    /// it's matched exactly. Empty lines and lines starting with `#` are ignored.
     ///
     /// # Errors
-    /// Returns an error if the file cannot be read.
+    /// Returns an error if the file cannot be read, a baseline line's version is invalid, or a
+    /// non-baseline line is not a valid glob pattern.
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
         let content = fs::read_to_string(path.as_ref()).with_context(|| {
             format!(
@@ -39,26 +101,132 @@ impl SystemDependencies {
             )
         })?;
 
-        let dependencies: HashSet<String> = content
+        let mut exact = HashSet::new();
+        let mut glob_patterns = Vec::new();
+        let mut version_baselines = HashMap::new();
+        let mut builder = GlobSetBuilder::new();
+
+        for line in content
             .lines()
             .map(str::trim)
             .filter(|line| !line.is_empty() && !line.starts_with('#'))
-            .map(std::string::ToString::to_string)
-            .collect();
+        {
+            if let Some((provider, version)) = Self::parse_version_baseline(line) {
+                let baseline = VersionBaseline::parse(version).with_context(|| {
+                    format!("Invalid version baseline in system deps file: {line}")
+                })?;
+                version_baselines.insert(provider.to_string(), baseline);
+            } else if Self::is_glob_pattern(line) {
+                let glob = Glob::new(line).with_context(|| {
+                    format!("Invalid glob pattern in system deps file: {line}")
+                })?;
+                builder.add(glob);
+                glob_patterns.push(line.to_string());
+            } else {
+                exact.insert(line.to_string());
+            }
+        }
 
-        Ok(Self { dependencies })
+        let globs = builder
+            .build()
+            .with_context(|| "Failed to build glob set for system deps file")?;
+
+        Ok(Self {
+            exact,
+            globs,
+            glob_patterns,
+            version_baselines,
+            soname_normalization: false,
+        })
     }
 
-    /// Get the system dependencies.
+    /// Enable soname normalization: a bare `exact` entry like `libfoo.so` (no version suffix,
+    /// no glob metacharacters) then also matches any versioned soname the dynamic linker would
+    /// treat as an instance of it, e.g. `libfoo.so.6` or `libfoo.so.6.0`. Checked only after
+    /// exact and glob matching both fail, so a more specific rule still takes precedence.
     #[must_use]
-    pub(crate) fn dependencies(&self) -> &HashSet<String> {
-        &self.dependencies
+    pub fn with_soname_normalization(mut self, enabled: bool) -> Self {
+        self.soname_normalization = enabled;
+        self
+    }
+
+    /// Parse a `PROVIDER <= VERSION` line (e.g. `"GLIBC <= 2.17"`) into its provider and version
+    /// text. Returns `None` for any line without a `<=`, so ordinary dependency name/glob lines
+    /// are left alone.
+    fn parse_version_baseline(line: &str) -> Option<(&str, &str)> {
+        let (provider, version) = line.split_once("<=")?;
+        Some((provider.trim(), version.trim()))
+    }
+
+    /// Whether a system-deps line should be compiled as a glob pattern rather than matched
+    /// exactly, i.e. whether it contains any glob metacharacter.
+    fn is_glob_pattern(line: &str) -> bool {
+        line.contains(['*', '?', '[', ']'])
     }
 
-    /// Check if a dependency name exactly matches any of the system dependencies.
+    /// Check if a dependency name matches any of the system dependencies, either exactly or
+    /// via a glob pattern.
     #[must_use]
     pub(crate) fn contains(&self, dependency: &str) -> bool {
-        self.dependencies.contains(dependency)
+        self.matching_rule(dependency).is_some()
+    }
+
+    /// Find the rule that matches `dependency`: the dependency name itself for an exact match,
+    /// the original glob pattern text for a pattern match, or (if soname normalization is
+    /// enabled) the bare soname entry it was normalized from. Checked in that order: exact,
+    /// then glob, then normalized.
+    #[must_use]
+    pub(crate) fn matching_rule(&self, dependency: &str) -> Option<&str> {
+        if let Some(exact) = self.exact.get(dependency) {
+            return Some(exact.as_str());
+        }
+        if let Some(index) = self.globs.matches(dependency).into_iter().next() {
+            return Some(self.glob_patterns[index].as_str());
+        }
+        if self.soname_normalization {
+            return self
+                .exact
+                .iter()
+                .find(|entry| Self::is_bare_soname(entry) && Self::is_versioned_variant(entry, dependency))
+                .map(String::as_str);
+        }
+        None
+    }
+
+    /// Whether `entry` is an unversioned soname (e.g. `libfoo.so`, not `libfoo.so.6`) and so is
+    /// eligible to match versioned variants under soname normalization.
+    fn is_bare_soname(entry: &str) -> bool {
+        entry.ends_with(".so")
+    }
+
+    /// Whether `dependency` is a versioned instance of the bare soname `entry`, i.e.
+    /// `{entry}.N[.M...]` where every dot-separated segment after `entry` is all-digits.
+    fn is_versioned_variant(entry: &str, dependency: &str) -> bool {
+        dependency
+            .strip_prefix(entry)
+            .and_then(|suffix| suffix.strip_prefix('.'))
+            .is_some_and(|version| {
+                !version.is_empty()
+                    && version
+                        .split('.')
+                        .all(|segment| !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit()))
+            })
+    }
+
+    /// Iterate over every declared rule (exact names and glob pattern text), so callers can
+    /// diff the declared set against what's actually used.
+    pub(crate) fn declared_rules(&self) -> impl Iterator<Item = &str> {
+        self.exact
+            .iter()
+            .map(String::as_str)
+            .chain(self.glob_patterns.iter().map(String::as_str))
+    }
+
+    /// Get the declared per-provider symbol version baselines (e.g. `GLIBC` -> `2.17`), as
+    /// declared by any `PROVIDER <= VERSION` lines in the system-dependencies file.
+    #[must_use]
+    pub(crate) fn version_baselines(&self) -> &HashMap<String, VersionBaseline> {
+        &self.version_baselines
     }
 }
 
@@ -138,4 +306,212 @@ mod tests {
         assert!(dependencies.contains("libc.so.6"));
         assert!(dependencies.contains("libpthread.so.0"));
     }
+
+    #[test]
+    fn test_glob_pattern_matches_versioned_sonames() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "libm.so.*").unwrap();
+        file.flush().unwrap();
+
+        let dependencies = SystemDependencies::from_file(file.path()).unwrap();
+        assert!(dependencies.contains("libm.so.6"));
+        assert!(dependencies.contains("libm.so.6.0"));
+        assert!(!dependencies.contains("libm.so"));
+        assert!(!dependencies.contains("libc.so.6"));
+    }
+
+    #[test]
+    fn test_glob_pattern_with_character_class() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "libstdc++.so.[0-9]*").unwrap();
+        file.flush().unwrap();
+
+        let dependencies = SystemDependencies::from_file(file.path()).unwrap();
+        assert!(dependencies.contains("libstdc++.so.6"));
+        assert!(!dependencies.contains("libstdc++.so"));
+    }
+
+    #[test]
+    fn test_exact_and_glob_patterns_combined() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "libm.so.*").unwrap();
+        writeln!(file, "libc.so.6").unwrap();
+        file.flush().unwrap();
+
+        let dependencies = SystemDependencies::from_file(file.path()).unwrap();
+        assert!(dependencies.contains("libm.so.6"));
+        assert!(dependencies.contains("libc.so.6"));
+        assert!(!dependencies.contains("libc.so.6.1"));
+    }
+
+    #[test]
+    fn test_matching_rule_exact() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "libc.so.6").unwrap();
+        file.flush().unwrap();
+
+        let dependencies = SystemDependencies::from_file(file.path()).unwrap();
+        assert_eq!(dependencies.matching_rule("libc.so.6"), Some("libc.so.6"));
+        assert_eq!(dependencies.matching_rule("libm.so.6"), None);
+    }
+
+    #[test]
+    fn test_matching_rule_glob_returns_pattern_not_literal_name() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "libm.so.*").unwrap();
+        file.flush().unwrap();
+
+        let dependencies = SystemDependencies::from_file(file.path()).unwrap();
+        assert_eq!(dependencies.matching_rule("libm.so.6"), Some("libm.so.*"));
+    }
+
+    #[test]
+    fn test_declared_rules_includes_exact_and_glob_entries() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "libc.so.6").unwrap();
+        writeln!(file, "libm.so.*").unwrap();
+        file.flush().unwrap();
+
+        let dependencies = SystemDependencies::from_file(file.path()).unwrap();
+        let mut rules: Vec<&str> = dependencies.declared_rules().collect();
+        rules.sort_unstable();
+        assert_eq!(rules, vec!["libc.so.6", "libm.so.*"]);
+    }
+
+    #[test]
+    fn test_declared_rules_empty_for_empty_resolver() {
+        let dependencies = SystemDependencies::empty();
+        assert_eq!(dependencies.declared_rules().count(), 0);
+    }
+
+    #[test]
+    fn test_invalid_glob_pattern_errors() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "libm.so.[").unwrap();
+        file.flush().unwrap();
+
+        assert!(SystemDependencies::from_file(file.path()).is_err());
+    }
+
+    #[test]
+    fn test_version_baseline_lines_are_parsed_per_provider() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "GLIBC <= 2.17").unwrap();
+        writeln!(file, "GLIBCXX <= 3.4.19").unwrap();
+        file.flush().unwrap();
+
+        let dependencies = SystemDependencies::from_file(file.path()).unwrap();
+        let baselines = dependencies.version_baselines();
+        assert_eq!(baselines.get("GLIBC").unwrap().to_string(), "2.17");
+        assert_eq!(baselines.get("GLIBCXX").unwrap().to_string(), "3.4.19");
+    }
+
+    #[test]
+    fn test_version_baseline_lines_are_not_treated_as_dependency_rules() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "GLIBC <= 2.17").unwrap();
+        file.flush().unwrap();
+
+        let dependencies = SystemDependencies::from_file(file.path()).unwrap();
+        assert!(!dependencies.contains("GLIBC"));
+        assert_eq!(dependencies.declared_rules().count(), 0);
+    }
+
+    #[test]
+    fn test_invalid_version_baseline_errors() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "GLIBC <= not-a-version").unwrap();
+        file.flush().unwrap();
+
+        let result = SystemDependencies::from_file(file.path());
+        assert!(result.is_err());
+        assert!(result
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("Invalid version baseline"));
+    }
+
+    #[test]
+    fn test_empty_resolver_has_no_version_baselines() {
+        let dependencies = SystemDependencies::empty();
+        assert!(dependencies.version_baselines().is_empty());
+    }
+
+    #[test]
+    fn test_from_patterns_matches_exact_and_glob_entries() {
+        let dependencies =
+            SystemDependencies::from_patterns(["libfoo.so.1".to_string(), "libbar.*".to_string()])
+                .unwrap();
+        assert!(dependencies.contains("libfoo.so.1"));
+        assert!(dependencies.contains("libbar.so.2"));
+        assert!(!dependencies.contains("libbaz.so"));
+    }
+
+    #[test]
+    fn test_from_patterns_rejects_invalid_glob() {
+        let error = SystemDependencies::from_patterns(["[".to_string()]).unwrap_err();
+        assert!(error.to_string().contains("Invalid glob pattern"));
+    }
+
+    #[test]
+    fn test_soname_normalization_disabled_by_default() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "libfoo.so").unwrap();
+        file.flush().unwrap();
+
+        let dependencies = SystemDependencies::from_file(file.path()).unwrap();
+        assert!(dependencies.contains("libfoo.so"));
+        assert!(!dependencies.contains("libfoo.so.6"));
+    }
+
+    #[test]
+    fn test_soname_normalization_matches_versioned_sonames() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "libfoo.so").unwrap();
+        file.flush().unwrap();
+
+        let dependencies = SystemDependencies::from_file(file.path())
+            .unwrap()
+            .with_soname_normalization(true);
+        assert!(dependencies.contains("libfoo.so"));
+        assert!(dependencies.contains("libfoo.so.6"));
+        assert!(dependencies.contains("libfoo.so.6.0"));
+        assert!(!dependencies.contains("libfoobar.so.6"));
+        assert!(!dependencies.contains("libfoo.so.6a"));
+    }
+
+    #[test]
+    fn test_soname_normalization_does_not_override_more_specific_rules() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "libfoo.so").unwrap();
+        writeln!(file, "libfoo.so.6").unwrap();
+        file.flush().unwrap();
+
+        let dependencies = SystemDependencies::from_file(file.path())
+            .unwrap()
+            .with_soname_normalization(true);
+        // The exact libfoo.so.6 entry wins over normalizing the bare libfoo.so entry, which
+        // still covers versions with no more specific rule.
+        assert_eq!(
+            dependencies.matching_rule("libfoo.so.6"),
+            Some("libfoo.so.6")
+        );
+        assert_eq!(dependencies.matching_rule("libfoo.so.7"), Some("libfoo.so"));
+    }
+
+    #[test]
+    fn test_soname_normalization_ignores_glob_entries() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "libfoo.so.*").unwrap();
+        file.flush().unwrap();
+
+        let dependencies = SystemDependencies::from_file(file.path())
+            .unwrap()
+            .with_soname_normalization(true);
+        // Already matched by the glob, but confirms a glob-style entry isn't also treated as
+        // a bare soname eligible for normalization.
+        assert!(dependencies.contains("libfoo.so.6"));
+        assert!(!dependencies.contains("libfoo.so"));
+    }
 }
@@ -0,0 +1,153 @@
+// Copyright (C) 2026 Checkmk GmbH - License: GNU General Public License v2
+// This file is part of Checkmk (https://checkmk.com). It is subject to the terms and
+// conditions defined in the file COPYING, which is part of this source code package.
+
+//! Cross-checks a DEB package's declared `Depends`/`Recommends` fields (from its control file)
+//! against the `DT_NEEDED` SONAMEs actually referenced by its ELFs, so maintainers can see when
+//! a package's declared dependency closure has drifted from its real runtime linkage.
+
+use serde::Serialize;
+use std::collections::HashSet;
+
+use crate::package::{ControlMetadata, PackageElfs};
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct Totals {
+    // DT_NEEDED sonames referenced by an ELF in the package that no declared Depends/Recommends
+    // entry appears to cover.
+    pub(crate) undeclared: usize,
+    // Declared Depends/Recommends entries that don't appear to cover any DT_NEEDED soname
+    // actually referenced by an ELF in the package.
+    pub(crate) superfluous: usize,
+}
+
+impl Totals {
+    // `None` for non-DEB packages (or DEB packages whose control file couldn't be parsed), in
+    // which case there's nothing to cross-check against.
+    pub(crate) fn calculate(elfs: &PackageElfs, control_metadata: Option<&ControlMetadata>) -> Self {
+        let Some(control_metadata) = control_metadata else {
+            return Self::default();
+        };
+
+        let needed: HashSet<&str> = elfs
+            .values()
+            .flat_map(|elf| elf.dependencies())
+            .map(String::as_str)
+            .collect();
+        let declared: HashSet<&str> = control_metadata
+            .depends
+            .iter()
+            .chain(&control_metadata.recommends)
+            .map(String::as_str)
+            .collect();
+
+        let undeclared = needed
+            .iter()
+            .filter(|soname| !declared.iter().any(|pkg| Self::package_covers_soname(pkg, soname)))
+            .count();
+        let superfluous = declared
+            .iter()
+            .filter(|pkg| !needed.iter().any(|soname| Self::package_covers_soname(pkg, soname)))
+            .count();
+
+        Self {
+            undeclared,
+            superfluous,
+        }
+    }
+
+    /// Heuristic match between a declared Debian package name (e.g. `libssl1.1`) and a
+    /// `DT_NEEDED` soname (e.g. `libssl.so.1.1`): strip both down to their `lib<name>` stem and
+    /// compare. Debian's lib-package naming convention embeds the SONAME's ABI version in the
+    /// package name, but not in a way that can be recovered exactly, so this only catches the
+    /// common case where the stems match.
+    fn package_covers_soname(package: &str, soname: &str) -> bool {
+        let package_stem = package
+            .trim_start_matches("lib")
+            .trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+        let soname_stem = soname.trim_start_matches("lib").split(".so").next().unwrap_or(soname);
+        !package_stem.is_empty() && package_stem.eq_ignore_ascii_case(soname_stem)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::package::Elf;
+    use std::path::{Path, PathBuf};
+
+    fn control_metadata(depends: &[&str], recommends: &[&str]) -> ControlMetadata {
+        ControlMetadata {
+            package: "myapp".to_string(),
+            version: "1.0".to_string(),
+            depends: depends.iter().map(|s| (*s).to_string()).collect(),
+            recommends: recommends.iter().map(|s| (*s).to_string()).collect(),
+        }
+    }
+
+    fn elf_needing(dependencies: &[&str]) -> Elf {
+        Elf::new_for_testing_with_dependencies(
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            dependencies.iter().map(|d| (*d).to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn test_calculate_with_no_control_metadata_is_default() {
+        let path = PathBuf::from("/usr/bin/myapp");
+        let elf = elf_needing(&["libfoo.so.1"]);
+        let mut elfs: PackageElfs = PackageElfs::default();
+        elfs.insert(path.as_path(), &elf);
+
+        let totals = Totals::calculate(&elfs, None);
+        assert_eq!(totals, Totals::default());
+    }
+
+    #[test]
+    fn test_calculate_matches_declared_dependency_to_needed_soname() {
+        let path = PathBuf::from("/usr/bin/myapp");
+        let elf = elf_needing(&["libssl.so.1.1"]);
+        let mut elfs: PackageElfs = PackageElfs::default();
+        elfs.insert(path.as_path(), &elf);
+        let control = control_metadata(&["libssl1.1"], &[]);
+
+        let totals = Totals::calculate(&elfs, Some(&control));
+        assert_eq!(totals.undeclared, 0);
+        assert_eq!(totals.superfluous, 0);
+    }
+
+    #[test]
+    fn test_calculate_detects_undeclared_needed_library() {
+        let path = PathBuf::from("/usr/bin/myapp");
+        let elf = elf_needing(&["libssl.so.1.1"]);
+        let mut elfs: PackageElfs = PackageElfs::default();
+        elfs.insert(path.as_path(), &elf);
+        let control = control_metadata(&[], &[]);
+
+        let totals = Totals::calculate(&elfs, Some(&control));
+        assert_eq!(totals.undeclared, 1);
+        assert_eq!(totals.superfluous, 0);
+    }
+
+    #[test]
+    fn test_calculate_detects_superfluous_declared_dependency() {
+        let path = PathBuf::from("/usr/bin/myapp");
+        let elf = elf_needing(&[]);
+        let mut elfs: PackageElfs = PackageElfs::default();
+        elfs.insert(path.as_path(), &elf);
+        let control = control_metadata(&["libssl1.1"], &[]);
+
+        let totals = Totals::calculate(&elfs, Some(&control));
+        assert_eq!(totals.undeclared, 0);
+        assert_eq!(totals.superfluous, 1);
+    }
+
+    #[test]
+    fn test_package_covers_soname_strips_version_suffixes() {
+        assert!(Totals::package_covers_soname("libssl1.1", "libssl.so.1.1"));
+        assert!(Totals::package_covers_soname("libc6", "libc.so.6"));
+        assert!(!Totals::package_covers_soname("libfoo1", "libbar.so.1"));
+    }
+}
@@ -15,7 +15,16 @@ pub(crate) struct Totals {
     pub(crate) missing_unique: usize,
     pub(crate) found: usize,
     pub(crate) found_unique: usize,
+    // Found dependencies resolved to a file shipped inside the package itself (via RPATH/RUNPATH,
+    // `$ORIGIN`, or a well-known/discovered library directory), as opposed to ones assumed to be
+    // satisfied by the host's own copy. A package with `missing > 0` but `resolved_in_package ==
+    // 0` is a strong signal of a broken rpath.
+    pub(crate) resolved_in_package: usize,
     pub(crate) error: usize,
+    // Found in the package, but missing a symbol version (`.gnu.version_d`) the dependent
+    // requires (`.gnu.version_r`) -- the "built against a newer glibc/libstdc++" class of
+    // breakage a plain presence check misses.
+    pub(crate) version_unsatisfied: usize,
     pub(crate) system: usize,
     pub(crate) package: usize,
     pub(crate) unknown: usize,
@@ -41,8 +50,14 @@ impl Totals {
                         DependencyStatus::Found => {
                             totals.found += 1;
                             found_unique.insert(*dependency);
+                            if result.kind == DependencyKind::Package {
+                                totals.resolved_in_package += 1;
+                            }
                         }
                         DependencyStatus::Error(_) => totals.error += 1,
+                        DependencyStatus::VersionUnsatisfied { .. } => {
+                            totals.version_unsatisfied += 1;
+                        }
                     }
                     match result.kind {
                         DependencyKind::System => totals.system += 1,
@@ -66,15 +81,19 @@ impl Add for Totals {
     fn add(self, other: Self) -> Self {
         let missing = self.missing + other.missing;
         let found = self.found + other.found;
+        let resolved_in_package = self.resolved_in_package + other.resolved_in_package;
         let error = self.error + other.error;
+        let version_unsatisfied = self.version_unsatisfied + other.version_unsatisfied;
         let package = self.package + other.package;
         let system = self.system + other.system;
         let unknown = self.unknown + other.unknown;
-        let total = missing + found + error;
+        let total = missing + found + error + version_unsatisfied;
         Self {
             missing,
             found,
+            resolved_in_package,
             error,
+            version_unsatisfied,
             system,
             package,
             unknown,
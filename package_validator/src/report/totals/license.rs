@@ -0,0 +1,220 @@
+// Copyright (C) 2026 Checkmk GmbH - License: GNU General Public License v2
+// This file is part of Checkmk (https://checkmk.com). It is subject to the terms and
+// conditions defined in the file COPYING, which is part of this source code package.
+
+//! Normalizes every license/copyright artifact found in a package into a single SPDX
+//! expression, so compliance tooling can see at a glance what's shipped inside.
+
+use serde::{Serialize, Serializer};
+use spdx_expression::SpdxExpression;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use crate::package::{PackageFile, PackageFiles};
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Totals {
+    pub(crate) expression: Option<SpdxExpression>,
+    // License/copyright artifacts found in the package from which no license could be detected,
+    // e.g. a bare `LICENSE` file whose text doesn't match any of our heuristics.
+    pub(crate) files_without_license: usize,
+}
+
+impl Serialize for Totals {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("Totals", 2)?;
+        state.serialize_field(
+            "expression",
+            &self.expression.as_ref().map(ToString::to_string),
+        )?;
+        state.serialize_field("files_without_license", &self.files_without_license)?;
+        state.end()
+    }
+}
+
+impl Totals {
+    pub(crate) fn calculate(files: &PackageFiles) -> Self {
+        let mut distinct_licenses = BTreeSet::new();
+        let mut files_without_license = 0usize;
+
+        for (path, file) in files {
+            let PackageFile::License(text) = file else {
+                continue;
+            };
+            let detected = detect_licenses(path, text);
+            if detected.is_empty() {
+                files_without_license += 1;
+            } else {
+                distinct_licenses.extend(detected);
+            }
+        }
+
+        let expression = if distinct_licenses.is_empty() {
+            None
+        } else {
+            let joined = distinct_licenses
+                .into_iter()
+                .collect::<Vec<_>>()
+                .join(" AND ");
+            SpdxExpression::parse(&joined).ok()
+        };
+
+        Self {
+            expression,
+            files_without_license,
+        }
+    }
+}
+
+/// Detect the distinct SPDX license identifiers referenced by a single license/copyright
+/// artifact: every per-file `License:` field of a Debian machine-readable copyright file, or a
+/// single identifier guessed from the text of a bare `LICENSE`/`COPYING` file.
+fn detect_licenses(path: &Path, text: &str) -> Vec<String> {
+    let is_debian_copyright = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.eq_ignore_ascii_case("copyright"));
+    if is_debian_copyright {
+        parse_debian_copyright(text)
+    } else {
+        heuristic_license_from_text(text).into_iter().collect()
+    }
+}
+
+/// Parse a Debian machine-readable copyright file (DEP-5): a series of stanzas separated by
+/// blank lines, each with `Key: Value` fields, where `License:` may repeat once per `Files:`
+/// stanza. Returns the distinct short license names found across all stanzas.
+fn parse_debian_copyright(text: &str) -> Vec<String> {
+    let mut licenses = BTreeSet::new();
+    for stanza in text.split("\n\n") {
+        for line in stanza.lines() {
+            let Some(value) = line.strip_prefix("License:") else {
+                continue;
+            };
+            // The short name is the first word; anything after it (or on following indented
+            // lines) is the license's full body text, which we don't need here.
+            if let Some(short_name) = value.trim().split_whitespace().next() {
+                licenses.insert(short_name.to_string());
+            }
+        }
+    }
+    licenses.into_iter().collect()
+}
+
+/// Guess an SPDX identifier from the body text of a bare `LICENSE`/`COPYING` file by looking
+/// for a handful of unambiguous phrases. Conservative by design: unrecognized license text
+/// returns `None` rather than a guess, so it's counted as undetectable instead of misreported.
+fn heuristic_license_from_text(text: &str) -> Option<String> {
+    if text.contains("GNU GENERAL PUBLIC LICENSE") {
+        return Some(gpl_family_version(text, "GPL"));
+    }
+    if text.contains("GNU LESSER GENERAL PUBLIC LICENSE") {
+        return Some(gpl_family_version(text, "LGPL"));
+    }
+    const SIGNATURES: &[(&str, &str)] = &[
+        ("MIT License", "MIT"),
+        ("Apache License", "Apache-2.0"),
+        ("Mozilla Public License", "MPL-2.0"),
+        ("ISC License", "ISC"),
+        ("BSD", "BSD-3-Clause"),
+    ];
+    SIGNATURES
+        .iter()
+        .find(|(signature, _)| text.contains(signature))
+        .map(|(_, spdx_id)| (*spdx_id).to_string())
+}
+
+/// Narrow a GPL-family license text down to its version, falling back to the bare family name
+/// (an invalid SPDX identifier on its own, but still surfaced rather than silently dropped) if
+/// no version string is found.
+fn gpl_family_version(text: &str, family: &str) -> String {
+    if text.contains("Version 3") {
+        format!("{family}-3.0-only")
+    } else if text.contains("Version 2") {
+        format!("{family}-2.0-only")
+    } else {
+        family.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn files_with(entries: Vec<(&str, PackageFile)>) -> PackageFiles {
+        entries
+            .into_iter()
+            .map(|(path, file)| (PathBuf::from(path), file))
+            .collect()
+    }
+
+    #[test]
+    fn test_calculate_with_no_license_files_returns_none() {
+        let files = files_with(vec![("/usr/bin/myapp", PackageFile::File)]);
+        let totals = Totals::calculate(&files);
+        assert_eq!(totals.expression, None);
+        assert_eq!(totals.files_without_license, 0);
+    }
+
+    #[test]
+    fn test_calculate_detects_mit_license_from_bare_file() {
+        let files = files_with(vec![(
+            "/opt/myapp/LICENSE",
+            PackageFile::License("MIT License\n\nPermission is hereby granted...".to_string()),
+        )]);
+        let totals = Totals::calculate(&files);
+        assert_eq!(totals.expression.unwrap().to_string(), "MIT");
+        assert_eq!(totals.files_without_license, 0);
+    }
+
+    #[test]
+    fn test_calculate_detects_gpl_version_from_bare_file() {
+        let files = files_with(vec![(
+            "/opt/myapp/COPYING",
+            PackageFile::License(
+                "GNU GENERAL PUBLIC LICENSE\nVersion 2, June 1991".to_string(),
+            ),
+        )]);
+        let totals = Totals::calculate(&files);
+        assert_eq!(totals.expression.unwrap().to_string(), "GPL-2.0-only");
+    }
+
+    #[test]
+    fn test_calculate_combines_distinct_licenses_from_debian_copyright() {
+        let text = "Format: https://www.debian.org/doc/packaging-manuals/copyright-format/1.0/\n\
+\n\
+Files: *\n\
+License: MIT\n\
+\n\
+Files: src/vendor/*\n\
+License: Apache-2.0";
+        let files = files_with(vec![(
+            "/usr/share/doc/myapp/copyright",
+            PackageFile::License(text.to_string()),
+        )]);
+        let totals = Totals::calculate(&files);
+        assert_eq!(totals.expression.unwrap().to_string(), "Apache-2.0 AND MIT");
+    }
+
+    #[test]
+    fn test_calculate_counts_undetectable_license_text() {
+        let files = files_with(vec![(
+            "/opt/myapp/LICENSE",
+            PackageFile::License("Some bespoke license text nobody has seen before.".to_string()),
+        )]);
+        let totals = Totals::calculate(&files);
+        assert_eq!(totals.expression, None);
+        assert_eq!(totals.files_without_license, 1);
+    }
+
+    #[test]
+    fn test_parse_debian_copyright_deduplicates_repeated_license() {
+        let text = "Files: *\nLicense: MIT\n\nFiles: other/*\nLicense: MIT";
+        assert_eq!(parse_debian_copyright(text), vec!["MIT".to_string()]);
+    }
+}
@@ -4,19 +4,25 @@
 
 //! Statistics calculation modules for ELF files and dependencies.
 
+mod declared_dependencies;
 mod dependencies;
 mod elf;
+mod license;
+mod symbols;
 
 use serde::Serialize;
 
-use crate::package::{PackageElfs, PackageFiles};
+use crate::package::{ControlMetadata, PackageElfs, PackageFiles};
 use crate::report::ReportDependencies;
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub(crate) struct ReportTotals {
     pub(crate) files: usize,
     pub(crate) elfs: elf::Totals,
     pub(crate) dependencies: dependencies::Totals,
+    pub(crate) license: license::Totals,
+    pub(crate) declared_dependencies: declared_dependencies::Totals,
+    pub(crate) symbols: symbols::Totals,
 }
 
 impl ReportTotals {
@@ -25,11 +31,15 @@ impl ReportTotals {
         files: &PackageFiles,
         elfs: &PackageElfs,
         dependencies: &ReportDependencies,
+        control_metadata: Option<&ControlMetadata>,
     ) -> Self {
         Self {
             files: files.len(),
             elfs: elf::Totals::calculate(elfs),
             dependencies: dependencies::Totals::calculate(dependencies),
+            license: license::Totals::calculate(files),
+            declared_dependencies: declared_dependencies::Totals::calculate(elfs, control_metadata),
+            symbols: symbols::Totals::calculate(elfs, dependencies),
         }
     }
 }
@@ -0,0 +1,165 @@
+// Copyright (C) 2026 Checkmk GmbH - License: GNU General Public License v2
+// This file is part of Checkmk (https://checkmk.com). It is subject to the terms and
+// conditions defined in the file COPYING, which is part of this source code package.
+
+//! Statistics for symbol-level dependency resolution: how many of a package's required dynamic
+//! symbols are satisfied by its (transitive) package-shipped `DT_NEEDED` closure. Mirrors
+//! `report::errors::scan_for_missing_symbols`'s traversal exactly (via the shared
+//! `transitive_package_dependencies` helper), so these counts always agree with the
+//! `MissingSymbol` errors actually reported.
+
+use rayon::prelude::*;
+use serde::Serialize;
+use std::ops::Add;
+
+use crate::package::PackageElfs;
+use crate::report::errors::transitive_package_dependencies;
+use crate::report::ReportDependencies;
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct Totals {
+    // Undefined global-bound dynamic symbols across every ELF whose dependency closure could be
+    // fully resolved in-package. Excludes weak undefined symbols, which are never errors.
+    pub(crate) required: usize,
+    pub(crate) missing: usize,
+    // ELFs with at least one undefined global symbol whose dependency closure couldn't be fully
+    // verified (a dependency is a system library, or simply unresolved), so their symbols
+    // couldn't be counted either way.
+    pub(crate) unverifiable: usize,
+}
+
+impl Totals {
+    pub(crate) fn calculate(elfs: &PackageElfs, dependencies: &ReportDependencies) -> Self {
+        elfs.par_iter()
+            .fold(Totals::default, |mut totals, (path, elf)| {
+                let Some(closure) = transitive_package_dependencies(path, dependencies, elfs) else {
+                    if !elf.undefined_symbols().is_empty() {
+                        totals.unverifiable += 1;
+                    }
+                    return totals;
+                };
+                for symbol in elf.undefined_symbols() {
+                    totals.required += 1;
+                    if !closure.iter().any(|dep| dep.exported_symbols().contains(symbol)) {
+                        totals.missing += 1;
+                    }
+                }
+                totals
+            })
+            .reduce(Totals::default, |a, b| a + b)
+    }
+}
+
+impl Add for Totals {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            required: self.required + other.required,
+            missing: self.missing + other.missing,
+            unverifiable: self.unverifiable + other.unverifiable,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::package::Elf;
+    use crate::report::dependency_resolver::{DependencyKind, DependencyResolverResult, DependencyStatus};
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    fn found_in_package(path: &str) -> DependencyResolverResult {
+        DependencyResolverResult::new(
+            DependencyStatus::Found,
+            DependencyKind::Package,
+            Vec::new(),
+            PathBuf::from(path),
+        )
+    }
+
+    fn elf_with_symbols(
+        dependencies: &[&str],
+        exported_symbols: &[&str],
+        undefined_symbols: &[&str],
+    ) -> Elf {
+        Elf::new_for_testing_with_symbols(
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            dependencies.iter().map(|d| (*d).to_string()).collect(),
+            exported_symbols.iter().map(|s| (*s).to_string()).collect(),
+            undefined_symbols.iter().map(|s| (*s).to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn test_calculate_counts_satisfied_and_missing_symbols() {
+        let myapp = elf_with_symbols(&["libfoo.so"], &[], &["needed_symbol", "missing_symbol"]);
+        let libfoo = elf_with_symbols(&[], &["needed_symbol"], &[]);
+        let myapp_path = PathBuf::from("/usr/bin/myapp");
+        let libfoo_path = PathBuf::from("/usr/lib/libfoo.so");
+
+        let mut elfs: PackageElfs = HashMap::new();
+        elfs.insert(myapp_path.as_path(), &myapp);
+        elfs.insert(libfoo_path.as_path(), &libfoo);
+
+        let mut dependencies: ReportDependencies = ReportDependencies::new();
+        dependencies.insert(
+            myapp_path.as_path(),
+            HashMap::from([("libfoo.so", found_in_package("/usr/lib/libfoo.so"))]),
+        );
+
+        let totals = Totals::calculate(&elfs, &dependencies);
+        assert_eq!(totals.required, 2);
+        assert_eq!(totals.missing, 1);
+        assert_eq!(totals.unverifiable, 0);
+    }
+
+    #[test]
+    fn test_calculate_counts_unverifiable_elf_separately() {
+        let myapp = elf_with_symbols(&["libc.so.6"], &[], &["needed_symbol"]);
+        let myapp_path = PathBuf::from("/usr/bin/myapp");
+
+        let mut elfs: PackageElfs = HashMap::new();
+        elfs.insert(myapp_path.as_path(), &myapp);
+
+        let mut dependencies: ReportDependencies = ReportDependencies::new();
+        dependencies.insert(
+            myapp_path.as_path(),
+            HashMap::from([(
+                "libc.so.6",
+                DependencyResolverResult::new(
+                    DependencyStatus::Found,
+                    DependencyKind::System,
+                    Vec::new(),
+                    None,
+                ),
+            )]),
+        );
+
+        let totals = Totals::calculate(&elfs, &dependencies);
+        assert_eq!(totals.required, 0);
+        assert_eq!(totals.missing, 0);
+        assert_eq!(totals.unverifiable, 1);
+    }
+
+    #[test]
+    fn test_add_sums_fields() {
+        let a = Totals {
+            required: 2,
+            missing: 1,
+            unverifiable: 0,
+        };
+        let b = Totals {
+            required: 3,
+            missing: 0,
+            unverifiable: 1,
+        };
+        let sum = a + b;
+        assert_eq!(sum.required, 5);
+        assert_eq!(sum.missing, 1);
+        assert_eq!(sum.unverifiable, 1);
+    }
+}
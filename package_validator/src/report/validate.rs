@@ -2,16 +2,28 @@
 // This file is part of Checkmk (https://checkmk.com). It is subject to the terms and
 // conditions defined in the file COPYING, which is part of this source code package.
 
-//! Validates reports and returns errors for missing or unresolved dependencies.
+//! Validates reports against a `ValidationPolicy` and returns errors for error dependencies or
+//! missing dependencies not tolerated by that policy.
 
-use super::{DependencyStatus, Report};
+use super::policy::ValidationPolicy;
+use super::{DependencyKind, DependencyStatus, Report};
 use anyhow::Result;
+use std::collections::HashSet;
 
-/// Validate the report.
+/// Validate the report against `policy`.
+///
+/// Error dependencies (e.g. symlink cycles) always fail, regardless of policy. Missing
+/// dependencies are first filtered by the policy's ignore-list, then (if
+/// `ValidationPolicy::downgrades_unknown_kind`) by `DependencyKind::Unknown`, and the remaining
+/// count of distinct dependency names is compared against `ValidationPolicy::max_missing_unique`
+/// (0 by default, i.e. any missing dependency fails). Every policy rule that suppresses a
+/// finding, or the threshold that ultimately passed or failed the report, is printed so a clean
+/// exit is auditable.
 ///
 /// # Errors
-/// Returns an error if missing/error dependencies are present.
-pub fn validate_report(report: &Report<'_>) -> Result<()> {
+/// Returns an error if error dependencies are present, or if missing dependencies (after policy
+/// filtering) exceed the configured threshold.
+pub fn validate_report(report: &Report<'_>, policy: &ValidationPolicy) -> Result<()> {
     if report.totals.dependencies.error > 0 {
         for (path, dependencies) in &report.dependencies {
             for (dependency, result) in dependencies {
@@ -25,11 +37,39 @@ pub fn validate_report(report: &Report<'_>) -> Result<()> {
             report.totals.dependencies.error
         ));
     }
-    if report.totals.dependencies.missing > 0 {
+
+    let mut missing_unique: HashSet<&str> = HashSet::new();
+    for dependencies in report.dependencies.values() {
+        for (dependency, result) in dependencies {
+            if !matches!(result.status, DependencyStatus::Missing) {
+                continue;
+            }
+            if let Some(rule) = policy.ignored_rule(dependency) {
+                eprintln!("POLICY: ignoring missing dependency {dependency} (matched ignore rule {rule:?})");
+                continue;
+            }
+            if policy.downgrades_unknown_kind() && result.kind == DependencyKind::Unknown {
+                eprintln!("POLICY: downgrading missing dependency {dependency} to a warning (kind Unknown)");
+                continue;
+            }
+            missing_unique.insert(dependency);
+        }
+    }
+
+    let threshold = policy.max_missing_unique();
+    if missing_unique.len() > threshold {
         return Err(anyhow::anyhow!(
-            "Missing dependencies found in the report: {} missing dependencies",
-            report.totals.dependencies.missing
+            "Missing dependencies found in the report: {} missing dependencies, exceeding policy threshold of {}",
+            missing_unique.len(),
+            threshold
         ));
     }
+    if !missing_unique.is_empty() {
+        eprintln!(
+            "POLICY: {} missing dependencies allowed within threshold of {}",
+            missing_unique.len(),
+            threshold
+        );
+    }
     Ok(())
 }
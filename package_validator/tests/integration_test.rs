@@ -3,8 +3,18 @@
 // conditions defined in the file COPYING, which is part of this source code package.
 use std::path::PathBuf;
 
+use package_validator::fixtures::{build_package, ArtifactKind, ArtifactSpec, PackageFormat, RpathSetting};
 use package_validator::package::Package;
-use package_validator::report::{Report, SystemDependencies};
+use package_validator::report::{Report, SearchConfig, SystemDependencies};
+
+/// A `SearchConfig` rooted at an empty, just-created directory, so `ld.so.conf` and the trusted
+/// default directories never pick up anything from the host running the test suite. The
+/// returned `TempDir` must outlive the `SearchConfig` or the sysroot it points at disappears.
+fn hermetic_search_config() -> (tempfile::TempDir, SearchConfig) {
+    let sysroot = tempfile::tempdir().unwrap();
+    let search_config = SearchConfig::new(sysroot.path(), Vec::new());
+    (sysroot, search_config)
+}
 
 fn get_examples_dir() -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("examples")
@@ -41,9 +51,11 @@ fn test_package_integration_report() {
 
         // Create a system dependencies resolver (empty is OK for testing)
         let system_deps = SystemDependencies::default();
+        let (_sysroot, search_config) = hermetic_search_config();
 
         // Generate report
-        let report = Report::new(&package, &system_deps).expect("Should generate report");
+        let report = Report::new(&package, &system_deps, None, &search_config)
+            .expect("Should generate report");
 
         // Test JSON output
         let json_str = serde_json::to_string(&report).expect("Should serialize report to JSON");
@@ -89,9 +101,11 @@ fn test_package_discovers_dependencies() {
 
         // Create a system dependencies resolver (empty is OK for testing)
         let system_deps = SystemDependencies::default();
+        let (_sysroot, search_config) = hermetic_search_config();
 
         // Generate report and check ELF/dependency info via JSON
-        let report = Report::new(&package, &system_deps).expect("Should generate report");
+        let report = Report::new(&package, &system_deps, None, &search_config)
+            .expect("Should generate report");
         let json: serde_json::Value =
             serde_json::from_str(&serde_json::to_string(&report).unwrap()).unwrap();
 
@@ -145,3 +159,76 @@ fn test_package_discovers_dependencies() {
         eprintln!("Warning: No package fixtures were available for testing");
     }
 }
+
+/// Build a package of `format` containing an executable that `$ORIGIN/../lib`-relative-RUNPATHs
+/// to a shared library shipped alongside it in the same package, plus a second dependency that
+/// nothing in the package or search path provides. Exercises both "found" and "missing"
+/// dependency resolution deterministically, unlike the example-fixture tests above, which depend
+/// on `examples/generate-examples.sh` having been run and skip themselves otherwise.
+fn build_synthetic_package(format: PackageFormat, dest: &std::path::Path) {
+    let artifacts = [
+        ArtifactSpec::new(ArtifactKind::Executable, "usr/bin/hello")
+            .needed("libhello.so.1")
+            .needed("libnowhere.so.1")
+            .rpath(RpathSetting::Runpath("$ORIGIN/../lib".to_string())),
+        ArtifactSpec::new(ArtifactKind::SharedLibrary, "usr/lib/libhello.so.1"),
+    ];
+    build_package(format, "synthetic-hello", "1.0.0", &artifacts, dest)
+        .expect("Should build synthetic fixture package");
+}
+
+#[test]
+fn test_synthetic_deb_package_resolves_and_reports_dependencies() {
+    let dir = tempfile::tempdir().unwrap();
+    let dest = dir.path().join("synthetic-hello.deb");
+    build_synthetic_package(PackageFormat::Deb, &dest);
+
+    let package = Package::new(dest).expect("Should extract synthetic .deb package");
+    let system_deps = SystemDependencies::default();
+    let (_sysroot, search_config) = hermetic_search_config();
+    let report = Report::new(&package, &system_deps, None, &search_config)
+        .expect("Should generate report for synthetic .deb package");
+    let json: serde_json::Value =
+        serde_json::from_str(&serde_json::to_string(&report).unwrap()).unwrap();
+
+    let elfs = &json["totals"]["elfs"];
+    assert_eq!(elfs["total"].as_u64(), Some(2));
+    assert_eq!(elfs["binaries"].as_u64(), Some(1));
+    assert_eq!(elfs["shared_libraries"].as_u64(), Some(1));
+
+    let deps = &json["totals"]["dependencies"];
+    assert_eq!(deps["total"].as_u64(), Some(2));
+    assert_eq!(
+        deps["found"].as_u64(),
+        Some(1),
+        "libhello.so.1 should resolve via the $ORIGIN/../lib RUNPATH to the shipped library"
+    );
+    assert_eq!(
+        deps["missing"].as_u64(),
+        Some(1),
+        "libnowhere.so.1 is neither shipped nor declared as a system dependency"
+    );
+}
+
+#[test]
+fn test_synthetic_rpm_package_resolves_and_reports_dependencies() {
+    let dir = tempfile::tempdir().unwrap();
+    let dest = dir.path().join("synthetic-hello.rpm");
+    build_synthetic_package(PackageFormat::Rpm, &dest);
+
+    let package = Package::new(dest).expect("Should extract synthetic .rpm package");
+    let system_deps = SystemDependencies::default();
+    let (_sysroot, search_config) = hermetic_search_config();
+    let report = Report::new(&package, &system_deps, None, &search_config)
+        .expect("Should generate report for synthetic .rpm package");
+    let json: serde_json::Value =
+        serde_json::from_str(&serde_json::to_string(&report).unwrap()).unwrap();
+
+    let elfs = &json["totals"]["elfs"];
+    assert_eq!(elfs["total"].as_u64(), Some(2));
+
+    let deps = &json["totals"]["dependencies"];
+    assert_eq!(deps["total"].as_u64(), Some(2));
+    assert_eq!(deps["found"].as_u64(), Some(1));
+    assert_eq!(deps["missing"].as_u64(), Some(1));
+}